@@ -0,0 +1,73 @@
+#[macro_use]
+extern crate criterion;
+extern crate noise;
+
+use criterion::{black_box, Criterion};
+use noise::{Fbm, NoiseFn, Perlin, Worley};
+
+criterion_group!(
+    get_many,
+    bench_perlin2_get_64x64,
+    bench_perlin2_get_many_64x64,
+    bench_fbm2_get_64x64,
+    bench_fbm2_get_many_64x64,
+    bench_worley2_get_64x64,
+    bench_worley2_get_many_64x64,
+);
+criterion_main!(get_many);
+
+fn points_64x64() -> Vec<[f64; 2]> {
+    let mut points = Vec::with_capacity(64 * 64);
+    for y in 0..64 {
+        for x in 0..64 {
+            points.push([x as f64 * 0.1, y as f64 * 0.1]);
+        }
+    }
+    points
+}
+
+fn bench_get<N: NoiseFn<f64, 2>>(c: &mut Criterion, name: &str, noise: &N) {
+    let points = points_64x64();
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for point in &points {
+                black_box(noise.get(black_box(*point)));
+            }
+        })
+    });
+}
+
+fn bench_get_many<N: NoiseFn<f64, 2>>(c: &mut Criterion, name: &str, noise: &N) {
+    let points = points_64x64();
+    let mut out = vec![0.0; points.len()];
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            noise.get_many(black_box(&points), &mut out);
+            black_box(&out);
+        })
+    });
+}
+
+fn bench_perlin2_get_64x64(c: &mut Criterion) {
+    bench_get(c, "perlin 2d get (64x64)", &Perlin::new(0));
+}
+
+fn bench_perlin2_get_many_64x64(c: &mut Criterion) {
+    bench_get_many(c, "perlin 2d get_many (64x64)", &Perlin::new(0));
+}
+
+fn bench_fbm2_get_64x64(c: &mut Criterion) {
+    bench_get(c, "fbm 2d get (64x64)", &Fbm::<Perlin>::new(0));
+}
+
+fn bench_fbm2_get_many_64x64(c: &mut Criterion) {
+    bench_get_many(c, "fbm 2d get_many (64x64)", &Fbm::<Perlin>::new(0));
+}
+
+fn bench_worley2_get_64x64(c: &mut Criterion) {
+    bench_get(c, "worley 2d get (64x64)", &Worley::new(0));
+}
+
+fn bench_worley2_get_many_64x64(c: &mut Criterion) {
+    bench_get_many(c, "worley 2d get_many (64x64)", &Worley::new(0));
+}
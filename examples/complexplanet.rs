@@ -1726,7 +1726,7 @@ fn main() {
 
     utils::write_image_to_file(
         &ImageRenderer::new()
-            .set_gradient(ColorGradient::new().build_terrain_gradient())
+            .set_gradient(ColorGradient::terrain())
             .render(&noise_map),
         "unscaledFinalPlanet.png",
     );
@@ -1739,7 +1739,7 @@ fn main() {
 
     utils::write_image_to_file(
         &ImageRenderer::new()
-            .set_gradient(ColorGradient::new().build_terrain_gradient())
+            .set_gradient(ColorGradient::terrain())
             .render(&noise_map),
         "unscaledFinalPlanet_4x_zoom.png",
     );
@@ -1752,14 +1752,14 @@ fn main() {
 
     utils::write_image_to_file(
         &ImageRenderer::new()
-            .set_gradient(ColorGradient::new().build_terrain_gradient())
+            .set_gradient(ColorGradient::terrain())
             .render(&noise_map),
         "unscaledFinalPlanet_16x_zoom.png",
     );
 
     utils::write_image_to_file(
         &ImageRenderer::new()
-            .set_gradient(ColorGradient::new().build_terrain_gradient())
+            .set_gradient(ColorGradient::terrain())
             .render(
                 &SphereMapBuilder::new(unscaledFinalPlanet)
                     .set_size(1024, 1024)
@@ -1,6 +1,6 @@
 extern crate noise;
 
-use noise::{utils::*, Cylinders};
+use noise::{core::spheres::RadialProfile, utils::*, Cylinders};
 
 mod utils;
 
@@ -14,4 +14,19 @@ fn main() {
         &PlaneMapBuilder::new(Cylinders::new().set_frequency(5.0)).build(),
         "cylinders-f5.png",
     );
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(
+            Cylinders::new()
+                .set_frequency(5.0)
+                .set_profile(RadialProfile::Cosine),
+        )
+        .build(),
+        "cylinders-f5-cosine.png",
+    );
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(Cylinders::new().set_profile(RadialProfile::Falloff)).build(),
+        "cylinders-falloff.png",
+    );
 }
@@ -0,0 +1,20 @@
+extern crate noise;
+
+use noise::{utils::*, FlowNoise};
+
+mod utils;
+
+fn main() {
+    for (frame, time) in [0.0, 0.5, 1.0].iter().enumerate() {
+        let noise = FlowNoise::default().set_time(*time);
+
+        utils::write_example_to_file(
+            &PlaneMapBuilder::new(noise)
+                .set_size(512, 512)
+                .set_x_bounds(-4.0, 4.0)
+                .set_y_bounds(-4.0, 4.0)
+                .build(),
+            &format!("flow_noise-{}.png", frame),
+        );
+    }
+}
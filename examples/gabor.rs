@@ -0,0 +1,32 @@
+extern crate noise;
+
+use noise::{utils::*, Gabor, NoiseFn};
+
+mod utils;
+
+fn main() {
+    let builder = |gabor: Gabor| {
+        PlaneMapBuilder::new_fn(|point: [f64; 2]| gabor.get(point))
+            .set_size(512, 512)
+            .set_x_bounds(-8.0, 8.0)
+            .set_y_bounds(-8.0, 8.0)
+            .build()
+    };
+
+    utils::write_example_to_file(&builder(Gabor::default()), "gabor.png");
+
+    utils::write_example_to_file(
+        &builder(Gabor::default().set_bandwidth(0.15)),
+        "gabor-narrow.png",
+    );
+
+    utils::write_example_to_file(
+        &builder(
+            Gabor::default()
+                .set_bandwidth(1.5)
+                .set_frequency(0.75)
+                .set_orientation(0.0),
+        ),
+        "gabor-wide.png",
+    );
+}
@@ -0,0 +1,30 @@
+//! An example of using the `HeteroTerrain` noise function
+
+extern crate noise;
+
+use noise::{utils::*, HeteroTerrain, Perlin, Worley};
+
+mod utils;
+
+fn main() {
+    let hetero_terrain = HeteroTerrain::<Perlin>::default();
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(hetero_terrain).build(),
+        "hetero_terrain_perlin.png",
+    );
+
+    let hetero_terrain = HeteroTerrain::<Worley>::default();
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(hetero_terrain).build(),
+        "hetero_terrain_worley.png",
+    );
+
+    let hetero_terrain = HeteroTerrain::<HeteroTerrain<Perlin>>::default();
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(hetero_terrain).build(),
+        "hetero_terrain_hetero_terrain_perlin.png",
+    );
+}
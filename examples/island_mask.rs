@@ -0,0 +1,32 @@
+//! An example of masking fBm terrain into an island continent
+
+extern crate noise;
+
+use noise::{presets, utils::*, Fbm, Multiply, Perlin};
+
+mod utils;
+
+fn main() {
+    let fbm = Fbm::<Perlin>::default();
+    let mask = presets::island_mask(10.0, 10.0, 2.0);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(mask)
+            .set_size(1000, 1000)
+            .set_x_bounds(-5.0, 5.0)
+            .set_y_bounds(-5.0, 5.0)
+            .build(),
+        "island_mask.png",
+    );
+
+    let island = Multiply::new(fbm, mask);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(island)
+            .set_size(1000, 1000)
+            .set_x_bounds(-5.0, 5.0)
+            .set_y_bounds(-5.0, 5.0)
+            .build(),
+        "island_fbm_perlin.png",
+    );
+}
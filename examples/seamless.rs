@@ -0,0 +1,19 @@
+extern crate noise;
+
+use noise::{utils::*, Fbm, NoiseFn, Perlin, Seamless};
+
+mod utils;
+
+fn main() {
+    let fbm = Fbm::<Perlin>::default();
+    let seamless = Seamless::new(fbm, [4, 4]);
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new_fn(|point| seamless.get(point))
+            .set_size(512, 512)
+            .set_x_bounds(0.0, 4.0)
+            .set_y_bounds(0.0, 4.0)
+            .build(),
+        "seamless.png",
+    );
+}
@@ -1,6 +1,6 @@
 extern crate noise;
 
-use noise::{core::spheres::*, utils::*};
+use noise::{core::spheres::*, utils::*, Spheres};
 
 mod utils;
 
@@ -31,4 +31,18 @@ fn main() {
             .build(),
         "spheres 4d.png",
     );
+
+    utils::write_example_to_file(
+        &PlaneMapBuilder::new(
+            Spheres::new()
+                .set_frequency(2.0)
+                .set_center([1.0, -1.0, 0.5])
+                .set_profile(RadialProfile::Cosine),
+        )
+        .set_size(1024, 1024)
+        .set_x_bounds(-5.0, 5.0)
+        .set_y_bounds(-5.0, 5.0)
+        .build(),
+        "spheres generator.png",
+    );
 }
@@ -0,0 +1,33 @@
+extern crate noise;
+
+use noise::{utils::*, Voronoise};
+
+mod utils;
+
+fn main() {
+    let builder = |voronoise: Voronoise| {
+        PlaneMapBuilder::new(voronoise.set_frequency(4.0))
+            .set_size(512, 512)
+            .build()
+    };
+
+    utils::write_example_to_file(
+        &builder(Voronoise::default().set_jitter(0.0).set_smoothness(0.0)),
+        "voronoise-grid.png",
+    );
+
+    utils::write_example_to_file(
+        &builder(Voronoise::default().set_jitter(0.0).set_smoothness(1.0)),
+        "voronoise-value.png",
+    );
+
+    utils::write_example_to_file(
+        &builder(Voronoise::default().set_jitter(1.0).set_smoothness(0.0)),
+        "voronoise-cells.png",
+    );
+
+    utils::write_example_to_file(
+        &builder(Voronoise::default().set_jitter(1.0).set_smoothness(1.0)),
+        "voronoise-smooth.png",
+    );
+}
@@ -178,4 +178,19 @@ fn main() {
         ReturnType::Distance,
         "worley/4d_chebyshev_distance.png",
     );
+    output_2d(
+        &euclidean,
+        ReturnType::Distance2,
+        "worley/2d_euclidean_distance2.png",
+    );
+    output_2d(
+        &euclidean,
+        ReturnType::Distance2Sub,
+        "worley/2d_euclidean_distance2sub.png",
+    );
+    output_3d(
+        &euclidean,
+        ReturnType::Distance2Sub,
+        "worley/3d_euclidean_distance2sub.png",
+    );
 }
@@ -1,9 +1,14 @@
 pub mod checkerboard;
+pub mod flow;
+pub mod gabor;
 pub mod open_simplex;
 pub mod perlin;
+#[cfg(feature = "simd")]
+pub mod perlin_simd;
 pub mod perlin_surflet;
 pub mod simplex;
 pub mod spheres;
 pub mod super_simplex;
 pub mod value;
+pub mod voronoise;
 pub mod worley;
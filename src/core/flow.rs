@@ -0,0 +1,146 @@
+//! Flow noise, after Perlin and Neyret, "Flow Noise" (2001).
+//!
+//! Flow noise is surflet gradient noise whose gradients rotate over time.
+//! Every lattice point turns its gradient at the same rate, but in its own
+//! direction in 2D and in its own plane in 3D, so the pattern swirls in place
+//! rather than sliding or fading. This suits fire, smoke and water. Each
+//! gradient makes a full turn as `time` advances by 2π, so the noise loops
+//! with that period.
+
+use crate::{
+    gradient,
+    math::vectors::{Vector2, Vector3},
+    permutationtable::NoiseHasher,
+};
+use core::f64::consts::TAU;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+// With unit gradients that may point in any direction, the largest sum of
+// surflets is reached halfway along a cell edge, where both of the edge's
+// gradients point at the sample. It is 81/256 in both 2D and 3D.
+const SCALE_FACTOR: f64 = 256.0 / 81.0;
+
+/// Angle in [0, 2π) picked by the hash of `cell`.
+#[inline(always)]
+fn hashed_angle<NH, const N: usize>(hasher: &NH, cell: [isize; N]) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    TAU * (hasher.hash(&cell) & 0xff) as f64 / 256.0
+}
+
+#[inline(always)]
+pub fn flow_2d<NH>(point: Vector2<f64>, hasher: &NH, time: f64) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    #[inline(always)]
+    fn surflet<NH>(hasher: &NH, corner: Vector2<isize>, distance: Vector2<f64>, time: f64) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        let attn: f64 = 1.0 - distance.magnitude_squared();
+        if attn <= 0.0 {
+            return 0.0;
+        }
+
+        // Half of the gradients turn clockwise and half counterclockwise.
+        let [x, y] = corner.into_array();
+        let spin = if hasher.hash(&[x, y, 1]) & 1 == 0 {
+            1.0
+        } else {
+            -1.0
+        };
+        let (sin, cos) = (hashed_angle(hasher, [x, y]) + spin * time).sin_cos();
+
+        attn.powi(4) * distance.dot(Vector2::new(cos, sin))
+    }
+
+    let corner = point.floor_to_isize();
+    let floored = corner.numcast().unwrap();
+    let distance = point - floored;
+
+    macro_rules! call_surflet(
+        ($x:expr, $y:expr) => {
+            {
+                let offset = Vector2::new($x, $y);
+                surflet(hasher, corner + offset, distance - offset.numcast().unwrap(), time)
+            }
+        }
+    );
+
+    let f00 = call_surflet!(0, 0);
+    let f10 = call_surflet!(1, 0);
+    let f01 = call_surflet!(0, 1);
+    let f11 = call_surflet!(1, 1);
+
+    // Scale to -1..1, clamping away rounding errors.
+    ((f00 + f10 + f01 + f11) * SCALE_FACTOR).clamp(-1.0, 1.0)
+}
+
+#[inline(always)]
+pub fn flow_3d<NH>(point: Vector3<f64>, hasher: &NH, time: f64) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    #[inline(always)]
+    fn surflet<NH>(hasher: &NH, corner: Vector3<isize>, distance: Vector3<f64>, time: f64) -> f64
+    where
+        NH: NoiseHasher + ?Sized,
+    {
+        let attn: f64 = 1.0 - distance.magnitude_squared();
+        if attn <= 0.0 {
+            return 0.0;
+        }
+
+        let [x, y, z] = corner.into_array();
+        let start = Vector3::from(gradient::grad3(hasher.hash(&[x, y, z])));
+
+        // Find a unit vector perpendicular to the starting gradient, from the
+        // axis that the gradient is least aligned with.
+        let axis = if start.x.abs() <= start.y.abs() && start.x.abs() <= start.z.abs() {
+            Vector3::new(1.0, 0.0, 0.0)
+        } else if start.y.abs() <= start.z.abs() {
+            Vector3::new(0.0, 1.0, 0.0)
+        } else {
+            Vector3::new(0.0, 0.0, 1.0)
+        };
+        let across = (axis - start * start.dot(axis)).normalize();
+
+        // Turn it around the starting gradient by a random angle, which picks
+        // the plane that the gradient rotates in.
+        let (sin, cos) = hashed_angle(hasher, [x, y, z, 1]).sin_cos();
+        let across = across * cos + start.cross(across) * sin;
+
+        let (sin, cos) = time.sin_cos();
+        let gradient = start * cos + across * sin;
+
+        attn.powi(4) * distance.dot(gradient)
+    }
+
+    let corner = point.floor_to_isize();
+    let floored = corner.numcast().unwrap();
+    let distance = point - floored;
+
+    macro_rules! call_surflet(
+        ($x:expr, $y:expr, $z:expr) => {
+            {
+                let offset = Vector3::new($x, $y, $z);
+                surflet(hasher, corner + offset, distance - offset.numcast().unwrap(), time)
+            }
+        }
+    );
+
+    let f000 = call_surflet!(0, 0, 0);
+    let f100 = call_surflet!(1, 0, 0);
+    let f010 = call_surflet!(0, 1, 0);
+    let f110 = call_surflet!(1, 1, 0);
+    let f001 = call_surflet!(0, 0, 1);
+    let f101 = call_surflet!(1, 0, 1);
+    let f011 = call_surflet!(0, 1, 1);
+    let f111 = call_surflet!(1, 1, 1);
+
+    // Scale to -1..1, clamping away rounding errors.
+    ((f000 + f100 + f010 + f110 + f001 + f101 + f011 + f111) * SCALE_FACTOR).clamp(-1.0, 1.0)
+}
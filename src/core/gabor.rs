@@ -0,0 +1,135 @@
+//! Sparse convolution Gabor noise, after Lagae et al., "Procedural Noise using
+//! Sparse Gabor Convolution" (2009).
+//!
+//! The noise is a sum of randomly placed and weighted Gabor kernels, each a
+//! cosine wave under a Gaussian envelope. The plane is divided into cells as
+//! wide as the kernel radius, so that only the impulses of the cell containing
+//! a point and of its eight neighbors can reach it.
+
+use crate::{
+    math::{to_lattice, vectors::Vector2},
+    permutationtable::{splitmix64, NoiseHasher},
+};
+use core::f64::consts::{PI, TAU};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Shape of the Gabor kernel, which sets the spectrum of the noise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GaborKernel {
+    /// Frequency of the cosine wave, in cycles per unit length.
+    pub frequency: f64,
+
+    /// Direction that the cosine wave oscillates in, in radians
+    /// counterclockwise from the _x_ axis. The noise forms stripes
+    /// perpendicular to this direction.
+    pub orientation: f64,
+
+    /// Width of the Gaussian envelope in the frequency domain. The envelope
+    /// in the spatial domain is `exp(-π * bandwidth² * r²)`, so a narrow
+    /// bandwidth gives long, regular stripes and a wide one short, irregular
+    /// blobs.
+    pub bandwidth: f64,
+}
+
+impl GaborKernel {
+    /// Distance beyond which the kernel is cut off, where its envelope has
+    /// fallen to `exp(-π)`, about 4% of its peak.
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.bandwidth.recip()
+    }
+
+    /// Value of the kernel at `offset` from its center.
+    #[inline]
+    pub fn evaluate(&self, offset: [f64; 2]) -> f64 {
+        let distance_squared = offset[0] * offset[0] + offset[1] * offset[1];
+        let bandwidth_squared = self.bandwidth * self.bandwidth;
+        if distance_squared * bandwidth_squared >= 1.0 {
+            return 0.0;
+        }
+
+        let (sin, cos) = self.orientation.sin_cos();
+        let envelope = (-PI * bandwidth_squared * distance_squared).exp();
+        let phase = TAU * self.frequency * (offset[0] * cos + offset[1] * sin);
+
+        envelope * phase.cos()
+    }
+
+    /// Standard deviation of the noise with `impulses_per_cell` impulses of
+    /// uniformly distributed weights in each cell.
+    ///
+    /// By Campbell's theorem, the variance is the impulse density times the
+    /// mean squared weight times the integral of the squared kernel, which
+    /// works out to `n / 12 * (1 + exp(-2π * frequency² / bandwidth²))`
+    /// for cells as wide as the kernel radius. The small part of the kernel
+    /// that is cut off is ignored.
+    pub fn standard_deviation(&self, impulses_per_cell: usize) -> f64 {
+        let ratio = self.frequency / self.bandwidth;
+        let variance = impulses_per_cell as f64 / 12.0 * (1.0 + (-TAU * ratio * ratio).exp());
+
+        variance.sqrt()
+    }
+}
+
+/// Returns the next uniformly distributed value in [0, 1) from `state`.
+#[inline]
+fn next_unit(state: &mut u64) -> f64 {
+    (splitmix64(state) >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+}
+
+/// 2-dimensional Gabor noise, scaled so that the output has a standard
+/// deviation of 1/3 and clamped to the [-1, 1] range.
+///
+/// Each cell holds `impulses_per_cell` impulses, placed and weighted by a
+/// random number generator seeded from the hashes of the cell, so the same
+/// hasher always produces the same noise.
+pub fn gabor_2d<NH>(
+    point: Vector2<f64>,
+    hasher: &NH,
+    kernel: &GaborKernel,
+    impulses_per_cell: usize,
+) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let deviation = kernel.standard_deviation(impulses_per_cell);
+    if deviation == 0.0 {
+        return 0.0;
+    }
+
+    let radius = kernel.radius();
+    let cell_point = point / radius;
+    let cell = [
+        to_lattice(cell_point.x.floor()),
+        to_lattice(cell_point.y.floor()),
+    ];
+
+    let mut result = 0.0;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let neighbor = [cell[0] + dx, cell[1] + dy];
+
+            // The hash values are small, so two of them are combined to give
+            // more variety between the cells.
+            let mut state = (hasher.hash(&[neighbor[0], neighbor[1], 0]) as u64)
+                .wrapping_mul(0x9e37_79b9_7f4a_7c15)
+                ^ hasher.hash(&[neighbor[0], neighbor[1], 1]) as u64;
+
+            for _ in 0..impulses_per_cell {
+                let impulse_x = neighbor[0] as f64 + next_unit(&mut state);
+                let impulse_y = neighbor[1] as f64 + next_unit(&mut state);
+                let weight = next_unit(&mut state) * 2.0 - 1.0;
+
+                let offset = [
+                    (cell_point.x - impulse_x) * radius,
+                    (cell_point.y - impulse_y) * radius,
+                ];
+                result += weight * kernel.evaluate(offset);
+            }
+        }
+    }
+
+    (result / (3.0 * deviation)).clamp(-1.0, 1.0)
+}
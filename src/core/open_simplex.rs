@@ -3,6 +3,8 @@ use crate::{
     math::vectors::{Vector2, Vector3, Vector4},
     permutationtable::NoiseHasher,
 };
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 #[inline(always)]
 pub fn open_simplex_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
@@ -29,7 +31,8 @@ where
     let stretched = point.map(|v| v + stretch_offset);
 
     // Floor to get grid coordinates of rhombus (stretched square) cell origin.
-    let stretched_floor = stretched.floor_to_isize().numcast().unwrap();
+    let stretched_cell = stretched.floor_to_isize();
+    let stretched_floor = stretched_cell.numcast().unwrap();
 
     // Skew out to get actual coordinates of rhombus origin. We'll need these later.
     let squish_offset = stretched_floor.sum() * SQUISH_CONSTANT;
@@ -48,8 +51,8 @@ where
         ($x:literal, $y:literal) => {
             {
                 let offset = Vector2::new($x, $y);
-                let vertex = stretched_floor + offset;
-                let index = hasher.hash(&vertex.numcast().unwrap().into_array());
+                let vertex = stretched_cell + offset.numcast().unwrap();
+                let index = hasher.hash(&vertex.into_array());
                 let dpos = rel_pos - (SQUISH_CONSTANT * offset.sum()) - offset;
 
                 surflet(index, dpos)
@@ -110,7 +113,8 @@ where
 
     // Floor to get simplectic honeycomb coordinates of rhombohedron
     // (stretched cube) super-cell origin.
-    let stretched_floor = stretched.floor_to_isize().numcast().unwrap();
+    let stretched_cell = stretched.floor_to_isize();
+    let stretched_floor = stretched_cell.numcast().unwrap();
 
     // Skew out to get actual coordinates of rhombohedron origin. We'll need
     // these later.
@@ -130,8 +134,8 @@ where
         ($x:literal, $y:literal, $z:literal) => {
             {
                 let offset = Vector3::new($x, $y, $z);
-                let vertex = stretched_floor + offset;
-                let index = hasher.hash(&vertex.numcast().unwrap().into_array());
+                let vertex = stretched_cell + offset.numcast().unwrap();
+                let index = hasher.hash(&vertex.into_array());
                 let dpos = rel_pos - (SQUISH_CONSTANT * offset.sum()) - offset;
 
                 surflet(index, dpos)
@@ -221,7 +225,8 @@ where
 
     // Floor to get simplectic honeycomb coordinates of rhombo-hypercube
     // super-cell origin.
-    let stretched_floor = stretched.floor_to_isize().numcast().unwrap();
+    let stretched_cell = stretched.floor_to_isize();
+    let stretched_floor = stretched_cell.numcast().unwrap();
 
     // Skew out to get actual coordinates of stretched rhombo-hypercube origin.
     // We'll need these later.
@@ -243,8 +248,8 @@ where
         ($x:literal, $y:literal, $z:literal, $w:literal) => {
             {
                 let offset = Vector4::new($x, $y, $z, $w);
-                let vertex = stretched_floor + offset;
-                let index = hasher.hash(&vertex.numcast().unwrap().into_array());
+                let vertex = stretched_cell + offset.numcast().unwrap();
+                let index = hasher.hash(&vertex.into_array());
                 let dpos = rel_pos - (SQUISH_CONSTANT * offset.sum()) - offset;
 
                 surflet(index, dpos)
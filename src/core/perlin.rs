@@ -1,13 +1,40 @@
 use crate::{
+    gradient::{self, GradientTable},
     math::{
         interpolate::linear,
         s_curve::quintic::Quintic,
+        to_lattice,
         vectors::{Vector2, Vector3, Vector4},
     },
     permutationtable::NoiseHasher,
 };
 use core::f64;
 
+#[cfg(feature = "twofloat")]
+use crate::math::double_double::DoubleDouble;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Splits an extended precision point into the lattice cell containing it and
+/// the offset of the point from the cell's corner.
+///
+/// Evaluating the noise at the offset, with the hashed cells translated by the
+/// returned cell, gives the noise at the point without the precision lost by
+/// rounding a large coordinate to an `f64`.
+#[cfg(feature = "twofloat")]
+pub fn perlin_lattice_split<const DIM: usize>(
+    point: [DoubleDouble; DIM],
+) -> ([isize; DIM], [f64; DIM]) {
+    let cell = point.map(|x| x.floor());
+
+    let mut offset = [0.0; DIM];
+    for axis in 0..DIM {
+        offset[axis] = (point[axis] - cell[axis]).to_f64();
+    }
+
+    (cell.map(|x| x as isize), offset)
+}
+
 #[inline(always)]
 pub fn perlin_1d<NH>(point: f64, hasher: &NH) -> f64
 where
@@ -20,7 +47,7 @@ where
     // 1/(sqrt(N)/2), N=1 -> 2/sqrt(1) -> 2
     const SCALE_FACTOR: f64 = 2.0;
 
-    let corner = point as isize;
+    let corner = to_lattice(point.floor());
     let distance = point - corner as f64;
 
     macro_rules! call_gradient(
@@ -282,3 +309,316 @@ where
     // outliers and return it.
     result.clamp(-1.0, 1.0)
 }
+
+#[inline(always)]
+pub fn perlin_unit_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_unit_2d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
+
+#[inline(always)]
+fn perlin_unit_2d_unclamped<NH>(point: Vector2<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // With unit-length gradients, the unscaled range is bounded by (-sqrt(N)/2, sqrt(N)/2), which
+    // can only be reached at the center of a cell. Scaling by the inverse guarantees (-1, 1).
+    //
+    // 1/(sqrt(N)/2), N=2 -> 2/sqrt(2)
+    const SCALE_FACTOR: f64 = 2.0 / f64::consts::SQRT_2;
+
+    let corner = point.floor_to_isize();
+    let distance = point - corner.numcast().unwrap();
+
+    macro_rules! call_gradient(
+        ($x:expr, $y:expr) => {
+            {
+                let offset = Vector2::new($x, $y);
+                let point = distance - offset.numcast().unwrap();
+                let index = hasher.hash(&(corner + offset).into_array());
+
+                point.dot(gradient::grad2_normalized(index).into())
+            }
+        }
+    );
+
+    let g00 = call_gradient!(0, 0);
+    let g10 = call_gradient!(1, 0);
+    let g01 = call_gradient!(0, 1);
+    let g11 = call_gradient!(1, 1);
+
+    let curve = distance.map_quintic();
+
+    linear(
+        linear(g00, g01, curve.y),
+        linear(g10, g11, curve.y),
+        curve.x,
+    ) * SCALE_FACTOR
+}
+
+#[inline(always)]
+pub fn perlin_unit_3d<NH>(point: Vector3<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_unit_3d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
+
+#[inline(always)]
+fn perlin_unit_3d_unclamped<NH>(point: Vector3<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // With unit-length gradients, the unscaled range is bounded by (-sqrt(N)/2, sqrt(N)/2), which
+    // can only be reached at the center of a cell. Scaling by the inverse guarantees (-1, 1).
+    //
+    // 1/(sqrt(N)/2), N=3 -> 2/sqrt(3)
+    const SCALE_FACTOR: f64 = 1.154_700_538_379_251_5;
+
+    let corner = point.floor_to_isize();
+    let distance = point - corner.numcast().unwrap();
+
+    macro_rules! call_gradient(
+        ($x:expr, $y:expr, $z:expr) => {
+            {
+                let offset = Vector3::new($x, $y, $z);
+                let point = distance - offset.numcast().unwrap();
+                let index = hasher.hash(&(corner + offset).into_array());
+
+                point.dot(gradient::grad3_normalized(index).into())
+            }
+        }
+    );
+
+    let g000 = call_gradient!(0, 0, 0);
+    let g100 = call_gradient!(1, 0, 0);
+    let g010 = call_gradient!(0, 1, 0);
+    let g110 = call_gradient!(1, 1, 0);
+    let g001 = call_gradient!(0, 0, 1);
+    let g101 = call_gradient!(1, 0, 1);
+    let g011 = call_gradient!(0, 1, 1);
+    let g111 = call_gradient!(1, 1, 1);
+
+    let curve = distance.map_quintic();
+
+    linear(
+        linear(
+            linear(g000, g001, curve.z),
+            linear(g010, g011, curve.z),
+            curve.y,
+        ),
+        linear(
+            linear(g100, g101, curve.z),
+            linear(g110, g111, curve.z),
+            curve.y,
+        ),
+        curve.x,
+    ) * SCALE_FACTOR
+}
+
+#[inline(always)]
+pub fn perlin_unit_4d<NH>(point: Vector4<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_unit_4d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
+
+#[inline(always)]
+fn perlin_unit_4d_unclamped<NH>(point: Vector4<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    // With unit-length gradients, the unscaled range is bounded by (-sqrt(N)/2, sqrt(N)/2), which
+    // can only be reached at the center of a cell. Scaling by the inverse guarantees (-1, 1).
+    const SCALE_FACTOR: f64 = 1.0; // 1/(sqrt(N)/2), N=4 -> 2/sqrt(4) -> 2/2 -> 1
+
+    let corner = point.floor_to_isize();
+    let distance = point - corner.numcast().unwrap();
+
+    macro_rules! call_gradient(
+        ($x:expr, $y:expr, $z:expr, $w:expr) => {
+            {
+                let offset = Vector4::new($x, $y, $z, $w);
+                let point = distance - offset.numcast().unwrap();
+                let index = hasher.hash(&(corner + offset).into_array());
+
+                point.dot(gradient::grad4_normalized(index).into())
+            }
+        }
+    );
+
+    let g0000 = call_gradient!(0, 0, 0, 0);
+    let g1000 = call_gradient!(1, 0, 0, 0);
+    let g0100 = call_gradient!(0, 1, 0, 0);
+    let g1100 = call_gradient!(1, 1, 0, 0);
+    let g0010 = call_gradient!(0, 0, 1, 0);
+    let g1010 = call_gradient!(1, 0, 1, 0);
+    let g0110 = call_gradient!(0, 1, 1, 0);
+    let g1110 = call_gradient!(1, 1, 1, 0);
+    let g0001 = call_gradient!(0, 0, 0, 1);
+    let g1001 = call_gradient!(1, 0, 0, 1);
+    let g0101 = call_gradient!(0, 1, 0, 1);
+    let g1101 = call_gradient!(1, 1, 0, 1);
+    let g0011 = call_gradient!(0, 0, 1, 1);
+    let g1011 = call_gradient!(1, 0, 1, 1);
+    let g0111 = call_gradient!(0, 1, 1, 1);
+    let g1111 = call_gradient!(1, 1, 1, 1);
+
+    let curve = distance.map_quintic();
+
+    linear(
+        linear(
+            linear(
+                linear(g0000, g0001, curve.w),
+                linear(g0010, g0011, curve.w),
+                curve.z,
+            ),
+            linear(
+                linear(g0100, g0101, curve.w),
+                linear(g0110, g0111, curve.w),
+                curve.z,
+            ),
+            curve.y,
+        ),
+        linear(
+            linear(
+                linear(g1000, g1001, curve.w),
+                linear(g1010, g1011, curve.w),
+                curve.z,
+            ),
+            linear(
+                linear(g1100, g1101, curve.w),
+                linear(g1110, g1111, curve.w),
+                curve.z,
+            ),
+            curve.y,
+        ),
+        curve.x,
+    ) * SCALE_FACTOR
+}
+
+/// Perlin noise using the gradients of a [`GradientTable`].
+///
+/// The corner contributions are blended with the same quintic curve as the
+/// other Perlin functions. The unscaled range is bounded by `L*sqrt(N)/2`,
+/// where `L` is the length of the longest gradient, so the result is scaled by
+/// the inverse of that bound.
+#[inline(always)]
+pub fn perlin_table<NH, const DIM: usize>(
+    point: [f64; DIM],
+    hasher: &NH,
+    table: &GradientTable<DIM>,
+) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let scale_factor = 2.0 / ((DIM as f64).sqrt() * table.max_length());
+
+    let corner = point.map(|x| to_lattice(x.floor()));
+    let mut distance = [0.0; DIM];
+    let mut curve = [0.0; DIM];
+    for axis in 0..DIM {
+        distance[axis] = point[axis] - corner[axis] as f64;
+        curve[axis] = distance[axis].map_quintic();
+    }
+
+    // Sum the contribution of each corner of the cell weighted by the product
+    // of its curve values, which is the same as interpolating along each axis
+    // in turn.
+    let mut result = 0.0;
+    for bits in 0..(1_usize << DIM) {
+        let mut lattice = corner;
+        let mut weight = 1.0;
+        let mut offset = distance;
+        for axis in 0..DIM {
+            if bits & (1 << axis) != 0 {
+                lattice[axis] += 1;
+                offset[axis] -= 1.0;
+                weight *= curve[axis];
+            } else {
+                weight *= 1.0 - curve[axis];
+            }
+        }
+
+        let gradient = table.get(hasher.hash(&lattice));
+        let dot: f64 = (0..DIM).map(|axis| gradient[axis] * offset[axis]).sum();
+
+        result += dot * weight;
+    }
+
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    (result * scale_factor).clamp(-1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+
+    /// Hashes each corner of the cell at the origin to the gradient that points
+    /// most directly at the center of the cell, where the noise then peaks.
+    struct TowardsCenter<const N: usize>(fn(usize) -> [f64; N]);
+
+    impl<const N: usize> NoiseHasher for TowardsCenter<N> {
+        fn hash(&self, corner: &[isize]) -> usize {
+            let towards_center = |index: &usize| {
+                self.0(*index)
+                    .iter()
+                    .zip(corner)
+                    .map(|(g, &c)| g * (0.5 - c as f64))
+                    .sum::<f64>()
+            };
+
+            (0..32)
+                .max_by(|a, b| towards_center(a).total_cmp(&towards_center(b)))
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn unit_gradients_peak_within_range() {
+        // The diagonal gradients of 2D reach the bound, while those of 3D and
+        // 4D can't point straight at the center.
+        let peak_2d = perlin_unit_2d_unclamped(
+            Vector2::new(0.5, 0.5),
+            &TowardsCenter(gradient::grad2_normalized),
+        );
+        let peak_3d = perlin_unit_3d_unclamped(
+            Vector3::new(0.5, 0.5, 0.5),
+            &TowardsCenter(gradient::grad3_normalized),
+        );
+        let peak_4d = perlin_unit_4d_unclamped(
+            Vector4::new(0.5, 0.5, 0.5, 0.5),
+            &TowardsCenter(gradient::grad4_normalized),
+        );
+
+        assert!((peak_2d - 1.0).abs() < 1e-12, "{}", peak_2d);
+        assert!(
+            (peak_3d - (2.0f64 / 3.0).sqrt()).abs() < 1e-12,
+            "{}",
+            peak_3d
+        );
+        assert!((peak_4d - 0.75f64.sqrt()).abs() < 1e-12, "{}", peak_4d);
+
+        for seed in 0..8 {
+            let hasher = PermutationTable::new(seed);
+            for i in 0..2000 {
+                let x = i as f64 * 0.0371 - 37.0;
+                let y = i as f64 * 0.0913 + 11.0;
+                for value in [
+                    perlin_unit_2d_unclamped(Vector2::new(x, y), &hasher),
+                    perlin_unit_3d_unclamped(Vector3::new(x, y, x - y), &hasher),
+                    perlin_unit_4d_unclamped(Vector4::new(x, y, y * 0.5, x * 0.25), &hasher),
+                ] {
+                    assert!(value.abs() <= 1.0 + 1e-12, "{} out of range", value);
+                }
+            }
+        }
+    }
+}
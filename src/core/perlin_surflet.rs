@@ -3,14 +3,34 @@ use crate::{
     math::vectors::{Vector2, Vector3, Vector4},
     permutationtable::NoiseHasher,
 };
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+// The scale factors are the inverses of the largest sum of surflets reachable
+// with the gradient tables, taken over every point in a cell with each corner
+// picking whichever of its gradients contributes the most. The 2D maximum is
+// exactly 81/256, reached halfway along a cell edge. The 3D and 4D maxima were
+// found with a fine grid search refined by a local search, and are reached at
+// (0.5, 0.142_089_9, 0) and (0.5, 0.121_294_5, 0.121_294_5, 0) respectively,
+// up to symmetry.
+const SCALE_FACTOR_2D: f64 = 256.0 / 81.0;
+const SCALE_FACTOR_3D: f64 = 1.0 / 0.257_603_224_879_562_9;
+const SCALE_FACTOR_4D: f64 = 1.0 / 0.231_172_056_922_647_7;
 
 #[inline(always)]
 pub fn perlin_surflet_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
-    const SCALE_FACTOR: f64 = 3.160_493_827_160_493_7;
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_surflet_2d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
 
+#[inline(always)]
+fn perlin_surflet_2d_unclamped<NH>(point: Vector2<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
     fn surflet(index: usize, distance: Vector2<f64>) -> f64 {
         let attn: f64 = 1.0 - distance.magnitude_squared();
 
@@ -41,8 +61,8 @@ where
     let f01 = call_surflet!(0, 1);
     let f11 = call_surflet!(1, 1);
 
-    // Multiply by arbitrary value to scale to -1..1
-    ((f00 + f10 + f01 + f11) * SCALE_FACTOR).clamp(-1.0, 1.0)
+    // Scale to -1..1.
+    (f00 + f10 + f01 + f11) * SCALE_FACTOR_2D
 }
 
 #[inline(always)]
@@ -50,8 +70,15 @@ pub fn perlin_surflet_3d<NH>(point: Vector3<f64>, hasher: &NH) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
-    const SCALE_FACTOR: f64 = 3.889_855_325_553_107_4;
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_surflet_3d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
 
+#[inline(always)]
+fn perlin_surflet_3d_unclamped<NH>(point: Vector3<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
     #[inline(always)]
     fn surflet(index: usize, distance: Vector3<f64>) -> f64 {
         let attn: f64 = 1.0 - distance.magnitude_squared();
@@ -87,8 +114,8 @@ where
     let f011 = call_surflet!(0, 1, 1);
     let f111 = call_surflet!(1, 1, 1);
 
-    // Multiply by arbitrary value to scale to -1..1
-    ((f000 + f100 + f010 + f110 + f001 + f101 + f011 + f111) * SCALE_FACTOR).clamp(-1.0, 1.0)
+    // Scale to -1..1.
+    (f000 + f100 + f010 + f110 + f001 + f101 + f011 + f111) * SCALE_FACTOR_3D
 }
 
 #[inline(always)]
@@ -96,8 +123,15 @@ pub fn perlin_surflet_4d<NH>(point: Vector4<f64>, hasher: &NH) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
-    const SCALE_FACTOR: f64 = 4.424_369_240_215_691;
+    // Only rounding errors can push the result past the bounds, so clamp them away.
+    perlin_surflet_4d_unclamped(point, hasher).clamp(-1.0, 1.0)
+}
 
+#[inline(always)]
+fn perlin_surflet_4d_unclamped<NH>(point: Vector4<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
     #[inline(always)]
     fn surflet(index: usize, distance: Vector4<f64>) -> f64 {
         let attn: f64 = 1.0 - distance.magnitude_squared();
@@ -141,8 +175,8 @@ where
     let f0111 = call_surflet!(0, 1, 1, 1);
     let f1111 = call_surflet!(1, 1, 1, 1);
 
-    // Multiply by arbitrary value to scale to -1..1
-    ((f0000
+    // Scale to -1..1.
+    (f0000
         + f1000
         + f0100
         + f1100
@@ -158,6 +192,98 @@ where
         + f1011
         + f0111
         + f1111)
-        * SCALE_FACTOR)
-        .clamp(-1.0, 1.0)
+        * SCALE_FACTOR_4D
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+    use alloc::vec::Vec;
+
+    /// Sum of the surflets at `point` in the unit cell when every corner uses
+    /// the gradient that contributes the most.
+    fn best_case_sum<const N: usize>(point: [f64; N], gradients: &[[f64; N]]) -> f64 {
+        let mut sum = 0.0;
+        for corner in 0..(1_usize << N) {
+            let mut distance = point;
+            for (axis, x) in distance.iter_mut().enumerate() {
+                *x -= ((corner >> axis) & 1) as f64;
+            }
+
+            let attn = 1.0 - distance.iter().map(|x| x * x).sum::<f64>();
+            if attn > 0.0 {
+                let best = gradients
+                    .iter()
+                    .map(|g| g.iter().zip(&distance).map(|(g, d)| g * d).sum::<f64>())
+                    .fold(0.0, f64::max);
+                sum += attn.powi(4) * best;
+            }
+        }
+
+        sum
+    }
+
+    fn grid_max<const N: usize>(gradients: &[[f64; N]], steps: usize) -> f64 {
+        (0..steps.pow(N as u32))
+            .map(|i| {
+                let mut point = [0.0; N];
+                let mut rest = i;
+                for x in point.iter_mut() {
+                    *x = (rest % steps) as f64 / (steps - 1) as f64;
+                    rest /= steps;
+                }
+                best_case_sum(point, gradients)
+            })
+            .fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn scale_factors_match_gradient_table_maxima() {
+        let grad2: Vec<_> = (0..8).map(gradient::grad2).collect();
+        let grad3: Vec<_> = (0..32).map(gradient::grad3).collect();
+        let grad4: Vec<_> = (0..64).map(gradient::grad4).collect();
+
+        let max2 = best_case_sum([0.5, 0.0], &grad2) * SCALE_FACTOR_2D;
+        let max3 = best_case_sum([0.5, 0.142_089_916_753_374_85, 0.0], &grad3) * SCALE_FACTOR_3D;
+        let max4 = best_case_sum(
+            [0.5, 0.121_294_449_015_361_5, 0.121_294_451_922_727_68, 0.0],
+            &grad4,
+        ) * SCALE_FACTOR_4D;
+        assert!((max2 - 1.0).abs() < 1e-12);
+        assert!((max3 - 1.0).abs() < 1e-12);
+        assert!((max4 - 1.0).abs() < 1e-12);
+
+        assert!(grid_max(&grad2, 101) * SCALE_FACTOR_2D <= 1.0 + 1e-12);
+        assert!(grid_max(&grad3, 31) * SCALE_FACTOR_3D <= 1.0 + 1e-12);
+        assert!(grid_max(&grad4, 11) * SCALE_FACTOR_4D <= 1.0 + 1e-12);
+    }
+
+    #[test]
+    fn output_stays_in_range() {
+        let mut extremes = [0.0_f64; 3];
+        for seed in 0..16 {
+            let hasher = PermutationTable::new(seed);
+            for i in 0..2000 {
+                let x = i as f64 * 0.0713 - 60.0;
+                let y = i as f64 * 0.0377 + 13.0;
+                let values = [
+                    perlin_surflet_2d_unclamped(Vector2::new(x, y), &hasher),
+                    perlin_surflet_3d_unclamped(Vector3::new(x, y, x * 0.31), &hasher),
+                    perlin_surflet_4d_unclamped(Vector4::new(x, y, y * 0.43, x * 0.17), &hasher),
+                ];
+
+                for (extreme, value) in extremes.iter_mut().zip(values) {
+                    assert!(value.abs() <= 1.0 + 1e-12, "{} out of range", value);
+                    *extreme = extreme.max(value.abs());
+                }
+            }
+        }
+
+        // The scaling is tight enough that the sampled noise gets close to the
+        // bounds.
+        for extreme in extremes {
+            assert!(extreme > 0.6, "maximum of {} is too low", extreme);
+        }
+    }
 }
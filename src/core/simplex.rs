@@ -5,6 +5,9 @@ use crate::{
 };
 use num_traits::{Float, NumCast};
 
+#[cfg(feature = "twofloat")]
+use crate::math::double_double::DoubleDouble;
+
 // Skew Value
 //
 //     sqrt(n + 1) - 1
@@ -33,33 +36,70 @@ where
     (F::one() - (F::one() / (n + F::one()).sqrt())) / n
 }
 
-/// The simplex noise code was adapted from code by Stefan Gustavson,
-/// http://staffwww.itn.liu.se/~stegu/aqsis/aqsis-newnoise/sdnoise1234.c
-///
-/// This is Stefan Gustavson's original copyright notice:
+/// Splits an extended precision point into the skewed lattice cell containing
+/// it and the offset of the point from the cell's origin in unskewed space.
 ///
-/// /* sdnoise1234, Simplex noise with true analytic
-///  * derivative in 1D to 4D.
-///  *
-///  * Copyright © 2003-2011, Stefan Gustavson
-///  *
-///  * Contact: stefan.gustavson@gmail.com
-///  *
-///  * This library is public domain software, released by the author
-///  * into the public domain in February 2011. You may do anything
-///  * you like with it. You may even remove all attributions,
-///  * but of course I'd appreciate it if you kept my name somewhere.
-///  *
-///  * This library is distributed in the hope that it will be useful,
-///  * but WITHOUT ANY WARRANTY; without even the implied warranty of
-///  * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
-///  * General Public License for more details.
-///  */
+/// Evaluating the noise at the offset, with the hashed cells translated by the
+/// returned cell, gives the noise at the point without the precision lost by
+/// rounding a large coordinate to an `f64`.
+#[cfg(feature = "twofloat")]
+pub fn simplex_lattice_split<const DIM: usize>(
+    point: [DoubleDouble; DIM],
+) -> ([isize; DIM], [f64; DIM]) {
+    let sum = point
+        .iter()
+        .fold(DoubleDouble::default(), |sum, &x| sum + x);
+    let skew = sum * skew_factor::<f64>(DIM);
+    let cell = point.map(|x| (x + skew).floor());
+
+    let cell_sum = cell.iter().sum::<f64>();
+    let unskew = DoubleDouble::from(cell_sum) * unskew_factor::<f64>(DIM);
+
+    let mut offset = [0.0; DIM];
+    for axis in 0..DIM {
+        offset[axis] = (point[axis] - cell[axis] + unskew).to_f64();
+    }
 
+    (cell.map(|x| x as isize), offset)
+}
+
+// The simplex noise code was adapted from code by Stefan Gustavson,
+// http://staffwww.itn.liu.se/~stegu/aqsis/aqsis-newnoise/sdnoise1234.c
+//
+// This is Stefan Gustavson's original copyright notice:
+//
+// /* sdnoise1234, Simplex noise with true analytic
+//  * derivative in 1D to 4D.
+//  *
+//  * Copyright © 2003-2011, Stefan Gustavson
+//  *
+//  * Contact: stefan.gustavson@gmail.com
+//  *
+//  * This library is public domain software, released by the author
+//  * into the public domain in February 2011. You may do anything
+//  * you like with it. You may even remove all attributions,
+//  * but of course I'd appreciate it if you kept my name somewhere.
+//  *
+//  * This library is distributed in the hope that it will be useful,
+//  * but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU
+//  * General Public License for more details.
+//  */
 #[inline(always)]
 pub fn simplex_2d<NH>(point: Vector2<f64>, hasher: &NH) -> (f64, [f64; 2])
 where
     NH: NoiseHasher + ?Sized,
+{
+    simplex_2d_with(point, |cell| hasher.hash(&cell))
+}
+
+/// Same as [`simplex_2d`], with the gradient index of each lattice cell supplied
+/// by `hash` instead of a [`NoiseHasher`]. This allows experimenting with other
+/// hashing strategies and gradient selections.
+#[inline(always)]
+pub fn simplex_2d_with<F>(point: Vector2<f64>, hash: F) -> (f64, [f64; 2])
+where
+    F: Fn([isize; 2]) -> usize,
 {
     let skew_factor: f64 = skew_factor(2);
     let unskew_factor: f64 = unskew_factor(2);
@@ -97,9 +137,9 @@ where
     let offset3 = offset1 - 1.0 + 2.0 * unskew_factor;
 
     // Calculate gradient indexes for each corner
-    let gi0 = hasher.hash(&cell.into_array());
-    let gi1 = hasher.hash(&(cell + order.numcast().unwrap()).into_array());
-    let gi2 = hasher.hash(&(cell + 1).into_array());
+    let gi0 = hash(cell.into_array());
+    let gi1 = hash((cell + order.numcast().unwrap()).into_array());
+    let gi2 = hash((cell + 1).into_array());
 
     struct SurfletComponents {
         value: f64,
@@ -146,23 +186,17 @@ where
     // The result is scaled to return values in the interval [-1, 1].
     let noise = corner0.value + corner1.value + corner2.value;
 
-    // A straight, unoptimised calculation would be like:
-    //   dnoise_dx = -8.0 * t20 * t0 * x0 * ( gx0 * x0 + gy0 * y0 ) + t40 * gx0;
-    //   dnoise_dy = -8.0 * t20 * t0 * y0 * ( gx0 * x0 + gy0 * y0 ) + t40 * gy0;
-    //   dnoise_dx += -8.0 * t21 * t1 * x1 * ( gx1 * x1 + gy1 * y1 ) + t41 * gx1;
-    //   dnoise_dy += -8.0 * t21 * t1 * y1 * ( gx1 * x1 + gy1 * y1 ) + t41 * gy1;
-    //   dnoise_dx += -8.0 * t22 * t2 * x2 * ( gx2 * x2 + gy2 * y2 ) + t42 * gx2;
-    //   dnoise_dy += -8.0 * t22 * t2 * y2 * ( gx2 * x2 + gy2 * y2 ) + t42 * gy2;
-    //
-    let mut dnoise = offset1 + corner0.t2 * corner0.t * corner0.gradient.dot(offset1);
-    dnoise += offset2 * corner1.t2 * corner1.t * corner1.gradient.dot(offset2);
-    dnoise += offset3 * corner2.t2 * corner2.t * corner2.gradient.dot(offset3);
+    // Each corner contributes (2t^2 + t^4)(g.p), where t = 1 - 2|p|^2, so its
+    // gradient is (2t^2 + t^4)g - 16t(1 + t^2)(g.p)p.
+    let mut dnoise = offset1 * corner0.t * (1.0 + corner0.t2) * corner0.gradient.dot(offset1);
+    dnoise += offset2 * corner1.t * (1.0 + corner1.t2) * corner1.gradient.dot(offset2);
+    dnoise += offset3 * corner2.t * (1.0 + corner2.t2) * corner2.gradient.dot(offset3);
 
-    dnoise *= -8.0;
+    dnoise *= -16.0;
 
-    dnoise += corner0.gradient * corner0.t4
-        + corner1.gradient * corner1.t4
-        + corner2.gradient * corner2.t4;
+    dnoise += corner0.gradient * (2.0 * corner0.t2 + corner0.t4)
+        + corner1.gradient * (2.0 * corner1.t2 + corner1.t4)
+        + corner2.gradient * (2.0 * corner2.t2 + corner2.t4);
 
     (noise, dnoise.into())
 }
@@ -171,6 +205,17 @@ where
 pub fn simplex_3d<NH>(point: Vector3<f64>, hasher: &NH) -> (f64, [f64; 3])
 where
     NH: NoiseHasher + ?Sized,
+{
+    simplex_3d_with(point, |cell| hasher.hash(&cell))
+}
+
+/// Same as [`simplex_3d`], with the gradient index of each lattice cell supplied
+/// by `hash` instead of a [`NoiseHasher`]. This allows experimenting with other
+/// hashing strategies and gradient selections.
+#[inline(always)]
+pub fn simplex_3d_with<F>(point: Vector3<f64>, hash: F) -> (f64, [f64; 3])
+where
+    F: Fn([isize; 3]) -> usize,
 {
     let skew_factor: f64 = skew_factor(3);
     let unskew_factor: f64 = unskew_factor(3);
@@ -226,10 +271,10 @@ where
     let offset4 = offset1 - Vector3::one() + 3.0 * unskew_factor;
 
     // Calculate gradient indexes for each corner
-    let gi0 = hasher.hash(&cell.into_array());
-    let gi1 = hasher.hash(&(cell + order1).into_array());
-    let gi2 = hasher.hash(&(cell + order2).into_array());
-    let gi3 = hasher.hash(&(cell + 1).into_array());
+    let gi0 = hash(cell.into_array());
+    let gi1 = hash((cell + order1).into_array());
+    let gi2 = hash((cell + order2).into_array());
+    let gi3 = hash((cell + 1).into_array());
 
     struct SurfletComponents {
         value: f64,
@@ -276,31 +321,18 @@ where
      * The result is scaled to return values in the range [-1,1] */
     let noise = corner0.value + corner1.value + corner2.value + corner3.value;
 
-    /*  A straight, unoptimised calculation would be like:
-     *    dnoise_dx = -8.0 * t20 * t0 * x0 * dot(gx0, gy0, gz0, x0, y0, z0) + t40 * gx0;
-     *    dnoise_dy = -8.0 * t20 * t0 * y0 * dot(gx0, gy0, gz0, x0, y0, z0) + t40 * gy0;
-     *    dnoise_dz = -8.0 * t20 * t0 * z0 * dot(gx0, gy0, gz0, x0, y0, z0) + t40 * gz0;
-     *    dnoise_dx += -8.0 * t21 * t1 * x1 * dot(gx1, gy1, gz1, x1, y1, z1) + t41 * gx1;
-     *    dnoise_dy += -8.0 * t21 * t1 * y1 * dot(gx1, gy1, gz1, x1, y1, z1) + t41 * gy1;
-     *    dnoise_dz += -8.0 * t21 * t1 * z1 * dot(gx1, gy1, gz1, x1, y1, z1) + t41 * gz1;
-     *    dnoise_dx += -8.0 * t22 * t2 * x2 * dot(gx2, gy2, gz2, x2, y2, z2) + t42 * gx2;
-     *    dnoise_dy += -8.0 * t22 * t2 * y2 * dot(gx2, gy2, gz2, x2, y2, z2) + t42 * gy2;
-     *    dnoise_dz += -8.0 * t22 * t2 * z2 * dot(gx2, gy2, gz2, x2, y2, z2) + t42 * gz2;
-     *    dnoise_dx += -8.0 * t23 * t3 * x3 * dot(gx3, gy3, gz3, x3, y3, z3) + t43 * gx3;
-     *    dnoise_dy += -8.0 * t23 * t3 * y3 * dot(gx3, gy3, gz3, x3, y3, z3) + t43 * gy3;
-     *    dnoise_dz += -8.0 * t23 * t3 * z3 * dot(gx3, gy3, gz3, x3, y3, z3) + t43 * gz3;
-     */
-    let mut dnoise = offset1 * corner0.t2 * corner0.t * corner0.gradient.dot(offset1);
-    dnoise += offset2 * corner1.t2 * corner1.t * corner1.gradient.dot(offset2);
-    dnoise += offset3 * corner2.t2 * corner2.t * corner2.gradient.dot(offset3);
-    dnoise += offset4 * corner3.t2 * corner3.t * corner3.gradient.dot(offset4);
-
-    dnoise *= -8.0;
-
-    dnoise += corner0.gradient * corner0.t4
-        + corner1.gradient * corner1.t4
-        + corner2.gradient * corner2.t4
-        + corner3.gradient * corner3.t4;
+    // Sum the gradients of the corner contributions, as in `simplex_2d_with`.
+    let mut dnoise = offset1 * corner0.t * (1.0 + corner0.t2) * corner0.gradient.dot(offset1);
+    dnoise += offset2 * corner1.t * (1.0 + corner1.t2) * corner1.gradient.dot(offset2);
+    dnoise += offset3 * corner2.t * (1.0 + corner2.t2) * corner2.gradient.dot(offset3);
+    dnoise += offset4 * corner3.t * (1.0 + corner3.t2) * corner3.gradient.dot(offset4);
+
+    dnoise *= -16.0;
+
+    dnoise += corner0.gradient * (2.0 * corner0.t2 + corner0.t4)
+        + corner1.gradient * (2.0 * corner1.t2 + corner1.t4)
+        + corner2.gradient * (2.0 * corner2.t2 + corner2.t4)
+        + corner3.gradient * (2.0 * corner3.t2 + corner3.t4);
 
     (noise, dnoise.into())
 }
@@ -309,6 +341,17 @@ where
 pub fn simplex_4d<NH>(point: Vector4<f64>, hasher: &NH) -> (f64, [f64; 4])
 where
     NH: NoiseHasher + ?Sized,
+{
+    simplex_4d_with(point, |cell| hasher.hash(&cell))
+}
+
+/// Same as [`simplex_4d`], with the gradient index of each lattice cell supplied
+/// by `hash` instead of a [`NoiseHasher`]. This allows experimenting with other
+/// hashing strategies and gradient selections.
+#[inline(always)]
+pub fn simplex_4d_with<F>(point: Vector4<f64>, hash: F) -> (f64, [f64; 4])
+where
+    F: Fn([isize; 4]) -> usize,
 {
     let skew_factor: f64 = skew_factor(4);
     let unskew_factor: f64 = unskew_factor(4);
@@ -365,11 +408,11 @@ where
     let offset5 = offset1 - 1.0 + 4.0 * unskew_factor;
 
     // Calculate gradient indexes for each corner
-    let gi0 = hasher.hash(&cell.into_array());
-    let gi1 = hasher.hash(&(cell + order1).into_array());
-    let gi2 = hasher.hash(&(cell + order2).into_array());
-    let gi3 = hasher.hash(&(cell + order3).into_array());
-    let gi4 = hasher.hash(&(cell + 1).into_array());
+    let gi0 = hash(cell.into_array());
+    let gi1 = hash((cell + order1).into_array());
+    let gi2 = hash((cell + order2).into_array());
+    let gi3 = hash((cell + order3).into_array());
+    let gi4 = hash((cell + 1).into_array());
 
     struct SurfletComponents {
         value: f64,
@@ -416,41 +459,20 @@ where
     // Sum up and scale the result to cover the range [-1,1]
     let noise = corner1.value + corner2.value + corner3.value + corner4.value + corner5.value;
 
-    /*  A straight, unoptimised calculation would be like:
-     *    dnoise_dx = -8.0 * t20 * t0 * x0 * dot(gx0, gy0, gz0, gw0, x0, y0, z0, w0) + t40 * gx0;
-     *    dnoise_dy = -8.0 * t20 * t0 * y0 * dot(gx0, gy0, gz0, gw0, x0, y0, z0, w0) + t40 * gy0;
-     *    dnoise_dz = -8.0 * t20 * t0 * z0 * dot(gx0, gy0, gz0, gw0, x0, y0, z0, w0) + t40 * gz0;
-     *    dnoise_dw = -8.0 * t20 * t0 * w0 * dot(gx0, gy0, gz0, gw0, x0, y0, z0, w0) + t40 * gw0;
-     *    dnoise_dx += -8.0 * t21 * t1 * x1 * dot(gx1, gy1, gz1, gw1, x1, y1, z1, w1) + t41 * gx1;
-     *    dnoise_dy += -8.0 * t21 * t1 * y1 * dot(gx1, gy1, gz1, gw1, x1, y1, z1, w1) + t41 * gy1;
-     *    dnoise_dz += -8.0 * t21 * t1 * z1 * dot(gx1, gy1, gz1, gw1, x1, y1, z1, w1) + t41 * gz1;
-     *    dnoise_dw += -8.0 * t21 * t1 * w1 * dot(gx1, gy1, gz1, gw1, x1, y1, z1, w1) + t41 * gw1;
-     *    dnoise_dx += -8.0 * t22 * t2 * x2 * dot(gx2, gy2, gz2, gw2, x2, y2, z2, w2) + t42 * gx2;
-     *    dnoise_dy += -8.0 * t22 * t2 * y2 * dot(gx2, gy2, gz2, gw2, x2, y2, z2, w2) + t42 * gy2;
-     *    dnoise_dz += -8.0 * t22 * t2 * z2 * dot(gx2, gy2, gz2, gw2, x2, y2, z2, w2) + t42 * gz2;
-     *    dnoise_dw += -8.0 * t22 * t2 * w2 * dot(gx2, gy2, gz2, gw2, x2, y2, z2, w2) + t42 * gw2;
-     *    dnoise_dx += -8.0 * t23 * t3 * x3 * dot(gx3, gy3, gz3, gw3, x3, y3, z3, w3) + t43 * gx3;
-     *    dnoise_dy += -8.0 * t23 * t3 * y3 * dot(gx3, gy3, gz3, gw3, x3, y3, z3, w3) + t43 * gy3;
-     *    dnoise_dz += -8.0 * t23 * t3 * z3 * dot(gx3, gy3, gz3, gw3, x3, y3, z3, w3) + t43 * gz3;
-     *    dnoise_dw += -8.0 * t23 * t3 * w3 * dot(gx3, gy3, gz3, gw3, x3, y3, z3, w3) + t43 * gw3;
-     *    dnoise_dx += -8.0 * t24 * t4 * x4 * dot(gx4, gy4, gz4, gw4, x4, y4, z4, w4) + t44 * gx4;
-     *    dnoise_dy += -8.0 * t24 * t4 * y4 * dot(gx4, gy4, gz4, gw4, x4, y4, z4, w4) + t44 * gy4;
-     *    dnoise_dz += -8.0 * t24 * t4 * z4 * dot(gx4, gy4, gz4, gw4, x4, y4, z4, w4) + t44 * gz4;
-     *    dnoise_dw += -8.0 * t24 * t4 * w4 * dot(gx4, gy4, gz4, gw4, x4, y4, z4, w4) + t44 * gw4;
-     */
-    let mut dnoise = offset1 * corner1.t2 * corner1.t * corner1.gradient.dot(offset1);
-    dnoise += offset2 * corner2.t2 * corner2.t * corner2.gradient.dot(offset2);
-    dnoise += offset3 * corner3.t2 * corner3.t * corner3.gradient.dot(offset3);
-    dnoise += offset4 * corner4.t2 * corner4.t * corner4.gradient.dot(offset4);
-    dnoise += offset5 * corner5.t2 * corner5.t * corner5.gradient.dot(offset5);
-
-    dnoise *= -8.0;
-
-    dnoise += corner1.gradient * corner1.t4
-        + corner2.gradient * corner2.t4
-        + corner3.gradient * corner3.t4
-        + corner4.gradient * corner4.t4
-        + corner5.gradient * corner5.t4;
+    // Sum the gradients of the corner contributions, as in `simplex_2d_with`.
+    let mut dnoise = offset1 * corner1.t * (1.0 + corner1.t2) * corner1.gradient.dot(offset1);
+    dnoise += offset2 * corner2.t * (1.0 + corner2.t2) * corner2.gradient.dot(offset2);
+    dnoise += offset3 * corner3.t * (1.0 + corner3.t2) * corner3.gradient.dot(offset3);
+    dnoise += offset4 * corner4.t * (1.0 + corner4.t2) * corner4.gradient.dot(offset4);
+    dnoise += offset5 * corner5.t * (1.0 + corner5.t2) * corner5.gradient.dot(offset5);
+
+    dnoise *= -16.0;
+
+    dnoise += corner1.gradient * (2.0 * corner1.t2 + corner1.t4)
+        + corner2.gradient * (2.0 * corner2.t2 + corner2.t4)
+        + corner3.gradient * (2.0 * corner3.t2 + corner3.t4)
+        + corner4.gradient * (2.0 * corner4.t2 + corner4.t4)
+        + corner5.gradient * (2.0 * corner5.t2 + corner5.t4);
 
     (noise, dnoise.into())
 }
@@ -469,3 +491,66 @@ const SIMPLEX: [[u8; 4]; 64] = [
     [2, 0, 1, 3], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [3, 0, 1, 2], [3, 0, 2, 1], [0, 0, 0, 0], [3, 1, 2, 0],
     [2, 1, 0, 3], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [3, 1, 0, 2], [0, 0, 0, 0], [3, 2, 0, 1], [3, 2, 1, 0],
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+
+    #[test]
+    fn closure_hash_matches_hasher() {
+        let hasher = PermutationTable::new(9);
+
+        for i in 0..256 {
+            let t = i as f64 * 0.173 - 21.7;
+            let point2 = Vector2::new(t, t * 0.61 + 3.0);
+            let point3 = Vector3::new(t, t * 0.61 + 3.0, -t * 1.3);
+            let point4 = Vector4::new(t, t * 0.61 + 3.0, -t * 1.3, t * 0.2);
+
+            assert_eq!(
+                simplex_2d_with(point2, |cell| hasher.hash(&cell)),
+                simplex_2d(point2, &hasher)
+            );
+            assert_eq!(
+                simplex_3d_with(point3, |cell| hasher.hash(&cell)),
+                simplex_3d(point3, &hasher)
+            );
+            assert_eq!(
+                simplex_4d_with(point4, |cell| hasher.hash(&cell)),
+                simplex_4d(point4, &hasher)
+            );
+        }
+    }
+
+    fn assert_derivative_matches<const N: usize>(noise: impl Fn([f64; N]) -> (f64, [f64; N])) {
+        let step = 1e-6;
+
+        for i in 0..256 {
+            let t = i as f64 * 0.173 - 21.7;
+            let mut point = [0.0; N];
+            for (axis, x) in point.iter_mut().enumerate() {
+                *x = t * (0.61 + axis as f64 * 0.37) + axis as f64;
+            }
+
+            let (_, derivative) = noise(point);
+            for axis in 0..N {
+                let mut ahead = point;
+                let mut behind = point;
+                ahead[axis] += step;
+                behind[axis] -= step;
+
+                let estimate = (noise(ahead).0 - noise(behind).0) / (2.0 * step);
+                assert!((derivative[axis] - estimate).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn derivatives_match_finite_differences() {
+        let hasher = PermutationTable::new(9);
+
+        assert_derivative_matches(|point| simplex_2d(point.into(), &hasher));
+        assert_derivative_matches(|point| simplex_3d(point.into(), &hasher));
+        assert_derivative_matches(|point| simplex_4d(point.into(), &hasher));
+    }
+}
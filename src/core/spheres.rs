@@ -1,22 +1,121 @@
 use crate::math::vectors::{Vector2, Vector3, Vector4};
+use core::f64::consts::PI;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Shape of the value produced along the radius of the concentric generators.
+///
+/// Every profile outputs values in the range [-1, 1], with the radial
+/// distance measured after scaling by the frequency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RadialProfile {
+    /// Triangle wave with sharp shells. Outputs 1.0 on every integer radius and
+    /// falls linearly to -1.0 halfway between two shells.
+    Triangle,
+
+    /// Cosine wave with smooth shells. Outputs 1.0 on every integer radius and
+    /// -1.0 halfway between two shells, with no sharp creases.
+    Cosine,
+
+    /// A single falloff with no repeating shells. Outputs 1.0 at the center,
+    /// falls linearly to -1.0 at a radius of 1.0, and stays at -1.0 beyond it.
+    /// This is useful as an island mask.
+    Falloff,
+}
+
+impl RadialProfile {
+    /// Maps a radial distance onto the profile.
+    #[inline(always)]
+    pub fn map(self, dist_from_center: f64) -> f64 {
+        match self {
+            RadialProfile::Triangle => {
+                let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
+                let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
+                let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
+
+                1.0 - (nearest_dist * 4.0)
+            }
+            RadialProfile::Cosine => (dist_from_center * 2.0 * PI).cos(),
+            RadialProfile::Falloff => 1.0 - (dist_from_center.min(1.0) * 2.0),
+        }
+    }
+}
 
 macro_rules! impl_sphere {
-    ($name:ident, $vector:ty) => {
+    ($name:ident, $profile_name:ident, $vector:ty) => {
         #[inline(always)]
         pub fn $name(point: $vector, frequency: f64) -> f64 {
-            let point = point * frequency;
-
-            let dist_from_center = point.magnitude();
+            $profile_name(point, frequency, RadialProfile::Triangle)
+        }
 
-            let dist_from_smaller_sphere = dist_from_center - dist_from_center.floor();
-            let dist_from_larger_sphere = 1.0 - dist_from_smaller_sphere;
-            let nearest_dist = dist_from_smaller_sphere.min(dist_from_larger_sphere);
+        #[inline(always)]
+        pub fn $profile_name(point: $vector, frequency: f64, profile: RadialProfile) -> f64 {
+            let point = point * frequency;
 
-            1.0 - (nearest_dist * 4.0)
+            profile.map(point.magnitude())
         }
     };
 }
 
-impl_sphere!(spheres_2d, Vector2<f64>);
-impl_sphere!(spheres_3d, Vector3<f64>);
-impl_sphere!(spheres_4d, Vector4<f64>);
+impl_sphere!(spheres_2d, spheres_2d_with_profile, Vector2<f64>);
+impl_sphere!(spheres_3d, spheres_3d_with_profile, Vector3<f64>);
+impl_sphere!(spheres_4d, spheres_4d_with_profile, Vector4<f64>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(profile: RadialProfile, radius: f64) -> f64 {
+        spheres_2d_with_profile(Vector2::new(radius, 0.0), 1.0, profile)
+    }
+
+    #[test]
+    fn triangle_profile_has_sharp_shells() {
+        for &(radius, expected) in &[
+            (0.0, 1.0),
+            (0.25, 0.0),
+            (0.5, -1.0),
+            (1.0, 1.0),
+            (1.75, 0.0),
+        ] {
+            assert!((sample(RadialProfile::Triangle, radius) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cosine_profile_has_smooth_shells() {
+        for &(radius, expected) in &[
+            (0.0, 1.0),
+            (0.25, 0.0),
+            (0.5, -1.0),
+            (1.0, 1.0),
+            (1.75, 0.0),
+        ] {
+            assert!((sample(RadialProfile::Cosine, radius) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn falloff_profile_is_high_at_center() {
+        for &(radius, expected) in &[
+            (0.0, 1.0),
+            (0.25, 0.5),
+            (0.5, 0.0),
+            (1.0, -1.0),
+            (3.0, -1.0),
+        ] {
+            assert!((sample(RadialProfile::Falloff, radius) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn default_profile_is_triangle() {
+        let point = Vector3::new(0.3, -1.2, 0.7);
+
+        assert_eq!(
+            spheres_3d(point, 2.0),
+            spheres_3d_with_profile(point, 2.0, RadialProfile::Triangle)
+        );
+    }
+}
@@ -1,14 +1,85 @@
 use crate::{
-    math::{interpolate::linear, s_curve::quintic::Quintic, vectors::*},
+    math::{
+        interpolate::linear,
+        s_curve::{cubic::Cubic, quintic::Quintic},
+        to_lattice,
+        vectors::*,
+    },
     permutationtable::NoiseHasher,
 };
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Curve used to blend between the values at the lattice points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Straight linear blending. The noise is continuous, but its slope jumps
+    /// at every lattice line, which shows up as a visible grid.
+    Linear,
+
+    /// Cubic S-curve, 3t<sup>2</sup> - 2t<sup>3</sup>. The slope is continuous,
+    /// but the curvature still jumps at the lattice lines.
+    Cubic,
+
+    /// Quintic S-curve, 6t<sup>5</sup> - 15t<sup>4</sup> + 10t<sup>3</sup>,
+    /// which also keeps the curvature continuous.
+    #[default]
+    Quintic,
+}
+
+impl Interpolation {
+    /// Maps the position of a point between two lattice points, from 0.0 to
+    /// 1.0, onto the blending weight of the second lattice point.
+    #[inline(always)]
+    pub fn map(self, t: f64) -> f64 {
+        match self {
+            Interpolation::Linear => t.clamp(0.0, 1.0),
+            Interpolation::Cubic => t.map_cubic(),
+            Interpolation::Quintic => t.map_quintic(),
+        }
+    }
+}
+
+pub fn value_1d<NH>(point: f64, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    value_1d_with_interpolation(point, hasher, Interpolation::Quintic)
+}
+
+pub fn value_1d_with_interpolation<NH>(point: f64, hasher: &NH, interpolation: Interpolation) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let corner = to_lattice(point.floor());
+    let weight = interpolation.map(point - corner as f64);
+
+    let f0 = hasher.hash(&[corner]) as f64 / 255.0;
+    let f1 = hasher.hash(&[corner + 1]) as f64 / 255.0;
+
+    let result = linear(f0, f1, weight);
+
+    result * 2.0 - 1.0
+}
 
 pub fn value_2d<NH>(point: Vector2<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    value_2d_with_interpolation(point, hasher, Interpolation::Quintic)
+}
+
+pub fn value_2d_with_interpolation<NH>(
+    point: Vector2<f64>,
+    hasher: &NH,
+    interpolation: Interpolation,
+) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
     let corner = point.floor_to_isize();
-    let weight = (point - corner.numcast().unwrap()).map_quintic();
+    let weight = (point - corner.numcast().unwrap()).map(|t| interpolation.map(t));
 
     macro_rules! get(
         ($offset:expr) => {
@@ -33,11 +104,22 @@ where
 }
 
 pub fn value_3d<NH>(point: Vector3<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    value_3d_with_interpolation(point, hasher, Interpolation::Quintic)
+}
+
+pub fn value_3d_with_interpolation<NH>(
+    point: Vector3<f64>,
+    hasher: &NH,
+    interpolation: Interpolation,
+) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
     let corner = point.floor_to_isize();
-    let weight = (point - corner.numcast().unwrap()).map_quintic();
+    let weight = (point - corner.numcast().unwrap()).map(|t| interpolation.map(t));
 
     macro_rules! get(
         ($offset:expr) => {
@@ -74,11 +156,22 @@ where
 }
 
 pub fn value_4d<NH>(point: Vector4<f64>, hasher: &NH) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    value_4d_with_interpolation(point, hasher, Interpolation::Quintic)
+}
+
+pub fn value_4d_with_interpolation<NH>(
+    point: Vector4<f64>,
+    hasher: &NH,
+    interpolation: Interpolation,
+) -> f64
 where
     NH: NoiseHasher + ?Sized,
 {
     let corner = point.floor_to_isize();
-    let weight = (point - corner.numcast().unwrap()).map_quintic();
+    let weight = (point - corner.numcast().unwrap()).map(|t| interpolation.map(t));
 
     macro_rules! get(
         ($offset:expr) => {
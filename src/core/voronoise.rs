@@ -0,0 +1,106 @@
+//! Voronoise, after Inigo Quilez's article of the same name.
+//!
+//! Voronoise generalizes value noise and cellular noise into one family. Each
+//! cell holds a random value at a jittered point, and the output is a weighted
+//! average of the values of the nearby cells, with weights that fall off with
+//! the distance to their points. The `jitter` parameter moves the points from
+//! the cell corners (value noise) to random positions (cellular noise), and
+//! the `smoothness` parameter widens the weights from only the nearest point
+//! (sharp cells) to all of the nearby points (smooth blending).
+
+use crate::{
+    math::{
+        to_lattice,
+        vectors::{Vector2, Vector3},
+    },
+    permutationtable::NoiseHasher,
+};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// 2-dimensional voronoise in the [-1, 1] range.
+///
+/// `jitter` and `smoothness` are usually in the [0, 1] range, and correspond
+/// to the `u` and `v` parameters of the original.
+pub fn voronoise_2d<NH>(point: Vector2<f64>, hasher: &NH, jitter: f64, smoothness: f64) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    voronoise(point.into_array(), hasher, jitter, smoothness)
+}
+
+/// 3-dimensional voronoise in the [-1, 1] range.
+///
+/// See [`voronoise_2d`] for the parameters.
+pub fn voronoise_3d<NH>(point: Vector3<f64>, hasher: &NH, jitter: f64, smoothness: f64) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    voronoise(point.into_array(), hasher, jitter, smoothness)
+}
+
+#[inline(always)]
+fn voronoise<NH, const DIM: usize>(
+    point: [f64; DIM],
+    hasher: &NH,
+    jitter: f64,
+    smoothness: f64,
+) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let cell = point.map(|x| to_lattice(x.floor()));
+    let fract = point.map(|x| x - x.floor());
+
+    // Sharpness of the weights. At a smoothness of 0.0 only the nearest point
+    // has a noticeable weight.
+    let sharpness = 1.0 + 63.0 * (1.0 - smoothness).powi(6);
+    let max_distance = (DIM as f64).sqrt();
+
+    let mut value_sum = 0.0;
+    let mut weight_sum = 0.0;
+    for n in 0..5usize.pow(DIM as u32) {
+        let mut neighbor = cell;
+        let mut offset = [0.0; DIM];
+        let mut rest = n;
+        for axis in 0..DIM {
+            let step = (rest % 5) as isize - 2;
+            rest /= 5;
+
+            neighbor[axis] += step;
+            offset[axis] = step as f64 - fract[axis];
+        }
+
+        let mut distance_squared = 0.0;
+        for (axis, x) in offset.iter_mut().enumerate() {
+            *x += cell_random(hasher, &neighbor, axis) * jitter;
+            distance_squared += *x * *x;
+        }
+
+        let t = (distance_squared.sqrt() / max_distance).clamp(0.0, 1.0);
+        let weight = (1.0 - t * t * (3.0 - 2.0 * t)).powf(sharpness);
+
+        value_sum += cell_random(hasher, &neighbor, DIM) * weight;
+        weight_sum += weight;
+    }
+
+    if weight_sum == 0.0 {
+        return 0.0;
+    }
+
+    value_sum / weight_sum * 2.0 - 1.0
+}
+
+/// Returns a random value in [0, 1] for `cell`, with a different stream for
+/// each `key`.
+#[inline(always)]
+fn cell_random<NH, const DIM: usize>(hasher: &NH, cell: &[isize; DIM], key: usize) -> f64
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let mut to_hash = [0; 5];
+    to_hash[..DIM].copy_from_slice(cell);
+    to_hash[DIM] = key as isize;
+
+    (hasher.hash(&to_hash[..=DIM]) & 0xff) as f64 / 255.0
+}
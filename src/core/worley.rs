@@ -1,16 +1,49 @@
 use crate::{
-    math::vectors::{Vector2, Vector3, Vector4},
+    math::{
+        to_lattice,
+        vectors::{Vector2, Vector3, Vector4},
+    },
     permutationtable::NoiseHasher,
 };
 use core::f64;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReturnType {
+    /// Distance to the nearest seed point, commonly called F1, in cells.
+    ///
+    /// The output is `F1 * 2 - 1`, so it is -1.0 on the seed points and rises
+    /// with the distance from them.
     Distance,
+
+    /// Value of the cell containing the point, in the [-1, 1] range.
     Value,
+
+    /// Distance to the second-nearest seed point, commonly called F2, in
+    /// cells.
+    ///
+    /// The output is `F2 * 2 - 1`.
+    Distance2,
+
+    /// Difference between the distances to the second-nearest and the nearest
+    /// seed points, F2 - F1, in cells. This falls to zero along the borders
+    /// between cells.
+    ///
+    /// The output is `(F2 - F1) * 2 - 1`, so it is -1.0 on the borders.
+    Distance2Sub,
+
+    /// Value of the cell containing the point, in the [0, 1] range. This is
+    /// the same cell value as [`Value`](Self::Value) without the remapping to
+    /// [-1, 1], for use as a mask.
+    ValueUnsigned,
 }
 
 pub mod distance_functions {
+    #[cfg(not(any(feature = "std", test)))]
+    use num_traits::Float;
+
     pub fn euclidean(p1: &[f64], p2: &[f64]) -> f64 {
         p1.iter()
             .zip(p2)
@@ -43,6 +76,210 @@ pub mod distance_functions {
             .map(|a| a.abs())
             .fold(f64::MIN, |a, b| a.max(b))
     }
+
+    /// Sum of the products of every pair of components of the difference
+    /// vector, including each component with itself. This equals the square of
+    /// the sum of the differences, giving cells elongated along the diagonal.
+    ///
+    /// This is not a metric: it is zero for every difference whose components
+    /// sum to zero, so points anywhere along an anti-diagonal through a seed
+    /// point are at distance zero from it. Worley noise assumes that distant
+    /// seed points are far away and only searches the cells around the point,
+    /// so with this function a seed point outside of the search can have a
+    /// smaller value than the nearest one found, and the cells are cut off at
+    /// the edge of the search. The output is kept for compatibility, but is
+    /// better treated as a stylized pattern than as a distance.
+    pub fn quadratic(p1: &[f64], p2: &[f64]) -> f64 {
+        let mut result = 0.0;
+        for (a1, b1) in p1.iter().zip(p2) {
+            for (a2, b2) in p1.iter().zip(p2) {
+                result += (*a1 - *b1) * (*a2 - *b2);
+            }
+        }
+        result
+    }
+
+    /// Returns the Minkowski distance of order `p`, `(Σ|a - b|^p)^(1/p)`.
+    ///
+    /// This is a continuous knob over the cell shapes: `p = 1` gives the
+    /// [`manhattan`] distance, `p = 2` the [`euclidean`] distance, and the
+    /// result approaches the [`chebyshev`] distance as `p` grows. Orders below
+    /// 1 are not proper distances, but give star-shaped cells.
+    ///
+    /// The returned closure can't be serialized, see
+    /// [`Worley`](crate::Worley).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `p` is not positive.
+    pub fn minkowski(p: f64) -> impl Fn(&[f64], &[f64]) -> f64 + Copy {
+        assert!(p > 0.0, "minkowski order must be positive, got {}", p);
+
+        move |p1: &[f64], p2: &[f64]| {
+            // Dividing by the largest component keeps the powers from
+            // overflowing for large orders.
+            let max = chebyshev(p1, p2);
+            if max == 0.0 {
+                return 0.0;
+            }
+
+            let sum = p1
+                .iter()
+                .zip(p2)
+                .map(|(a, b)| ((*a - *b).abs() / max).powf(p))
+                .fold(0.0, |acc, x| acc + x);
+
+            max * sum.powf(p.recip())
+        }
+    }
+}
+
+/// Offset of the seed point of the cell with corner `cell` and hash `index`
+/// from that corner.
+///
+/// With a positive `min_spacing`, the seed point is relaxed away from the seed
+/// points of the neighboring cells that are nearer than `min_spacing`, by half
+/// of the shortfall for each of them. Since both seed points of a pair move
+/// apart, a pair without other close neighbors ends up `min_spacing` apart.
+/// Only the unrelaxed neighbors are considered, so the result is deterministic
+/// and the total movement is limited to half of `min_spacing`.
+#[inline(always)]
+fn seed_offset<NH, G, const DIM: usize>(
+    hasher: &NH,
+    cell: [isize; DIM],
+    index: usize,
+    jitter: f64,
+    min_spacing: f64,
+    get_offset: G,
+) -> [f64; DIM]
+where
+    NH: NoiseHasher + ?Sized,
+    G: Fn(usize) -> [f64; DIM],
+{
+    let offset = get_offset(index).map(|x| x * jitter);
+    if min_spacing <= 0.0 {
+        return offset;
+    }
+
+    let mut push = [0.0; DIM];
+    for n in 0..3usize.pow(DIM as u32) {
+        let mut step = [0isize; DIM];
+        let mut rest = n;
+        for x in step.iter_mut() {
+            *x = (rest % 3) as isize - 1;
+            rest /= 3;
+        }
+        if step == [0; DIM] {
+            continue;
+        }
+
+        let mut neighbor = cell;
+        for (x, d) in neighbor.iter_mut().zip(step) {
+            *x += d;
+        }
+        let neighbor_offset = get_offset(hasher.hash(&neighbor));
+
+        // Vector from the neighbor's seed point to this one.
+        let mut delta = [0.0; DIM];
+        for axis in 0..DIM {
+            delta[axis] = offset[axis] - (neighbor_offset[axis] * jitter + step[axis] as f64);
+        }
+        let distance = delta.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+        if distance < min_spacing {
+            // Coincident seed points are pushed apart along the cell offset.
+            let (direction, length) = if distance > 0.0 {
+                (delta, distance)
+            } else {
+                let length = (step.iter().map(|x| x * x).sum::<isize>() as f64).sqrt();
+                (step.map(|x| -x as f64), length)
+            };
+            let amount = (min_spacing - distance) * 0.5 / length;
+            for (x, d) in push.iter_mut().zip(direction) {
+                *x += d * amount;
+            }
+        }
+    }
+
+    let length = push.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let scale = if length > min_spacing * 0.5 {
+        min_spacing * 0.5 / length
+    } else {
+        1.0
+    };
+
+    let mut result = offset;
+    for (x, d) in result.iter_mut().zip(push) {
+        *x += d * scale;
+    }
+    result
+}
+
+/// Output value for the return types that need the second-nearest seed point.
+///
+/// These test every cell in a window around the point's nearest cell corner,
+/// instead of the few neighbors that suffice for the nearest seed point. Each
+/// seed point lies within half a cell times the jitter of its corner, and the
+/// window is wide enough that with the euclidean distance no seed point outside
+/// of it can be nearer than the second-nearest one inside it. With the default
+/// jitter of 1.0 the window extends two cells to each side.
+/// [`distance_functions::quadratic`] is not a metric, so with it a nearer seed
+/// point can lie outside of the window.
+fn second_nearest_value<F, NH, G, const DIM: usize>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: [f64; DIM],
+    jitter: f64,
+    min_spacing: f64,
+    get_offset: G,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+    G: Fn(usize) -> [f64; DIM],
+{
+    // The second-nearest seed point is at most sqrt((DIM - 1) / 4 + 1) plus the
+    // jitter radius away, which is under 1.33 + jitter / 2 for up to four
+    // dimensions, while the seed points of corners `k` cells away along an
+    // axis are at least k - 0.5 minus the jitter radius away. Relaxation can
+    // move a seed point up to another half of the minimum spacing.
+    let radius = ((1.83 + jitter.abs() + min_spacing).ceil() as isize - 1).max(1);
+    let width = (2 * radius + 1) as usize;
+
+    let near = point.map(|x| to_lattice((x - 0.5).ceil()));
+
+    let mut nearest = f64::MAX;
+    let mut second_nearest = f64::MAX;
+    for n in 0..width.pow(DIM as u32) {
+        let mut cell = near;
+        let mut rest = n;
+        for x in cell.iter_mut() {
+            *x += (rest % width) as isize - radius;
+            rest /= width;
+        }
+
+        let index = hasher.hash(&cell);
+        let mut seed_point = seed_offset(hasher, cell, index, jitter, min_spacing, &get_offset);
+        for (x, whole) in seed_point.iter_mut().zip(cell) {
+            *x += whole as f64;
+        }
+
+        let distance = distance_function(&point, &seed_point);
+        if distance < nearest {
+            second_nearest = nearest;
+            nearest = distance;
+        } else if distance < second_nearest {
+            second_nearest = distance;
+        }
+    }
+
+    let value = match return_type {
+        ReturnType::Distance2Sub => second_nearest - nearest,
+        _ => second_nearest,
+    };
+
+    value * 2.0 - 1.0
 }
 
 pub fn worley_2d<F, NH>(
@@ -55,10 +292,127 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
-    fn get_point(index: usize, whole: Vector2<isize>) -> Vector2<f64> {
-        get_vec2(index) + whole.numcast().unwrap()
+    worley_2d_with_jitter(hasher, distance_function, return_type, point, 1.0)
+}
+
+/// Same as [`worley_2d`], with each seed point's offset from its cell corner
+/// multiplied by `jitter`. A jitter of 0.0 places the seed points on a regular
+/// grid.
+pub fn worley_2d_with_jitter<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector2<f64>,
+    jitter: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_2d_with_spacing(hasher, distance_function, return_type, point, jitter, 0.0)
+}
+
+/// Same as [`worley_2d_with_jitter`], with the seed points relaxed apart to
+/// keep them at least roughly `min_spacing` from the seed points of their
+/// neighboring cells. A spacing of 0.0 leaves the seed points in place.
+pub fn worley_2d_with_spacing<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector2<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    if let ReturnType::Distance2 | ReturnType::Distance2Sub = return_type {
+        return second_nearest_value(
+            hasher,
+            distance_function,
+            return_type,
+            point.into_array(),
+            jitter,
+            min_spacing,
+            |index| get_vec2(index).into_array(),
+        );
     }
 
+    let (distance, seed_index, _) =
+        nearest_seed_2d(hasher, distance_function, point, jitter, min_spacing);
+
+    let value = match return_type {
+        ReturnType::Distance => distance,
+        ReturnType::Value => seed_index as f64 / 255.0,
+        // Already in [0, 1], so this skips the remapping below.
+        ReturnType::ValueUnsigned => return seed_index as f64 / 255.0,
+        ReturnType::Distance2 | ReturnType::Distance2Sub => unreachable!(),
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Position of the seed point of the cell at the lattice point `cell`, as used
+/// by [`worley_2d_with_spacing`].
+pub fn worley_2d_seed_point<NH>(
+    hasher: &NH,
+    cell: [isize; 2],
+    jitter: f64,
+    min_spacing: f64,
+) -> Vector2<f64>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    let offset = seed_offset(hasher, cell, hasher.hash(&cell), jitter, min_spacing, |i| {
+        get_vec2(i).into_array()
+    });
+
+    Vector2::new(offset[0] + cell[0] as f64, offset[1] + cell[1] as f64)
+}
+
+/// Position of the seed point nearest to `point`, as used by
+/// [`worley_2d_with_spacing`] with the `Distance` and `Value` return types.
+pub fn worley_2d_cell_point<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector2<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> Vector2<f64>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    nearest_seed_2d(hasher, distance_function, point, jitter, min_spacing).2
+}
+
+/// Finds the seed point nearest to `point`, returning its distance, the hash of
+/// its cell and its position.
+#[inline(always)]
+fn nearest_seed_2d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector2<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> (f64, usize, Vector2<f64>)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let get_point = |index: usize, whole: Vector2<isize>| -> Vector2<f64> {
+        let offset = seed_offset(
+            hasher,
+            whole.into_array(),
+            index,
+            jitter,
+            min_spacing,
+            |i| get_vec2(i).into_array(),
+        );
+        Vector2::from(offset) + whole.numcast().unwrap()
+    };
+
     let cell = point.floor_to_isize();
     let floor = cell.numcast().unwrap();
     let frac = point - floor;
@@ -68,9 +422,10 @@ where
     let near = half.map(|x| x as isize) + cell;
     let far = half.map(|x| !x as isize) + cell;
 
-    let mut seed_cell = near;
-    let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    // The hash of the nearest cell doubles as its value, so it is kept from the
+    // search rather than being recomputed once the nearest cell is known.
+    let mut seed_index = hasher.hash(&near.into_array());
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -84,7 +439,8 @@ where
                 let cur_distance = distance_function(&point.into_array(), &offset.into_array());
                 if cur_distance < distance {
                     distance = cur_distance;
-                    seed_cell = test_point;
+                    seed_index = index;
+                    seed_point = offset;
                 }
             }
         }
@@ -102,12 +458,7 @@ where
         test_point![far.x, far.y];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
-    };
-
-    value * 2.0 - 1.0
+    (distance, seed_index, seed_point)
 }
 
 #[rustfmt::skip]
@@ -139,10 +490,111 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
-    fn get_point(index: usize, whole: Vector3<isize>) -> Vector3<f64> {
-        get_vec3(index) + whole.numcast().unwrap()
+    worley_3d_with_jitter(hasher, distance_function, return_type, point, 1.0)
+}
+
+/// Same as [`worley_3d`], with each seed point's offset from its cell corner
+/// multiplied by `jitter`. A jitter of 0.0 places the seed points on a regular
+/// grid.
+#[inline(always)]
+pub fn worley_3d_with_jitter<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector3<f64>,
+    jitter: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_3d_with_spacing(hasher, distance_function, return_type, point, jitter, 0.0)
+}
+
+/// Same as [`worley_3d_with_jitter`], with the seed points relaxed apart to
+/// keep them at least roughly `min_spacing` from the seed points of their
+/// neighboring cells. A spacing of 0.0 leaves the seed points in place.
+#[inline(always)]
+pub fn worley_3d_with_spacing<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector3<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    if let ReturnType::Distance2 | ReturnType::Distance2Sub = return_type {
+        return second_nearest_value(
+            hasher,
+            distance_function,
+            return_type,
+            point.into_array(),
+            jitter,
+            min_spacing,
+            |index| get_vec3(index).into_array(),
+        );
     }
 
+    let (distance, seed_index, _) =
+        nearest_seed_3d(hasher, distance_function, point, jitter, min_spacing);
+
+    let value = match return_type {
+        ReturnType::Distance => distance,
+        ReturnType::Value => seed_index as f64 / 255.0,
+        // Already in [0, 1], so this skips the remapping below.
+        ReturnType::ValueUnsigned => return seed_index as f64 / 255.0,
+        ReturnType::Distance2 | ReturnType::Distance2Sub => unreachable!(),
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Position of the seed point nearest to `point`, as used by
+/// [`worley_3d_with_spacing`] with the `Distance` and `Value` return types.
+pub fn worley_3d_cell_point<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector3<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> Vector3<f64>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    nearest_seed_3d(hasher, distance_function, point, jitter, min_spacing).2
+}
+
+/// Finds the seed point nearest to `point`, returning its distance, the hash of
+/// its cell and its position.
+#[inline(always)]
+fn nearest_seed_3d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector3<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> (f64, usize, Vector3<f64>)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let get_point = |index: usize, whole: Vector3<isize>| -> Vector3<f64> {
+        let offset = seed_offset(
+            hasher,
+            whole.into_array(),
+            index,
+            jitter,
+            min_spacing,
+            |i| get_vec3(i).into_array(),
+        );
+        Vector3::from(offset) + whole.numcast().unwrap()
+    };
+
     let cell = point.floor_to_isize();
     let floor = cell.numcast().unwrap();
     let frac = point - floor;
@@ -152,9 +604,8 @@ where
     let near = half.map(|x| x as isize) + cell;
     let far = half.map(|x| !x as isize) + cell;
 
-    let mut seed_cell = near;
-    let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    let mut seed_index = hasher.hash(&near.into_array());
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -168,7 +619,8 @@ where
                 let cur_distance = distance_function(&point.into_array(), &offset.into_array());
                 if cur_distance < distance {
                     distance = cur_distance;
-                    seed_cell = test_point;
+                    seed_index = index;
+                    seed_point = offset;
                 }
             }
         }
@@ -198,12 +650,7 @@ where
         test_point![far.x, far.y, far.z];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
-    };
-
-    value * 2.0 - 1.0
+    (distance, seed_index, seed_point)
 }
 
 #[rustfmt::skip]
@@ -247,10 +694,113 @@ where
     F: Fn(&[f64], &[f64]) -> f64,
     NH: NoiseHasher + ?Sized,
 {
-    fn get_point(index: usize, whole: Vector4<isize>) -> Vector4<f64> {
-        get_vec4(index) + whole.numcast().unwrap()
+    worley_4d_with_jitter(hasher, distance_function, return_type, point, 1.0)
+}
+
+/// Same as [`worley_4d`], with each seed point's offset from its cell corner
+/// multiplied by `jitter`. A jitter of 0.0 places the seed points on a regular
+/// grid.
+#[inline(always)]
+#[allow(clippy::cognitive_complexity)]
+pub fn worley_4d_with_jitter<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector4<f64>,
+    jitter: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    worley_4d_with_spacing(hasher, distance_function, return_type, point, jitter, 0.0)
+}
+
+/// Same as [`worley_4d_with_jitter`], with the seed points relaxed apart to
+/// keep them at least roughly `min_spacing` from the seed points of their
+/// neighboring cells. A spacing of 0.0 leaves the seed points in place.
+#[inline(always)]
+#[allow(clippy::cognitive_complexity)]
+pub fn worley_4d_with_spacing<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    return_type: ReturnType,
+    point: Vector4<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> f64
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    if let ReturnType::Distance2 | ReturnType::Distance2Sub = return_type {
+        return second_nearest_value(
+            hasher,
+            distance_function,
+            return_type,
+            point.into_array(),
+            jitter,
+            min_spacing,
+            |index| get_vec4(index).into_array(),
+        );
     }
 
+    let (distance, seed_index, _) =
+        nearest_seed_4d(hasher, distance_function, point, jitter, min_spacing);
+
+    let value = match return_type {
+        ReturnType::Distance => distance,
+        ReturnType::Value => seed_index as f64 / 255.0,
+        // Already in [0, 1], so this skips the remapping below.
+        ReturnType::ValueUnsigned => return seed_index as f64 / 255.0,
+        ReturnType::Distance2 | ReturnType::Distance2Sub => unreachable!(),
+    };
+
+    value * 2.0 - 1.0
+}
+
+/// Position of the seed point nearest to `point`, as used by
+/// [`worley_4d_with_spacing`] with the `Distance` and `Value` return types.
+pub fn worley_4d_cell_point<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector4<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> Vector4<f64>
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    nearest_seed_4d(hasher, distance_function, point, jitter, min_spacing).2
+}
+
+/// Finds the seed point nearest to `point`, returning its distance, the hash of
+/// its cell and its position.
+#[inline(always)]
+fn nearest_seed_4d<F, NH>(
+    hasher: &NH,
+    distance_function: F,
+    point: Vector4<f64>,
+    jitter: f64,
+    min_spacing: f64,
+) -> (f64, usize, Vector4<f64>)
+where
+    F: Fn(&[f64], &[f64]) -> f64,
+    NH: NoiseHasher + ?Sized,
+{
+    let get_point = |index: usize, whole: Vector4<isize>| -> Vector4<f64> {
+        let offset = seed_offset(
+            hasher,
+            whole.into_array(),
+            index,
+            jitter,
+            min_spacing,
+            |i| get_vec4(i).into_array(),
+        );
+        Vector4::from(offset) + whole.numcast().unwrap()
+    };
+
     let cell = point.floor_to_isize();
     let floor = cell.numcast().unwrap();
     let frac = point - floor;
@@ -260,9 +810,8 @@ where
     let near = half.map(|x| x as isize) + cell;
     let far = half.map(|x| !x as isize) + cell;
 
-    let mut seed_cell = near;
-    let seed_index = hasher.hash(&near.into_array());
-    let seed_point = get_point(seed_index, near);
+    let mut seed_index = hasher.hash(&near.into_array());
+    let mut seed_point = get_point(seed_index, near);
     let mut distance = distance_function(&point.into_array(), &seed_point.into_array());
 
     let range = frac.map(|x| (0.5 - x).powf(2.0));
@@ -276,7 +825,8 @@ where
                 let cur_distance = distance_function(&point.into_array(), &offset.into_array());
                 if cur_distance < distance {
                     distance = cur_distance;
-                    seed_cell = test_point;
+                    seed_index = index;
+                    seed_point = offset;
                 }
             }
         }
@@ -331,12 +881,7 @@ where
         test_point![far.x, far.y, far.z, far.w];
     }
 
-    let value = match return_type {
-        ReturnType::Distance => distance,
-        ReturnType::Value => hasher.hash(&seed_cell.into_array()) as f64 / 255.0,
-    };
-
-    value * 2.0 - 1.0
+    (distance, seed_index, seed_point)
 }
 
 #[rustfmt::skip]
@@ -381,3 +926,238 @@ fn get_vec4(index: usize) -> Vector4<f64> {
         _ => unreachable!("Attempt to access 4D gradient {} of 32", index % 32),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permutationtable::PermutationTable;
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingHasher {
+        table: PermutationTable,
+        calls: AtomicUsize,
+    }
+
+    impl NoiseHasher for CountingHasher {
+        fn hash(&self, to_hash: &[isize]) -> usize {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.table.hash(to_hash)
+        }
+    }
+
+    fn hash_calls(return_type: ReturnType, point: Vector2<f64>) -> (f64, usize) {
+        let hasher = CountingHasher {
+            table: PermutationTable::new(0),
+            calls: AtomicUsize::new(0),
+        };
+        let value = worley_2d(&hasher, distance_functions::euclidean, return_type, point);
+
+        (value, hasher.calls.load(Ordering::Relaxed))
+    }
+
+    #[test]
+    fn quadratic_matches_collected_differences() {
+        fn collected(p1: &[f64], p2: &[f64]) -> f64 {
+            let temp: Vec<f64> = p1.iter().zip(p2).map(|(a, b)| *a - *b).collect();
+            let mut result = 0.0;
+            for i in &temp {
+                for j in &temp {
+                    result += *i * *j;
+                }
+            }
+            result
+        }
+
+        let pairs: [(&[f64], &[f64]); 4] = [
+            (&[0.3, -1.2], &[-0.4, 0.9]),
+            (&[0.3, -1.2, 2.5], &[-0.4, 0.9, 1.1]),
+            (&[1.0, 2.0, 3.0, 4.0], &[4.0, 3.0, 2.0, 1.0]),
+            (&[-7.25, 0.0, 1e-3, 12.5], &[3.5, -0.125, 8.0, 12.5]),
+        ];
+        for (p1, p2) in pairs {
+            assert_eq!(distance_functions::quadratic(p1, p2), collected(p1, p2));
+        }
+    }
+
+    #[test]
+    fn minkowski_spans_the_other_distances() {
+        let p1 = [0.3, -1.2, 2.5];
+        let p2 = [-0.4, 0.9, 1.1];
+
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+        assert!(close(
+            distance_functions::minkowski(1.0)(&p1, &p2),
+            distance_functions::manhattan(&p1, &p2)
+        ));
+        assert!(close(
+            distance_functions::minkowski(2.0)(&p1, &p2),
+            distance_functions::euclidean(&p1, &p2)
+        ));
+        assert!(
+            (distance_functions::minkowski(1000.0)(&p1, &p2)
+                - distance_functions::chebyshev(&p1, &p2))
+            .abs()
+                < 1e-3
+        );
+        assert!(distance_functions::minkowski(1e6)(&[1e300], &[-1e300]).is_finite());
+        assert_eq!(distance_functions::minkowski(3.0)(&p1, &p1), 0.0);
+    }
+
+    #[test]
+    fn return_types_hash_each_cell_once() {
+        for i in 0..64 {
+            let point = Vector2::new(i as f64 * 0.37 - 7.1, i as f64 * 0.53 + 2.9);
+
+            let (_, distance_calls) = hash_calls(ReturnType::Distance, point);
+            let (_, value_calls) = hash_calls(ReturnType::Value, point);
+
+            assert!((1..=4).contains(&distance_calls));
+            assert_eq!(distance_calls, value_calls);
+        }
+    }
+
+    #[test]
+    fn outputs_are_unchanged() {
+        // Recorded before the winning cell's hash was kept from the search.
+        let expected = [
+            (0.03923048454132738, 0.9607843137254901),
+            (-0.44214697276074477, 1.0),
+            (-0.27556918894900784, -1.0),
+            (-0.9717157287525388, 1.0),
+            (-0.1461811432379595, 0.9607843137254901),
+            (-0.7169787769836573, 0.968627450980392),
+            (0.2840893368256121, 0.968627450980392),
+            (-0.678728171592297, 0.968627450980392),
+        ];
+
+        for (i, &(distance, value)) in expected.iter().enumerate() {
+            let point = Vector2::new(i as f64 * 0.37 - 7.1, i as f64 * 0.53 + 2.9);
+
+            assert_eq!(hash_calls(ReturnType::Distance, point).0, distance);
+            assert_eq!(hash_calls(ReturnType::Value, point).0, value);
+            assert_eq!(
+                hash_calls(ReturnType::ValueUnsigned, point).0,
+                (value + 1.0) * 0.5
+            );
+        }
+    }
+
+    /// Nearest and second-nearest distances found by testing every cell
+    /// within four cells of the point.
+    fn brute_force_2d(hasher: &PermutationTable, point: Vector2<f64>) -> (f64, f64) {
+        let cell = point.floor_to_isize();
+        let mut distances = Vec::new();
+        for y in -4..=4 {
+            for x in -4..=4 {
+                let whole = cell + Vector2::new(x, y);
+                let seed_point =
+                    get_vec2(hasher.hash(&whole.into_array())) + whole.numcast().unwrap();
+                distances.push(distance_functions::euclidean(
+                    &point.into_array(),
+                    &seed_point.into_array(),
+                ));
+            }
+        }
+        distances.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        (distances[0], distances[1])
+    }
+
+    #[test]
+    fn second_nearest_matches_brute_force() {
+        for seed in 0..4 {
+            let hasher = PermutationTable::new(seed);
+
+            for i in 0..512 {
+                let point = Vector2::new(i as f64 * 0.0731 - 17.3, i as f64 * 0.1277 + 4.1);
+                let (nearest, second_nearest) = brute_force_2d(&hasher, point);
+
+                let f2 = worley_2d(
+                    &hasher,
+                    distance_functions::euclidean,
+                    ReturnType::Distance2,
+                    point,
+                );
+                let edge = worley_2d(
+                    &hasher,
+                    distance_functions::euclidean,
+                    ReturnType::Distance2Sub,
+                    point,
+                );
+
+                assert!((f2 - (second_nearest * 2.0 - 1.0)).abs() < 1e-12);
+                assert!((edge - ((second_nearest - nearest) * 2.0 - 1.0)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn second_nearest_is_never_nearer() {
+        let hasher = PermutationTable::new(0);
+
+        for i in 0..512 {
+            let t = i as f64 * 0.0731 - 17.3;
+            let point3 = Vector3::new(t, t * 0.7 + 3.0, -t * 1.3);
+            let point4 = Vector4::new(t, t * 0.7 + 3.0, -t * 1.3, t * 0.2);
+
+            let f1 = worley_3d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance,
+                point3,
+            );
+            let f2 = worley_3d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance2,
+                point3,
+            );
+            let edge = worley_4d(
+                &hasher,
+                distance_functions::euclidean,
+                ReturnType::Distance2Sub,
+                point4,
+            );
+
+            assert!(f2 >= f1);
+            assert!(edge >= -1.0);
+        }
+    }
+
+    fn min_seed_spacing_2d(hasher: &PermutationTable, min_spacing: f64) -> f64 {
+        let seed_point = |x: isize, y: isize| {
+            let index = hasher.hash(&[x, y]);
+            let offset = seed_offset(hasher, [x, y], index, 1.0, min_spacing, |i| {
+                get_vec2(i).into_array()
+            });
+            Vector2::new(offset[0] + x as f64, offset[1] + y as f64)
+        };
+
+        let mut min_distance = f64::MAX;
+        for y in -20..20 {
+            for x in -20..20 {
+                let point = seed_point(x, y);
+                for (dx, dy) in [(1, 0), (0, 1), (1, 1), (1, -1)] {
+                    let distance = distance_functions::euclidean(
+                        &point.into_array(),
+                        &seed_point(x + dx, y + dy).into_array(),
+                    );
+                    min_distance = min_distance.min(distance);
+                }
+            }
+        }
+
+        min_distance
+    }
+
+    #[test]
+    fn min_spacing_spreads_seed_points() {
+        let hasher = PermutationTable::new(5);
+
+        let spacings = [0.0, 0.3, 0.6, 0.9].map(|s| min_seed_spacing_2d(&hasher, s));
+        assert!(spacings.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(spacings[0] < 0.3);
+        assert!(spacings[2] > 0.45);
+    }
+}
@@ -1,3 +1,13 @@
+//! Gradient vectors used by the gradient noise functions.
+//!
+//! The `*_normalized` functions return the edge gradients of the classic
+//! improved Perlin gradient set, scaled to unit length. Noise built from unit
+//! gradients has a known maximum of `sqrt(N)/2`, which makes it possible to
+//! scale the output exactly into the [-1, 1] range.
+
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
 #[inline(always)]
 #[rustfmt::skip]
 pub(crate) fn grad2(index: usize) -> [f64; 2] {
@@ -113,3 +123,136 @@ pub(crate) fn grad4(index: usize) -> [f64; 4] {
         _       => panic!("Attempt to access gradient {} of 64", index % 64),
     }
 }
+
+#[inline(always)]
+#[rustfmt::skip]
+pub fn grad2_normalized(index: usize) -> [f64; 2] {
+    // Vectors are the diagonals of the unit square, scaled to unit length
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
+
+    match index % 4 {
+        0 => [ DIAG,  DIAG],
+        1 => [-DIAG,  DIAG],
+        2 => [ DIAG, -DIAG],
+        3 => [-DIAG, -DIAG],
+        _ => panic!("Attempt to access gradient {} of 4", index % 4),
+    }
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+pub fn grad3_normalized(index: usize) -> [f64; 3] {
+    // Vectors are the edges of the unit cube, scaled to unit length
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
+
+    match index % 16 {
+        // 12 edges, then 4 of them repeated to pad the table to 16 entries
+        0  | 12 => [  DIAG,   DIAG,    0.0],
+        1  | 13 => [ -DIAG,   DIAG,    0.0],
+        2       => [  DIAG,  -DIAG,    0.0],
+        3       => [ -DIAG,  -DIAG,    0.0],
+        4       => [  DIAG,    0.0,   DIAG],
+        5       => [ -DIAG,    0.0,   DIAG],
+        6       => [  DIAG,    0.0,  -DIAG],
+        7       => [ -DIAG,    0.0,  -DIAG],
+        8       => [   0.0,   DIAG,   DIAG],
+        9  | 14 => [   0.0,  -DIAG,   DIAG],
+        10      => [   0.0,   DIAG,  -DIAG],
+        11 | 15 => [   0.0,  -DIAG,  -DIAG],
+        _       => panic!("Attempt to access gradient {} of 16", index % 16),
+    }
+}
+
+#[inline(always)]
+#[rustfmt::skip]
+pub fn grad4_normalized(index: usize) -> [f64; 4] {
+    // Vectors are the edges of the unit tesseract, scaled to unit length
+    const DIAG : f64 = 0.577_350_269_189_625_8;
+
+    match index % 32 {
+        0  => [   0.0,   DIAG,   DIAG,   DIAG],
+        1  => [   0.0,   DIAG,   DIAG,  -DIAG],
+        2  => [   0.0,   DIAG,  -DIAG,   DIAG],
+        3  => [   0.0,   DIAG,  -DIAG,  -DIAG],
+        4  => [   0.0,  -DIAG,   DIAG,   DIAG],
+        5  => [   0.0,  -DIAG,   DIAG,  -DIAG],
+        6  => [   0.0,  -DIAG,  -DIAG,   DIAG],
+        7  => [   0.0,  -DIAG,  -DIAG,  -DIAG],
+        8  => [  DIAG,    0.0,   DIAG,   DIAG],
+        9  => [  DIAG,    0.0,   DIAG,  -DIAG],
+        10 => [  DIAG,    0.0,  -DIAG,   DIAG],
+        11 => [  DIAG,    0.0,  -DIAG,  -DIAG],
+        12 => [ -DIAG,    0.0,   DIAG,   DIAG],
+        13 => [ -DIAG,    0.0,   DIAG,  -DIAG],
+        14 => [ -DIAG,    0.0,  -DIAG,   DIAG],
+        15 => [ -DIAG,    0.0,  -DIAG,  -DIAG],
+        16 => [  DIAG,   DIAG,    0.0,   DIAG],
+        17 => [  DIAG,   DIAG,    0.0,  -DIAG],
+        18 => [  DIAG,  -DIAG,    0.0,   DIAG],
+        19 => [  DIAG,  -DIAG,    0.0,  -DIAG],
+        20 => [ -DIAG,   DIAG,    0.0,   DIAG],
+        21 => [ -DIAG,   DIAG,    0.0,  -DIAG],
+        22 => [ -DIAG,  -DIAG,    0.0,   DIAG],
+        23 => [ -DIAG,  -DIAG,    0.0,  -DIAG],
+        24 => [  DIAG,   DIAG,   DIAG,    0.0],
+        25 => [  DIAG,   DIAG,  -DIAG,    0.0],
+        26 => [  DIAG,  -DIAG,   DIAG,    0.0],
+        27 => [  DIAG,  -DIAG,  -DIAG,    0.0],
+        28 => [ -DIAG,   DIAG,   DIAG,    0.0],
+        29 => [ -DIAG,   DIAG,  -DIAG,    0.0],
+        30 => [ -DIAG,  -DIAG,   DIAG,    0.0],
+        31 => [ -DIAG,  -DIAG,  -DIAG,    0.0],
+        _  => panic!("Attempt to access gradient {} of 32", index % 32),
+    }
+}
+
+/// A user supplied set of gradient vectors for gradient noise.
+///
+/// Each lattice point picks the gradient at its hash value modulo the number
+/// of gradients, so tables of any size can be used, although a power of two
+/// that divides 256 picks every gradient equally often. The gradients do not
+/// need to be unit length; noise built from the table is scaled by the longest
+/// gradient to keep the output in the [-1, 1] range.
+#[derive(Clone, Copy, Debug)]
+pub struct GradientTable<const DIM: usize> {
+    gradients: &'static [[f64; DIM]],
+    max_length: f64,
+}
+
+impl<const DIM: usize> GradientTable<DIM> {
+    /// Creates a table from the given gradients.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gradients` is empty or only contains zero vectors.
+    pub fn new(gradients: &'static [[f64; DIM]]) -> Self {
+        let max_length = gradients
+            .iter()
+            .map(|gradient| gradient.iter().map(|x| x * x).sum::<f64>().sqrt())
+            .fold(0.0, f64::max);
+
+        assert!(
+            max_length > 0.0,
+            "gradient table needs at least one non-zero gradient"
+        );
+
+        Self {
+            gradients,
+            max_length,
+        }
+    }
+
+    pub fn gradients(&self) -> &'static [[f64; DIM]] {
+        self.gradients
+    }
+
+    /// Returns the length of the longest gradient in the table.
+    pub fn max_length(&self) -> f64 {
+        self.max_length
+    }
+
+    #[inline(always)]
+    pub fn get(&self, index: usize) -> [f64; DIM] {
+        self.gradients[index % self.gradients.len()]
+    }
+}
@@ -8,19 +8,57 @@
 //! let perlin = Perlin::new(1);
 //! let val = perlin.get([42.4, 37.7, 2.8]);
 //! ```
+//!
+//! # Determinism
+//!
+//! Rust never reassociates floating-point arithmetic, and the fractal
+//! generators accumulate their octaves strictly in order and compute their
+//! amplitudes and scale factors with plain multiplications rather than
+//! `powi`. With the same parameters and seeds, the lattice generators and the
+//! fractals built from them therefore produce bit-identical output on every
+//! target, which makes them suitable for terrain that has to match between
+//! machines. Noise functions that call transcendental functions such as `sin`,
+//! `exp` or `powf` rely on the platform's math library and may differ in the
+//! last bits.
+//!
+//! # `no_std`
+//!
+//! The crate only needs `alloc`. Floating-point math comes from `std` with the
+//! default `std` feature, or from the `libm` crate with the `libm` feature, so
+//! bare-metal targets can depend on it with
+//! `default-features = false, features = ["libm"]`. The API is the same either
+//! way:
+//!
+//! ```rust
+//! use noise::{Fbm, MultiFractal, NoiseFn, Perlin, Worley};
+//!
+//! let fbm = Fbm::<Perlin>::new(0).set_octaves(4);
+//! let worley = Worley::new(0);
+//! let height = fbm.get([0.5, 1.25]) + worley.get([0.5, 1.25]);
+//! assert!(height.is_finite());
+//! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
+// Modules import `num_traits::Float` for the math methods of `f64` unless
+// `std` provides them, which it also does for the test harness.
 #![deny(missing_copy_implementations)]
 
 #[macro_use]
 extern crate alloc;
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature must be enabled for floating-point math");
+
+#[cfg(feature = "twofloat")]
+pub use crate::math::double_double::DoubleDouble;
 pub use crate::math::vectors::*;
 pub use crate::noise_fns::*;
+pub use crate::permutationtable::NoiseHasher;
 
 pub mod core;
-mod gradient;
+pub mod gradient;
 pub mod math;
 mod noise_fns;
 pub mod permutationtable;
+pub mod presets;
 pub mod utils;
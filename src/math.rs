@@ -1,10 +1,76 @@
 //! An ultra-light private math library to make our short lives easier as we
 //! implement super-complex noise stuff.
 
+#[cfg(feature = "twofloat")]
+pub mod double_double;
 pub(crate) mod interpolate;
 pub(crate) mod s_curve;
 pub mod vectors;
 
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Remainder of `x / y` in [0, |y|), like `f64::rem_euclid`, which is not
+/// available without `std`.
+#[inline]
+pub(crate) fn rem_euclid(x: f64, y: f64) -> f64 {
+    let r = x % y;
+
+    if r < 0.0 {
+        r + y.abs()
+    } else {
+        r
+    }
+}
+
+/// Margin kept between lattice coordinates and the limits of `isize`, so that
+/// the small offsets added to a lattice cell to reach its neighbors can't
+/// overflow.
+const LATTICE_MARGIN: isize = 16;
+
+/// Converts a floored coordinate into a lattice coordinate.
+///
+/// Coordinates that don't fit in an `isize`, including infinities and NaN, are
+/// clamped instead of panicking. The clamped cell is still within rounding of
+/// the coordinate up to `isize::MAX`, since `f64` can't represent the margin at
+/// that magnitude anyway.
+#[inline]
+pub(crate) fn to_lattice<T>(floored: T) -> isize
+where
+    T: num_traits::real::Real,
+{
+    let max = isize::MAX - LATTICE_MARGIN;
+
+    match <isize as num_traits::NumCast>::from(floored) {
+        Some(cell) => cell.clamp(-max, max),
+        None if floored > T::zero() => max,
+        None => -max,
+    }
+}
+
+/// Raises `base` to the power `exp` by multiplying it in one factor at a time.
+///
+/// The precision of `f64::powi` is unspecified, and its result can differ
+/// between targets. This always performs the same multiplications in the same
+/// order, so the result is identical everywhere, and it matches an amplitude
+/// that is multiplied by `base` once per octave.
+#[inline]
+pub(crate) fn pow_sequential(base: f64, exp: usize) -> f64 {
+    (0..exp).fold(1.0, |acc, _| acc * base)
+}
+
+/// Maps `value` from `(min, max)` onto [0, 1].
+#[inline]
+pub(crate) fn to_unit(value: f64, (min, max): (f64, f64)) -> f64 {
+    (value - min) / (max - min)
+}
+
+/// Maps `value` from [0, 1] onto `(min, max)`.
+#[inline]
+pub(crate) fn from_unit(value: f64, (min, max): (f64, f64)) -> f64 {
+    min + value * (max - min)
+}
+
 #[cfg(not(target_os = "emscripten"))]
 #[inline]
 pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
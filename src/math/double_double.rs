@@ -0,0 +1,137 @@
+//! Extended precision coordinates, stored as the unevaluated sum of two `f64`s.
+//!
+//! An `f64` has 52 bits of mantissa, so at a coordinate of 1e12 only about 13
+//! bits remain for the position within a lattice cell, and the noise turns
+//! blocky. Splitting the coordinate into a high and a low part keeps the
+//! fractional bits, and the lattice cell and the offset into it can then be
+//! computed without rounding.
+
+use core::ops::{Add, Mul, Sub};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// A coordinate with about twice the precision of an `f64`, the exact sum of
+/// `hi` and `lo`.
+///
+/// Only the operations needed to place points on the noise lattice are
+/// provided: building a coordinate from a large base and a small offset,
+/// addition, subtraction, and multiplication by an `f64`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+impl DoubleDouble {
+    /// Creates the coordinate `hi + lo`, without rounding the sum.
+    pub fn new(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = two_sum(hi, lo);
+
+        Self { hi, lo }
+    }
+
+    /// Returns the coordinate rounded to the nearest `f64`.
+    pub fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    /// Returns the largest integer less than or equal to the coordinate.
+    pub fn floor(self) -> f64 {
+        let floor = self.hi.floor();
+
+        // If `hi` has a fractional part, `lo` is too small to move the sum past
+        // an integer.
+        if floor == self.hi {
+            floor + self.lo.floor()
+        } else {
+            floor
+        }
+    }
+}
+
+impl From<f64> for DoubleDouble {
+    fn from(value: f64) -> Self {
+        Self { hi: value, lo: 0.0 }
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (hi, lo) = two_sum(self.hi, other.hi);
+        let (hi, lo) = quick_two_sum(hi, lo + self.lo + other.lo);
+
+        Self { hi, lo }
+    }
+}
+
+impl Add<f64> for DoubleDouble {
+    type Output = Self;
+
+    fn add(self, other: f64) -> Self {
+        self + Self::from(other)
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + Self {
+            hi: -other.hi,
+            lo: -other.lo,
+        }
+    }
+}
+
+impl Sub<f64> for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, other: f64) -> Self {
+        self + Self::from(-other)
+    }
+}
+
+impl Mul<f64> for DoubleDouble {
+    type Output = Self;
+
+    fn mul(self, other: f64) -> Self {
+        let hi = self.hi * other;
+        let lo = self.hi.mul_add(other, -hi);
+        let (hi, lo) = quick_two_sum(hi, lo + self.lo * other);
+
+        Self { hi, lo }
+    }
+}
+
+/// Sum of `a` and `b`, and the rounding error of the sum.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let error = (a - (sum - b_virtual)) + (b - b_virtual);
+
+    (sum, error)
+}
+
+/// Same as [`two_sum`], for `|a| >= |b|`.
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+
+    (sum, b - (sum - a))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_small_offsets_of_large_values() {
+        let x = DoubleDouble::new(1e12, 0.123_456_789);
+
+        assert_eq!((x - 1e12).to_f64(), 0.123_456_789);
+        assert_eq!(x.floor(), 1e12);
+        assert_eq!((x * 4.0 - 4e12).to_f64(), 0.493_827_156);
+        assert_eq!(DoubleDouble::new(1e12, -0.25).floor(), 1e12 - 1.0);
+    }
+}
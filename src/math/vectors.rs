@@ -155,9 +155,9 @@ macro_rules! vector_type {
             {
                 $type_name {
                     $($dim: if self.$dim <= T::zero() {
-                        <isize as NumCast>::from(self.$dim).unwrap() - 1
+                        crate::math::to_lattice(self.$dim.trunc()) - 1
                     } else {
-                        <isize as NumCast>::from(self.$dim).unwrap()
+                        crate::math::to_lattice(self.$dim.trunc())
                     }),+
                 }
             }
@@ -1,14 +1,18 @@
 pub use self::{
-    cache::*, combiners::*, generators::*, modifiers::*, selectors::*, transformers::*,
+    cache::*, combiners::*, finite_difference::*, generators::*, modifiers::*, ops::*,
+    selectors::*, transformers::*, upsample::*,
 };
 use alloc::boxed::Box;
 
 mod cache;
 mod combiners;
+mod finite_difference;
 mod generators;
 mod modifiers;
+mod ops;
 mod selectors;
 mod transformers;
+mod upsample;
 
 /// Base trait for noise functions.
 ///
@@ -19,15 +23,36 @@ mod transformers;
 /// value. Some of these methods include:
 ///
 /// * Calculating a value using a coherent-noise function or some other
-///     mathematical function.
+///   mathematical function.
 /// * Mathematically changing the output value from another noise function
-///     in various ways.
+///   in various ways.
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T, const DIM: usize> {
     fn get(&self, point: [T; DIM]) -> f64;
+
+    /// Calculates the output values for a slice of input points, writing the
+    /// result for `points[i]` into `out[i]`.
+    ///
+    /// The default implementation calls `get` for each point. Noise functions
+    /// that perform setup work on every call can override this to do that work
+    /// once for the whole slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `out` have different lengths.
+    fn get_many(&self, points: &[[T; DIM]], out: &mut [f64])
+    where
+        T: Copy,
+    {
+        assert_eq!(points.len(), out.len());
+
+        for (point, value) in points.iter().zip(out.iter_mut()) {
+            *value = self.get(*point);
+        }
+    }
 }
 
-impl<'a, T, M, const DIM: usize> NoiseFn<T, DIM> for &'a M
+impl<T, M, const DIM: usize> NoiseFn<T, DIM> for &M
 where
     M: NoiseFn<T, DIM> + ?Sized,
 {
@@ -35,8 +60,20 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         M::get(*self, point)
     }
+
+    #[inline]
+    fn get_many(&self, points: &[[T; DIM]], out: &mut [f64])
+    where
+        T: Copy,
+    {
+        M::get_many(*self, points, out)
+    }
 }
 
+/// Boxed noise functions forward to their contents. This includes trait
+/// objects such as `Box<dyn NoiseFn<f64, 3>>`, so graphs whose node types are
+/// only known at runtime can still be fed to any combiner, modifier or
+/// selector.
 impl<T, M, const DIM: usize> NoiseFn<T, DIM> for Box<M>
 where
     M: NoiseFn<T, DIM> + ?Sized,
@@ -45,8 +82,119 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         M::get(self, point)
     }
+
+    #[inline]
+    fn get_many(&self, points: &[[T; DIM]], out: &mut [f64])
+    where
+        T: Copy,
+    {
+        M::get_many(self, points, out)
+    }
+}
+
+/// Trait for noise functions that can also calculate their partial derivatives.
+///
+/// Noise functions without an analytic derivative can be wrapped in
+/// [`FiniteDifference`] to approximate it from nearby samples.
+pub trait NoiseFnWithDerivative<T, const DIM: usize>: NoiseFn<T, DIM> {
+    /// Returns the partial derivatives of the output value at `point`, one for
+    /// each input axis.
+    fn get_derivative(&self, point: [T; DIM]) -> [f64; DIM];
+
+    /// Returns the output value at `point` together with its partial
+    /// derivatives. Noise functions that calculate both at once override this
+    /// to avoid sampling twice.
+    fn get_with_derivative(&self, point: [T; DIM]) -> (f64, [f64; DIM])
+    where
+        T: Copy,
+    {
+        (self.get(point), self.get_derivative(point))
+    }
 }
 
+impl<T, M, const DIM: usize> NoiseFnWithDerivative<T, DIM> for &M
+where
+    M: NoiseFnWithDerivative<T, DIM> + ?Sized,
+{
+    #[inline]
+    fn get_derivative(&self, point: [T; DIM]) -> [f64; DIM] {
+        M::get_derivative(*self, point)
+    }
+
+    #[inline]
+    fn get_with_derivative(&self, point: [T; DIM]) -> (f64, [f64; DIM])
+    where
+        T: Copy,
+    {
+        M::get_with_derivative(*self, point)
+    }
+}
+
+impl<T, M, const DIM: usize> NoiseFnWithDerivative<T, DIM> for Box<M>
+where
+    M: NoiseFnWithDerivative<T, DIM> + ?Sized,
+{
+    #[inline]
+    fn get_derivative(&self, point: [T; DIM]) -> [f64; DIM] {
+        M::get_derivative(self, point)
+    }
+
+    #[inline]
+    fn get_with_derivative(&self, point: [T; DIM]) -> (f64, [f64; DIM])
+    where
+        T: Copy,
+    {
+        M::get_with_derivative(self, point)
+    }
+}
+
+/// Trait for sampling several noise functions at the same point.
+///
+/// It is implemented for tuples of up to eight noise functions and for arrays
+/// of noise functions of the same type, and returns their outputs in order.
+///
+/// ```
+/// use noise::{MultiSample, Perlin, Worley};
+///
+/// let [perlin, worley] = (Perlin::new(1), Worley::new(2)).get_all([0.5, 1.25]);
+/// ```
+pub trait MultiSample<T, const DIM: usize, const N: usize> {
+    fn get_all(&self, point: [T; DIM]) -> [f64; N];
+}
+
+impl<T, M, const DIM: usize, const N: usize> MultiSample<T, DIM, N> for [M; N]
+where
+    T: Copy,
+    M: NoiseFn<T, DIM>,
+{
+    fn get_all(&self, point: [T; DIM]) -> [f64; N] {
+        core::array::from_fn(|i| self[i].get(point))
+    }
+}
+
+macro_rules! impl_multi_sample_for_tuple {
+    ($n:literal; $($source:ident $index:tt),+) => {
+        impl<T, $($source),+, const DIM: usize> MultiSample<T, DIM, $n> for ($($source,)+)
+        where
+            T: Copy,
+            $($source: NoiseFn<T, DIM>),+
+        {
+            fn get_all(&self, point: [T; DIM]) -> [f64; $n] {
+                [$(self.$index.get(point)),+]
+            }
+        }
+    };
+}
+
+impl_multi_sample_for_tuple!(1; A 0);
+impl_multi_sample_for_tuple!(2; A 0, B 1);
+impl_multi_sample_for_tuple!(3; A 0, B 1, C 2);
+impl_multi_sample_for_tuple!(4; A 0, B 1, C 2, D 3);
+impl_multi_sample_for_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+impl_multi_sample_for_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_multi_sample_for_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_multi_sample_for_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
 /// Trait for functions that require a seed before generating their values
 pub trait Seedable {
     /// Set the seed for the function implementing the `Seedable` trait
@@ -55,3 +203,95 @@ pub trait Seedable {
     /// Getter to retrieve the seed from the function
     fn seed(&self) -> u32;
 }
+
+/// Trait for functions that can also be seeded with a 64-bit seed.
+///
+/// Seeds that fit in a `u32` give the same output as [`Seedable::set_seed`],
+/// and [`Seedable::seed`] returns the lower 32 bits of a larger seed.
+pub trait Seedable64: Seedable {
+    /// Set the 64-bit seed for the function
+    fn set_seed_u64(self, seed: u64) -> Self;
+
+    /// Getter to retrieve the full 64-bit seed from the function
+    fn seed_u64(&self) -> u64;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn boxed_trait_objects_compose_like_concrete_sources() {
+        let nodes: Vec<Box<dyn NoiseFn<f64, 3>>> = vec![
+            Box::new(Perlin::new(1)),
+            Box::new(Worley::new(2)),
+            Box::new(Fbm::<Perlin>::new(3)),
+        ];
+        let mut nodes = nodes.into_iter();
+        let (a, b, c) = (
+            nodes.next().unwrap(),
+            nodes.next().unwrap(),
+            nodes.next().unwrap(),
+        );
+
+        let boxed: Box<dyn NoiseFn<f64, 3>> =
+            Box::new(Select::new(Abs::new(a) + b, Constant::new(0.25), c));
+        let concrete = Select::new(
+            Abs::new(Perlin::new(1)) + Worley::new(2),
+            Constant::new(0.25),
+            Fbm::<Perlin>::new(3),
+        );
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21, 1.5];
+            assert_eq!(boxed.get(point), concrete.get(point));
+        }
+    }
+
+    #[test]
+    fn multi_sample_matches_separate_calls() {
+        let perlin = Perlin::new(1);
+        let worley = Worley::new(2);
+        let constant = Constant::new(0.25);
+        let point = [0.3, -1.7, 2.9];
+
+        assert_eq!(
+            (&perlin, &worley, constant).get_all(point),
+            [perlin.get(point), worley.get(point), 0.25]
+        );
+        assert_eq!(
+            [Perlin::new(1), Perlin::new(5)].get_all(point),
+            [perlin.get(point), Perlin::new(5).get(point)]
+        );
+    }
+
+    #[test]
+    fn seeds_round_trip_through_seedable() {
+        fn assert_round_trip<S: Seedable + Clone>(noise: S) {
+            for seed in [0, 1, 12_345, u32::MAX] {
+                assert_eq!(noise.clone().set_seed(seed).seed(), seed);
+            }
+        }
+
+        assert_round_trip(Perlin::default());
+        assert_round_trip(PerlinSurflet::default());
+        assert_round_trip(Simplex::default());
+        assert_round_trip(OpenSimplex::default());
+        assert_round_trip(SuperSimplex::default());
+        assert_round_trip(Value::default());
+        assert_round_trip(Worley::default());
+        assert_round_trip(Voronoise::default());
+        assert_round_trip(Gabor::default());
+        assert_round_trip(FlowNoise::default());
+        assert_round_trip(Fbm::<Perlin>::default());
+        assert_round_trip(Billow::<Perlin>::default());
+        assert_round_trip(BasicMulti::<Perlin>::default());
+        assert_round_trip(HybridMulti::<Perlin>::default());
+        assert_round_trip(RidgedMulti::<Perlin>::default());
+        assert_round_trip(HeteroTerrain::<Perlin>::default());
+        assert_round_trip(FractalWorley::default());
+        assert_round_trip(ErodedMulti::<Simplex>::default());
+        assert_round_trip(Turbulence::<_, Perlin>::new(Constant::new(0.0)));
+    }
+}
@@ -1,6 +1,11 @@
 use crate::noise_fns::NoiseFn;
 use alloc::vec::Vec;
-use core::cell::{Cell, RefCell};
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 /// Noise function that caches the last output value generated by the source
 /// function.
@@ -11,53 +16,226 @@ use core::cell::{Cell, RefCell};
 /// overwriting the cache with the result, and returning the result to the
 /// caller.
 ///
+/// Coordinates are compared bitwise, so `NaN` inputs can be served from the
+/// cache while `0.0` and `-0.0` are treated as different points. The same
+/// `Cache` can be sampled with points of any dimension; a point with a
+/// different dimension than the cached one is simply a miss.
+///
 /// Caching a noise function is useful if it is used as a source function for
 /// multiple noise functions. If a source function is not cached, the source
 /// function will redundantly calculate the same output value once for each
 /// noise function in which it is included.
-#[derive(Clone, Debug)]
+///
+/// # Thread safety
+///
+/// `Cache` is `Sync` whenever its source is, so it can be used with the
+/// parallel map builders. The single cache slot is guarded by an atomic flag
+/// that is only ever *tried*: a thread that finds the slot busy skips the
+/// cache and calls the source directly instead of waiting. Results are
+/// therefore always correct, but threads sampling different points will
+/// mostly miss the cache, which only pays off for repeated queries of the same
+/// point.
 pub struct Cache<Source> {
     /// Outputs the value to be cached.
     pub source: Source,
 
-    value: Cell<Option<f64>>,
+    entry: Slot<Entry>,
+}
 
-    point: RefCell<Vec<f64>>,
+#[derive(Default)]
+struct Entry {
+    point: Vec<u64>,
+    value: Option<f64>,
 }
 
 impl<Source> Cache<Source> {
     pub fn new(source: Source) -> Self {
         Cache {
             source,
-            value: Cell::new(None),
-            point: RefCell::new(Vec::new()),
+            entry: Slot::new(Entry::default()),
         }
     }
 }
 
+impl<Source> Clone for Cache<Source>
+where
+    Source: Clone,
+{
+    /// Clones the source function. The clone starts with an empty cache.
+    fn clone(&self) -> Self {
+        Self::new(self.source.clone())
+    }
+}
+
+impl<Source> fmt::Debug for Cache<Source>
+where
+    Source: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache")
+            .field("source", &self.source)
+            .finish_non_exhaustive()
+    }
+}
+
 impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Cache<Source>
 where
     Source: NoiseFn<f64, DIM>,
 {
     fn get(&self, point: [f64; DIM]) -> f64 {
-        match self.value.get() {
-            Some(value) if quick_eq(&self.point.borrow(), &point) => value,
-            Some(_) | None => {
-                let value = self.source.get(point);
-                self.value.set(Some(value));
+        if let Some(entry) = self.entry.try_lock() {
+            if let Some(value) = entry.lookup(&point) {
+                return value;
+            }
+        }
 
-                let mut cached_point = self.point.borrow_mut();
-                cached_point.clear();
-                cached_point.extend_from_slice(&point);
+        // The slot is released while the source runs so that other threads
+        // can keep using it in the meantime.
+        let value = self.source.get(point);
 
-                value
-            }
+        if let Some(mut entry) = self.entry.try_lock() {
+            entry.store(&point, value);
         }
+
+        value
+    }
+}
+
+impl Entry {
+    fn lookup(&self, point: &[f64]) -> Option<f64> {
+        let matches = self.point.len() == point.len()
+            && self
+                .point
+                .iter()
+                .zip(point)
+                .all(|(&cached, x)| cached == x.to_bits());
+
+        self.value.filter(|_| matches)
+    }
+
+    fn store(&mut self, point: &[f64], value: f64) {
+        self.point.clear();
+        self.point.extend(point.iter().map(|x| x.to_bits()));
+        self.value = Some(value);
     }
 }
 
-fn quick_eq(a: &[f64], b: &[f64]) -> bool {
-    assert_eq!(a.len(), b.len());
+/// Value shared between threads that is only ever *tried* to be locked, for
+/// caches that skip the cached value rather than wait when it is busy.
+pub(crate) struct Slot<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// Safety: `value` is only accessed through a `SlotGuard`, which holds the
+// `locked` flag for its lifetime, so at most one thread touches it at a time.
+unsafe impl<T: Send> Sync for Slot<T> {}
+
+impl<T> Slot<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Locks the slot, or returns `None` if another thread holds it.
+    pub(crate) fn try_lock(&self) -> Option<SlotGuard<'_, T>> {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()
+            .map(|_| SlotGuard {
+                locked: &self.locked,
+                // Safety: the flag was just acquired, see the `Sync` impl.
+                value: unsafe { &mut *self.value.get() },
+            })
+    }
+}
 
-    a.iter().eq(b)
+pub(crate) struct SlotGuard<'a, T> {
+    locked: &'a AtomicBool,
+    value: &'a mut T,
+}
+
+impl<T> Deref for SlotGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for SlotGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
+
+impl<T> Drop for SlotGuard<'_, T> {
+    fn drop(&mut self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::AtomicUsize;
+
+    #[derive(Default)]
+    struct CountCalls(AtomicUsize);
+
+    impl<const DIM: usize> NoiseFn<f64, DIM> for CountCalls {
+        fn get(&self, point: [f64; DIM]) -> f64 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            point.iter().sum()
+        }
+    }
+
+    /// Samples `cache` at `point` and returns how often the source was called.
+    fn misses<const DIM: usize>(cache: &Cache<CountCalls>, point: [f64; DIM]) -> usize {
+        let before = cache.source.0.load(Ordering::Relaxed);
+        cache.get(point);
+        cache.source.0.load(Ordering::Relaxed) - before
+    }
+
+    #[test]
+    fn repeated_points_hit_the_cache() {
+        let cache = Cache::new(CountCalls::default());
+
+        assert_eq!(misses(&cache, [1.0, 2.0]), 1);
+        assert_eq!(misses(&cache, [1.0, 2.0]), 0);
+        assert_eq!(cache.get([1.0, 2.0]), 3.0);
+        assert_eq!(misses(&cache, [1.0, 2.5]), 1);
+        assert_eq!(cache.get([1.0, 2.5]), 3.5);
+    }
+
+    #[test]
+    fn points_are_compared_bitwise() {
+        let cache = Cache::new(CountCalls::default());
+
+        assert_eq!(misses(&cache, [f64::NAN]), 1);
+        assert_eq!(misses(&cache, [f64::NAN]), 0);
+        assert_eq!(misses(&cache, [0.0]), 1);
+        assert_eq!(misses(&cache, [-0.0]), 1);
+    }
+
+    #[test]
+    fn any_dimension_can_share_one_cache() {
+        let cache = Cache::new(CountCalls::default());
+
+        for _ in 0..2 {
+            assert_eq!(misses(&cache, [1.0]), 1);
+            assert_eq!(misses(&cache, [1.0, 0.0]), 1);
+            assert_eq!(misses(&cache, [1.0, 0.0, 0.0, 0.0]), 1);
+        }
+        assert_eq!(misses(&cache, [1.0, 0.0, 0.0, 0.0]), 0);
+    }
+
+    #[test]
+    fn cache_is_sync_when_its_source_is() {
+        fn assert_sync<T: Sync>() {}
+
+        assert_sync::<Cache<CountCalls>>();
+    }
 }
@@ -1,7 +1,12 @@
-pub use self::{add::*, max::*, min::*, multiply::*, power::*};
+pub use self::{
+    add::*, divide::*, max::*, min::*, multiply::*, power::*, subtract::*, weighted_sum::*,
+};
 
 mod add;
+mod divide;
 mod max;
 mod min;
 mod multiply;
 mod power;
+mod subtract;
+mod weighted_sum;
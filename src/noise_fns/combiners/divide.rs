@@ -0,0 +1,80 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+
+/// Noise function that outputs the quotient of the two output values from two
+/// source functions, the output of `source1` divided by the output of
+/// `source2`.
+///
+/// Divisors closer to zero than [`MIN_DIVISOR`](Self::MIN_DIVISOR) are replaced
+/// by `MIN_DIVISOR` with the divisor's sign, so the output saturates at a large
+/// but finite value instead of becoming infinite or NaN. The sign of a zero
+/// divisor is respected, so `0.0` saturates at the positive limit and `-0.0` at
+/// the negative one.
+#[derive(Clone)]
+pub struct Divide<T, Source1, Source2, const DIM: usize>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source1, Source2, const DIM: usize> Divide<T, Source1, Source2, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Smallest magnitude of the divisor, 1e-6.
+    pub const MIN_DIVISOR: f64 = 1e-6;
+
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Divide<T, Source1, Source2, DIM>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let divisor = self.source2.get(point);
+        let divisor = if divisor.abs() < Self::MIN_DIVISOR {
+            Self::MIN_DIVISOR.copysign(divisor)
+        } else {
+            divisor
+        };
+
+        self.source1.get(point) / divisor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn divide(dividend: f64, divisor: f64) -> f64 {
+        Divide::new(Constant::new(dividend), Constant::new(divisor)).get([0.0, 0.0])
+    }
+
+    #[test]
+    fn small_divisors_saturate() {
+        assert_eq!(divide(0.5, -0.25), -2.0);
+        assert_eq!(divide(0.5, 0.0), 5e5);
+        assert_eq!(divide(0.5, -0.0), -5e5);
+        assert_eq!(divide(-0.5, 1e-9), -5e5);
+        assert_eq!(divide(0.0, 0.0), 0.0);
+    }
+}
@@ -3,6 +3,9 @@ use core::marker::PhantomData;
 
 /// Noise function that outputs the product of the two output values from two source
 /// functions.
+///
+/// `source1 * source2` builds a [`Product`](crate::Product), which outputs the same
+/// values without naming the dimension.
 #[derive(Clone)]
 pub struct Multiply<T, Source1, Source2, const DIM: usize>
 where
@@ -1,8 +1,15 @@
 use crate::noise_fns::NoiseFn;
 use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 /// Noise function that raises the output value from the first source function
 /// to the power of the output value of the second source function.
+///
+/// The power is taken of the magnitude of the base and the sign of the base is
+/// kept, so the output is `sign(base) * |base|^exponent`. Negative noise values
+/// therefore stay negative instead of becoming NaN for fractional exponents,
+/// which also means that even exponents don't make them positive.
 #[derive(Clone)]
 pub struct Power<T, Source1, Source2, const DIM: usize>
 where
@@ -39,6 +46,27 @@ where
     Source2: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source1.get(point)).powf(self.source2.get(point))
+        let base = self.source1.get(point);
+
+        base.abs().powf(self.source2.get(point)).copysign(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn power(base: f64, exponent: f64) -> f64 {
+        Power::new(Constant::new(base), Constant::new(exponent)).get([0.0, 0.0])
+    }
+
+    #[test]
+    fn negative_bases_keep_their_sign() {
+        assert_eq!(power(-0.25, 0.5), -0.5);
+        assert_eq!(power(0.25, 0.5), 0.5);
+        assert_eq!(power(-0.5, 2.0), -0.25);
+        assert_eq!(power(-2.0, -1.0), -0.5);
+        assert!(power(-0.7, 1.3).is_finite());
     }
 }
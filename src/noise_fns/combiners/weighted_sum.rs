@@ -0,0 +1,93 @@
+use crate::noise_fns::NoiseFn;
+use alloc::{boxed::Box, vec::Vec};
+
+/// Noise function that outputs the weighted average of the output values from
+/// any number of source functions.
+///
+/// The weights are normalized by their sum, so they don't need to add up to
+/// 1.0. Without sources, or when the weights add up to zero, the output value
+/// is 0.0.
+pub struct WeightedSum<T, const DIM: usize> {
+    /// Source functions with their weights.
+    sources: Vec<(Box<dyn NoiseFn<T, DIM>>, f64)>,
+
+    total_weight: f64,
+}
+
+impl<T, const DIM: usize> WeightedSum<T, DIM> {
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            total_weight: 0.0,
+        }
+    }
+
+    /// Adds a source function with the given weight.
+    pub fn add_source<Source>(mut self, source: Source, weight: f64) -> Self
+    where
+        Source: NoiseFn<T, DIM> + 'static,
+    {
+        self.sources.push((Box::new(source), weight));
+        self.total_weight += weight;
+
+        self
+    }
+
+    /// Returns the number of source functions.
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    /// Returns `true` if there are no source functions.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+impl<T, const DIM: usize> Default for WeightedSum<T, DIM> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const DIM: usize> NoiseFn<T, DIM> for WeightedSum<T, DIM>
+where
+    T: Copy,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+
+        let sum = self.sources.iter().fold(0.0, |sum, (source, weight)| {
+            sum + source.get(point) * weight
+        });
+
+        sum / self.total_weight
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn weights_are_normalized() {
+        let empty = WeightedSum::<f64, 2>::new();
+        assert_eq!(empty.get([0.0, 0.0]), 0.0);
+
+        let sum = WeightedSum::new()
+            .add_source(Constant::new(1.0), 3.0)
+            .add_source(Constant::new(-1.0), 1.0);
+        assert_eq!(sum.get([0.0, 0.0]), 0.5);
+
+        let perlin = Perlin::new(4);
+        let single = WeightedSum::new().add_source(perlin, 0.2);
+        assert_eq!(single.len(), 1);
+        for i in 0..16 {
+            let point = [i as f64 * 0.31, 0.5, -0.2];
+            assert!((single.get(point) - perlin.get(point)).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::noise_fns::{NoiseFn, NoiseFnWithDerivative, Seedable};
+
+/// Noise function that approximates the derivatives of the source function
+/// using central finite differences.
+///
+/// The output value is passed through unchanged. Each partial derivative is
+/// estimated by sampling the source function a small step to either side of
+/// the input point along that axis, so calculating the derivatives costs two
+/// extra calls to the source function per axis.
+#[derive(Clone, Debug)]
+pub struct FiniteDifference<Source> {
+    /// Source function to differentiate.
+    pub source: Source,
+
+    /// Distance from the input point to each of the samples used to estimate
+    /// the derivatives.
+    pub step: f64,
+}
+
+impl<Source> FiniteDifference<Source> {
+    pub const DEFAULT_STEP: f64 = 1e-4;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            step: Self::DEFAULT_STEP,
+        }
+    }
+
+    pub fn set_step(self, step: f64) -> Self {
+        Self { step, ..self }
+    }
+}
+
+impl<Source> Default for FiniteDifference<Source>
+where
+    Source: Default,
+{
+    fn default() -> Self {
+        Self::new(Source::default())
+    }
+}
+
+/// Seeds the source function, so that fractals such as
+/// [`ErodedMulti`](crate::ErodedMulti) can build their octaves from
+/// differentiated sources.
+impl<Source> Seedable for FiniteDifference<Source>
+where
+    Source: Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            source: self.source.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.source.seed()
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for FiniteDifference<Source>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        self.source.get(point)
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFnWithDerivative<f64, DIM> for FiniteDifference<Source>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get_derivative(&self, point: [f64; DIM]) -> [f64; DIM] {
+        let mut derivative = [0.0; DIM];
+
+        for (axis, value) in derivative.iter_mut().enumerate() {
+            let mut ahead = point;
+            let mut behind = point;
+            ahead[axis] += self.step;
+            behind[axis] -= self.step;
+
+            *value = (self.source.get(ahead) - self.source.get(behind)) / (2.0 * self.step);
+        }
+
+        derivative
+    }
+}
@@ -1,16 +1,124 @@
 pub use self::{
-    checkerboard::*, constant::*, cylinders::*, fractals::*, open_simplex::*, perlin::*,
-    perlin_surflet::*, simplex::*, super_simplex::*, value::*, worley::*,
+    checkerboard::*, constant::*, cylinders::*, flow_noise::*, fn_noise::*, fractals::*, gabor::*,
+    island_mask::*, open_simplex::*, perlin::*, perlin_surflet::*, simplex::*, spheres::*,
+    super_simplex::*, value::*, voronoise::*, worley::*,
 };
 
 mod checkerboard;
 mod constant;
 mod cylinders;
+mod flow_noise;
+mod fn_noise;
 mod fractals;
+mod gabor;
+mod island_mask;
 mod open_simplex;
 mod perlin;
 mod perlin_surflet;
 mod simplex;
+mod spheres;
 mod super_simplex;
 mod value;
+mod voronoise;
 mod worley;
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::{
+        core::{
+            spheres::RadialProfile,
+            value::Interpolation,
+            worley::{distance_functions, ReturnType},
+        },
+        noise_fns::{MultiFractal, NoiseFn},
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    fn assert_round_trip<N>(noise: &N)
+    where
+        N: NoiseFn<f64, 3> + Serialize + DeserializeOwned,
+    {
+        let json = serde_json::to_string(noise).unwrap();
+        let restored: N = serde_json::from_str(&json).unwrap();
+
+        for i in 0..64 {
+            let point = [
+                i as f64 * 0.37 - 5.0,
+                i as f64 * 0.21 + 1.3,
+                i as f64 * -0.11,
+            ];
+            assert_eq!(noise.get(point), restored.get(point), "{}", json);
+        }
+    }
+
+    #[test]
+    fn generators_round_trip_through_json() {
+        assert_round_trip(&Perlin::new(3).use_unit_gradients(true));
+        assert_round_trip(&Value::new(3));
+        assert_round_trip(&Value::new(3).set_interpolation(Interpolation::Cubic));
+        assert_round_trip(&Checkerboard::new(2));
+        assert_round_trip(&Checkerboard::default().set_cell_size(0.75));
+        assert_round_trip(&Constant::new(0.25));
+        assert_round_trip(&Cylinders::new().set_profile(RadialProfile::Cosine));
+        assert_round_trip(&Spheres::new().set_center([0.5, 1.0, -1.5]).set_phase(0.25));
+        assert_round_trip(&Cylinders::new().set_center([1.5, -2.0]).set_phase(0.25));
+        assert_round_trip(
+            &Worley::new(3)
+                .set_return_type(ReturnType::Distance)
+                .set_distance_function(distance_functions::manhattan)
+                .set_frequency(2.0),
+        );
+        assert_round_trip(&Worley::new(3).set_distance_function(distance_functions::quadratic));
+        assert_round_trip(
+            &Fbm::<Perlin>::new(3)
+                .set_octaves(4)
+                .set_persistence(0.6)
+                .set_octave_amplitudes(vec![1.0, 0.2])
+                .set_octave_frequencies(vec![0.5]),
+        );
+        assert_round_trip(&Billow::<Value>::new(3).set_lacunarity(2.0));
+        assert_round_trip(
+            &RidgedMulti::<Perlin>::new(3)
+                .set_attenuation(1.5)
+                .set_offset(1.2),
+        );
+        assert_round_trip(&HeteroTerrain::<Perlin>::new(3).set_offset(0.75));
+        assert_round_trip(&FlowNoise::new(3).set_time(1.25));
+        assert_round_trip(&Voronoise::new(3).set_jitter(0.25).set_smoothness(0.8));
+    }
+
+    #[test]
+    fn gabor_round_trips() {
+        let gabor = Gabor::new(3).set_bandwidth(0.3).set_orientation(1.0);
+        let json = serde_json::to_string(&gabor).unwrap();
+        let restored: Gabor = serde_json::from_str(&json).unwrap();
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.21 + 1.3];
+            assert_eq!(restored.get(point), gabor.get(point));
+        }
+    }
+
+    #[test]
+    fn permutation_table_is_not_serialized() {
+        let json = serde_json::to_string(&Perlin::new(3)).unwrap();
+
+        assert_eq!(json, r#"{"seed":3,"unit_gradients":false}"#);
+    }
+
+    #[test]
+    fn custom_distance_function_falls_back_to_default() {
+        let worley = Worley::new(3).set_distance_function(|a: &[f64], b: &[f64]| {
+            distance_functions::euclidean(a, b) * 0.5
+        });
+        let json = serde_json::to_string(&worley).unwrap();
+        let restored: Worley = serde_json::from_str(&json).unwrap();
+
+        assert!(json.contains(r#""distance_function":"custom""#));
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * 0.21];
+            assert_eq!(restored.get(point), Worley::new(3).get(point));
+        }
+    }
+}
@@ -2,16 +2,21 @@ use crate::{core::checkerboard::*, noise_fns::NoiseFn};
 
 /// Noise function that outputs a checkerboard pattern.
 ///
-/// This noise function can take one input, size, and outputs 2<sup>size</sup>-sized
-/// blocks of alternating values. The values of these blocks alternate between
-/// -1.0 and 1.0.
+/// This noise function outputs square blocks of alternating values, with the
+/// parity of a block computed from `floor(coord / size)` along each axis. The
+/// values of these blocks alternate between -1.0 and 1.0.
+///
+/// The block size is either a power of two, set with [`new`](Self::new) and
+/// [`set_size`](Self::set_size), or any positive length, set with
+/// [`set_cell_size`](Self::set_cell_size). It defaults to 1.0.
 ///
 /// This noise function is not very useful by itself, but it can be used for
-/// debugging purposes.
+/// debugging purposes, or mixed into other noise at a chosen scale.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Checkerboard {
-    // Controls the size of the block in 2^(size).
-    size: usize,
+    // Width of each block, in units.
+    size: f64,
 }
 
 impl Checkerboard {
@@ -19,40 +24,94 @@ impl Checkerboard {
 
     /// Controls the size of the block in 2^(size) units.
     pub fn new(size: usize) -> Self {
-        Self { size: 1 << size }
+        Self {
+            size: (1 << size) as f64,
+        }
     }
 
+    /// Sets the size of the block to 2^(size) units.
     pub fn set_size(self, size: usize) -> Self {
-        Self { size: 1 << size }
+        Self {
+            size: (1 << size) as f64,
+        }
     }
 
+    /// Sets the size of the block to `cell_size` units.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cell_size` is not positive.
+    pub fn set_cell_size(self, cell_size: f64) -> Self {
+        assert!(cell_size > 0.0, "cell size must be positive");
+
+        Self { size: cell_size }
+    }
+
+    /// Returns the size of the block in units, rounded down to a whole
+    /// number.
+    #[deprecated(note = "use `cell_size`, which does not round")]
     pub fn size(self) -> usize {
+        self.size as usize
+    }
+
+    /// Returns the size of the block in units.
+    pub fn cell_size(self) -> f64 {
         self.size
     }
 }
 
 impl Default for Checkerboard {
     fn default() -> Self {
-        Self {
-            size: 1 << Checkerboard::DEFAULT_SIZE,
-        }
+        Self::new(Checkerboard::DEFAULT_SIZE)
     }
 }
 
 impl NoiseFn<f64, 2> for Checkerboard {
     fn get(&self, point: [f64; 2]) -> f64 {
-        checkerboard_2d(point.into(), self.size as f64)
+        checkerboard_2d(point.into(), self.size)
     }
 }
 
 impl NoiseFn<f64, 3> for Checkerboard {
     fn get(&self, point: [f64; 3]) -> f64 {
-        checkerboard_3d(point.into(), self.size as f64)
+        checkerboard_3d(point.into(), self.size)
     }
 }
 
 impl NoiseFn<f64, 4> for Checkerboard {
     fn get(&self, point: [f64; 4]) -> f64 {
-        checkerboard_4d(point.into(), self.size as f64)
+        checkerboard_4d(point.into(), self.size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cell_size_sets_the_width_of_the_squares() {
+        let board = Checkerboard::default().set_cell_size(2.0);
+
+        assert_eq!(board.get([0.5, 0.5]), board.get([1.5, 1.5]));
+        assert_eq!(board.get([0.5, 0.5]), board.get([1.9, 0.1]));
+        assert_ne!(board.get([0.5, 0.5]), board.get([2.5, 0.5]));
+        assert_ne!(board.get([0.5, 0.5]), board.get([0.5, -0.5]));
+        assert_eq!(board.get([0.5, 0.5]), board.get([2.5, 2.5]));
+    }
+
+    #[test]
+    fn default_cell_size_matches_unit_squares() {
+        let board = Checkerboard::default();
+
+        assert_eq!(board.cell_size(), 1.0);
+        for i in 0..16 {
+            let x = i as f64 * 0.5 - 3.75;
+            let expected = if (x.floor() as isize) & 1 == 0 {
+                -1.0
+            } else {
+                1.0
+            };
+            assert_eq!(board.get([x, 0.5]), expected);
+        }
     }
 }
@@ -1,27 +1,72 @@
-use crate::{core::spheres::*, math::vectors::Vector2, noise_fns::NoiseFn};
+use crate::{core::spheres::RadialProfile, math::vectors::Vector2, noise_fns::NoiseFn};
 
 /// Noise function that outputs concentric cylinders.
 ///
-/// This noise function outputs concentric cylinders centered on the origin. The
-/// cylinders are oriented along the z axis similar to the concentric rings of
-/// a tree. Each cylinder extends infinitely along the z axis.
+/// This noise function outputs concentric cylinders centered on the origin, or
+/// on the point set with [`set_center`](Self::set_center). The cylinders are
+/// oriented along the z axis similar to the concentric rings of a tree. Each
+/// cylinder extends infinitely along the z axis.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cylinders {
     /// Frequency of the concentric objects.
     pub frequency: f64,
+
+    /// Shape of the value along the radius of the cylinders.
+    pub profile: RadialProfile,
+
+    /// Center of the cylinders in the _xy_ plane.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub center: [f64; 2],
+
+    /// Offset added to the radial distance after scaling by the frequency, in
+    /// units of one shell. A phase of 0.5 swaps the peaks and troughs of the
+    /// repeating profiles.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub phase: f64,
 }
 
 impl Cylinders {
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_PROFILE: RadialProfile = RadialProfile::Triangle;
+    pub const DEFAULT_CENTER: [f64; 2] = [0.0; 2];
+    pub const DEFAULT_PHASE: f64 = 0.0;
 
     pub fn new() -> Self {
         Self {
             frequency: Self::DEFAULT_FREQUENCY,
+            profile: Self::DEFAULT_PROFILE,
+            center: Self::DEFAULT_CENTER,
+            phase: Self::DEFAULT_PHASE,
         }
     }
 
     pub fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency }
+        Self { frequency, ..self }
+    }
+
+    /// Sets the radial profile, choosing between sharp shells, smooth shells,
+    /// or a single falloff from the center.
+    pub fn set_profile(self, profile: RadialProfile) -> Self {
+        Self { profile, ..self }
+    }
+
+    /// Sets the center of the cylinders in the _xy_ plane.
+    pub fn set_center(self, center: [f64; 2]) -> Self {
+        Self { center, ..self }
+    }
+
+    /// Sets the phase, which shifts every shell outwards by that fraction of
+    /// the spacing between shells.
+    pub fn set_phase(self, phase: f64) -> Self {
+        Self { phase, ..self }
+    }
+
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let offset = Vector2::new(x - self.center[0], y - self.center[1]);
+
+        self.profile
+            .map(offset.magnitude() * self.frequency + self.phase)
     }
 }
 
@@ -33,18 +78,43 @@ impl Default for Cylinders {
 
 impl NoiseFn<f64, 2> for Cylinders {
     fn get(&self, point: [f64; 2]) -> f64 {
-        spheres_2d(point.into(), self.frequency)
+        self.value(point[0], point[1])
     }
 }
 
 impl NoiseFn<f64, 3> for Cylinders {
     fn get(&self, point: [f64; 3]) -> f64 {
-        spheres_2d(Vector2::new(point[0], point[1]), self.frequency)
+        self.value(point[0], point[1])
     }
 }
 
 impl NoiseFn<f64, 4> for Cylinders {
     fn get(&self, point: [f64; 4]) -> f64 {
-        spheres_2d(Vector2::new(point[0], point[1]), self.frequency)
+        self.value(point[0], point[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn center_moves_the_rings() {
+        let origin = Cylinders::new();
+        let moved = Cylinders::new().set_center([3.0, -2.0]);
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * -0.21 + 1.3];
+            let shifted = [point[0] + 3.0, point[1] - 2.0];
+            assert!((origin.get(point) - moved.get(shifted)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn half_phase_inverts_the_shells() {
+        let cylinders = Cylinders::new().set_phase(0.5);
+
+        assert_eq!(cylinders.get([0.0, 0.0]), -1.0);
+        assert!((cylinders.get([0.5, 0.0]) - 1.0).abs() < 1e-12);
     }
 }
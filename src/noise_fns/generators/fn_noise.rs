@@ -0,0 +1,53 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that outputs the value of a closure.
+///
+/// This is handy for quick experiments and for analytic fields, such as
+/// gradients or distance functions, without defining a new type:
+///
+/// ```
+/// use noise::{utils::*, FnNoise};
+///
+/// let rings = FnNoise(|[x, y, _]: [f64; 3]| (x * x + y * y).sin());
+/// let map = PlaneMapBuilder::new(rings).build();
+/// ```
+///
+/// The closure is wrapped rather than implementing [`NoiseFn`] for every `Fn`
+/// directly, as that would overlap with the implementations for references
+/// and boxes. The type of the closure's argument usually has to be written
+/// out, since the dimension can't be inferred from the wrapper alone.
+#[derive(Clone, Copy)]
+pub struct FnNoise<F>(pub F);
+
+impl<F> FnNoise<F> {
+    pub fn new(function: F) -> Self {
+        Self(function)
+    }
+}
+
+impl<T, F, const DIM: usize> NoiseFn<T, DIM> for FnNoise<F>
+where
+    F: Fn([T; DIM]) -> f64,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        (self.0)(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn closures_can_be_combined_with_other_noise() {
+        let ramp = FnNoise(|[x, _]: [f64; 2]| x * 0.1);
+        let perlin = Perlin::new(1);
+        let sum = ramp + perlin;
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, 1.5];
+            assert_eq!(sum.get(point), point[0] * 0.1 + perlin.get(point));
+        }
+    }
+}
@@ -1,13 +1,19 @@
-pub use self::{basicmulti::*, billow::*, fbm::*, hybridmulti::*, ridgedmulti::*};
+pub use self::{
+    basicmulti::*, billow::*, erodedmulti::*, fbm::*, fractal_worley::*, heteroterrain::*,
+    hybridmulti::*, ridgedmulti::*,
+};
 use alloc::vec::Vec;
 
 mod basicmulti;
 mod billow;
+mod erodedmulti;
 mod fbm;
+mod fractal_worley;
+mod heteroterrain;
 mod hybridmulti;
 mod ridgedmulti;
 
-use crate::Seedable;
+use crate::{permutationtable::splitmix64, Seedable};
 
 /// Trait for `MultiFractal` functions
 pub trait MultiFractal {
@@ -27,7 +33,120 @@ where
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
         let source = Source::default();
-        sources.push(source.set_seed(seed + x as u32));
+        sources.push(source.set_seed(octave_seed(seed, x)));
     }
     sources
 }
+
+/// Derives the seed of an octave by hashing the fractal's seed together with
+/// the octave index. Adding the index to the seed instead would make fractals
+/// with nearby seeds share most of their octaves.
+fn octave_seed(seed: u32, octave: usize) -> u32 {
+    let mut state = (u64::from(seed) << 32) | octave as u64;
+
+    splitmix64(&mut state) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        core::worley::{distance_functions, ReturnType},
+        NoiseFn, Perlin, Worley,
+    };
+
+    /// Outputs recorded on x86_64. They must match bit for bit on every target,
+    /// so any change here is a break in cross-platform determinism.
+    #[test]
+    fn fractal_output_is_deterministic() {
+        #[rustfmt::skip]
+        let expected = [
+            (0, [0.3, -1.7, 2.9], 0.2662309147886323, -0.14981676440508873),
+            (0, [12.25, 4.5, -0.125], 0.15918539696665873, 0.4716591164247712),
+            (1, [0.3, -1.7, 2.9], -0.04295725913512635, 0.43230875821062065),
+            (1, [12.25, 4.5, -0.125], 0.062331779276693276, 0.8159954052672242),
+            (42, [0.3, -1.7, 2.9], 0.012044462155156145, 0.6832716565647339),
+            (42, [12.25, 4.5, -0.125], 0.09482928986370062, -0.6509358352696379),
+        ];
+
+        for (seed, point, fbm, ridged) in expected {
+            // Non-power-of-two persistences, so that the amplitudes and scale
+            // factors are rounded.
+            let fbm_noise = Fbm::<Perlin>::new(seed).set_persistence(0.6);
+            let ridged_noise = RidgedMulti::<Perlin>::new(seed).set_persistence(0.9);
+
+            assert_eq!(fbm_noise.get(point), fbm);
+            assert_eq!(ridged_noise.get(point), ridged);
+        }
+    }
+
+    #[test]
+    fn nearby_seeds_are_uncorrelated() {
+        // If the octave seeds were consecutive, every octave of the fractal
+        // with seed 1 would repeat the next octave of the one with seed 0,
+        // scaled by the lacunarity.
+        let a = Fbm::<Perlin>::new(0);
+        let b = Fbm::<Perlin>::new(1);
+        let lacunarity = Fbm::<Perlin>::DEFAULT_LACUNARITY;
+
+        let (mut ab, mut aa, mut bb) = (0.0, 0.0, 0.0);
+        for y in 0..64 {
+            for x in 0..64 {
+                let point = [x as f64 * 0.173, y as f64 * 0.191, 0.5];
+                let va = a.get(point);
+                let vb = b.get(point.map(|x| x * lacunarity));
+                ab += va * vb;
+                aa += va * va;
+                bb += vb * vb;
+            }
+        }
+
+        let correlation = ab / (aa * bb).sqrt();
+        assert!(correlation.abs() < 0.1, "correlation {}", correlation);
+    }
+
+    #[test]
+    fn fbm_and_billow_clamp_sources_outside_the_range() {
+        // Manhattan distances reach well past the unit range.
+        let worley = Worley::new(3)
+            .set_return_type(ReturnType::Distance)
+            .set_distance_function(distance_functions::manhattan);
+        let fbm = Fbm::<Worley>::new(3)
+            .set_octaves(2)
+            .set_sources(vec![worley.clone(); 2]);
+        let billow = Billow::<Worley>::new(3)
+            .set_octaves(2)
+            .set_sources(vec![worley.clone(); 2]);
+
+        let mut exceeded = false;
+        for i in 0..4096 {
+            let point = [i as f64 * 0.0731, i as f64 * -0.0417];
+            exceeded |= worley.get(point) > 1.0;
+
+            let (fbm, billow) = (fbm.get(point), billow.get(point));
+            assert!((-1.0..=1.0).contains(&fbm), "fbm {}", fbm);
+            assert!((-1.0..=1.0).contains(&billow), "billow {}", billow);
+        }
+        assert!(exceeded);
+    }
+
+    #[test]
+    fn negative_amplitudes_are_normalized_by_their_magnitude() {
+        let sources = vec![Perlin::new(1), Perlin::new(2)];
+        let point = [0.3, 0.7, 0.1];
+        let lacunarity = Fbm::<Perlin>::DEFAULT_LACUNARITY;
+        let first = sources[0].get(point);
+        let second = sources[1].get(point.map(|x| x * lacunarity));
+
+        let fbm = Fbm::<Perlin>::new(0)
+            .set_octaves(2)
+            .set_persistence(-0.5)
+            .set_sources(sources.clone());
+        let expected = (-0.5 * first + 0.25 * second) / 0.75;
+        assert!((fbm.get(point) - expected).abs() < 1e-12);
+
+        let cancelling = fbm.set_octave_amplitudes(vec![1.0, -1.0]);
+        let expected = (first - second) / 2.0;
+        assert!((cancelling.get(point) - expected).abs() < 1e-12);
+    }
+}
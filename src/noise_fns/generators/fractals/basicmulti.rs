@@ -1,6 +1,6 @@
 use crate::{
-    math::vectors::*,
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{pow_sequential, vectors::*},
+    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -15,7 +15,7 @@ use alloc::vec::Vec;
 /// not be as damped and thus will grow more jagged as iteration progresses.
 ///
 #[derive(Clone, Debug)]
-pub struct BasicMulti<T> {
+pub struct BasicMulti<T = Perlin> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -80,7 +80,7 @@ where
         let denom = if octaves == 1 {
             1.0
         } else {
-            (1..=octaves).fold(1.0, |acc, x| acc + (acc * persistence.powi(x as i32)))
+            (1..=octaves).fold(1.0, |acc, x| acc + (acc * pow_sequential(persistence, x)))
         };
 
         1.0 / denom
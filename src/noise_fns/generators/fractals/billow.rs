@@ -1,6 +1,6 @@
 use crate::{
-    math::{scale_shift, vectors::*},
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{pow_sequential, scale_shift, vectors::*},
+    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -11,8 +11,19 @@ use alloc::vec::Vec;
 /// This noise function is nearly identical to fBm noise, except this noise
 /// function modifies each octave with an absolute-value function. See the
 /// documentation for fBm for more information.
+///
+/// Like [`Fbm`](crate::Fbm), the output is clamped to [-1, 1] for sources that
+/// exceed it.
 #[derive(Clone, Debug)]
-pub struct Billow<T> {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "BillowConfig",
+        bound(serialize = "", deserialize = "T: Default + Seedable")
+    )
+)]
+pub struct Billow<T = Perlin> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -42,10 +53,42 @@ pub struct Billow<T> {
     pub persistence: f64,
 
     seed: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     sources: Vec<T>,
+    octave_amplitudes: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     scale_factor: f64,
 }
 
+/// Serialized form of [`Billow`]. The sources are rebuilt from the seed rather
+/// than stored, so sources replaced with `set_sources` are not preserved.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct BillowConfig {
+    octaves: usize,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    seed: u32,
+    #[serde(default)]
+    octave_amplitudes: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<BillowConfig> for Billow<T>
+where
+    T: Default + Seedable,
+{
+    fn from(config: BillowConfig) -> Self {
+        Self::new(config.seed)
+            .set_octaves(config.octaves)
+            .set_frequency(config.frequency)
+            .set_lacunarity(config.lacunarity)
+            .set_persistence(config.persistence)
+            .set_octave_amplitudes(config.octave_amplitudes)
+    }
+}
+
 impl<T> Billow<T>
 where
     T: Default + Seedable,
@@ -65,15 +108,53 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            octave_amplitudes: Vec::new(),
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
                 Self::DEFAULT_OCTAVE_COUNT,
+                &[],
             ),
         }
     }
 
-    fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
-        let denom = (1..=octaves).fold(0.0, |acc, x| acc + persistence.powi(x as i32));
+    /// Sets the amplitude of each octave, overriding the geometric falloff of
+    /// `persistence ^ (x + 1)` for octave `x`.
+    ///
+    /// This allows envelopes that don't decay geometrically, such as boosting
+    /// mid-frequency detail. Octaves beyond the end of the list keep their
+    /// geometric amplitude, and an empty list restores the default. The output
+    /// is normalized by the sum of the magnitudes of the amplitudes.
+    pub fn set_octave_amplitudes(self, octave_amplitudes: Vec<f64>) -> Self {
+        Self {
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                self.octaves,
+                &octave_amplitudes,
+            ),
+            octave_amplitudes,
+            ..self
+        }
+    }
+
+    /// Sets the amplitude envelope of the octaves. This is the same as
+    /// [`set_octave_amplitudes`](Self::set_octave_amplitudes).
+    pub fn set_amplitude_envelope(self, envelope: Vec<f64>) -> Self {
+        self.set_octave_amplitudes(envelope)
+    }
+
+    /// Returns the factor that scales the output into the [-1, 1] range.
+    ///
+    /// The output is divided by the sum of the magnitudes of the amplitudes,
+    /// which bounds it even for negative persistences or amplitudes, where the
+    /// amplitudes alone can cancel out.
+    fn calc_scale_factor(persistence: f64, octaves: usize, octave_amplitudes: &[f64]) -> f64 {
+        let denom = (1..=octaves).fold(0.0, |acc, x| {
+            acc + octave_amplitudes
+                .get(x - 1)
+                .copied()
+                .unwrap_or_else(|| pow_sequential(persistence, x))
+                .abs()
+        });
 
         1.0 / denom
     }
@@ -105,7 +186,11 @@ where
         Self {
             octaves,
             sources: super::build_sources(self.seed, octaves),
-            scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                octaves,
+                &self.octave_amplitudes,
+            ),
             ..self
         }
     }
@@ -121,7 +206,11 @@ where
     fn set_persistence(self, persistence: f64) -> Self {
         Self {
             persistence,
-            scale_factor: Self::calc_scale_factor(persistence, self.octaves),
+            scale_factor: Self::calc_scale_factor(
+                persistence,
+                self.octaves,
+                &self.octave_amplitudes,
+            ),
             ..self
         }
     }
@@ -171,7 +260,11 @@ where
             signal = scale_shift(signal, 2.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -184,7 +277,7 @@ where
         }
 
         // Scale the result to the [-1,1] range.
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
     }
 }
 
@@ -211,7 +304,11 @@ where
             signal = scale_shift(signal, 2.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -224,7 +321,7 @@ where
         }
 
         // Scale the result to the [-1,1] range.
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
     }
 }
 
@@ -251,7 +348,11 @@ where
             signal = scale_shift(signal, 2.0);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -264,6 +365,6 @@ where
         }
 
         // Scale the result to the [-1,1] range.
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
     }
 }
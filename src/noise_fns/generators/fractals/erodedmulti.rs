@@ -0,0 +1,258 @@
+use crate::{
+    math::pow_sequential,
+    noise_fns::{MultiFractal, NoiseFn, NoiseFnWithDerivative, Seedable, Simplex},
+};
+use alloc::vec::Vec;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that outputs fBm noise whose detail is worn away on steep
+/// slopes, as a cheap approximation of hydraulic erosion.
+///
+/// The octaves are summed as in [`Fbm`](crate::Fbm), but their gradients are
+/// summed alongside them, and each octave is scaled by
+/// `1 / (1 + erosion * |gradient|)`, where `gradient` is the sum of the
+/// gradients up to and including that octave. Steep slopes
+/// therefore stay smooth while flat areas keep their fine detail, which gives
+/// terrain the look of eroded mountains with sediment-filled valleys. This is
+/// the technique also known as "swiss" or "jordan" turbulence.
+///
+/// The gradient of each octave is measured in the coordinates of that octave,
+/// so the damping doesn't depend on the frequency.
+///
+/// The octaves need to provide their derivatives. The default [`Simplex`]
+/// octaves calculate them analytically along with the value, while other
+/// sources can approximate them with
+/// [`FiniteDifference`](crate::FiniteDifference).
+#[derive(Clone, Debug)]
+pub struct ErodedMulti<T = Simplex> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    ///
+    /// The frequency of each successive octave is equal to the product of the
+    /// previous octave's frequency and the lacunarity value.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    ///
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence value. Increasing the
+    /// persistence produces "rougher" noise.
+    pub persistence: f64,
+
+    /// How strongly steep slopes damp the following octaves. 0.0 gives plain
+    /// fBm.
+    pub erosion: f64,
+
+    seed: u32,
+    sources: Vec<T>,
+    scale_factor: f64,
+}
+
+impl<T> ErodedMulti<T>
+where
+    T: Default + Seedable,
+{
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_EROSION: f64 = 1.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            erosion: Self::DEFAULT_EROSION,
+            sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            scale_factor: Self::calc_scale_factor(
+                Self::DEFAULT_PERSISTENCE,
+                Self::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    pub fn set_sources(self, sources: Vec<T>) -> Self {
+        Self { sources, ..self }
+    }
+
+    /// Sets how strongly steep slopes damp the following octaves.
+    pub fn set_erosion(self, erosion: f64) -> Self {
+        Self { erosion, ..self }
+    }
+
+    fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
+        let denom = (1..=octaves).fold(0.0, |acc, x| acc + pow_sequential(persistence, x));
+
+        1.0 / denom
+    }
+}
+
+impl<T> Default for ErodedMulti<T>
+where
+    T: Default + Seedable,
+{
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl<T> MultiFractal for ErodedMulti<T>
+where
+    T: Default + Seedable,
+{
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves),
+            scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            scale_factor: Self::calc_scale_factor(persistence, self.octaves),
+            ..self
+        }
+    }
+}
+
+impl<T> Seedable for ErodedMulti<T>
+where
+    T: Default + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl<T, const DIM: usize> NoiseFn<f64, DIM> for ErodedMulti<T>
+where
+    T: NoiseFnWithDerivative<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut point = point.map(|x| x * self.frequency);
+
+        let mut result = 0.0;
+        let mut gradient = [0.0; DIM];
+
+        let mut attenuation = self.persistence;
+
+        for source in &self.sources[..self.octaves] {
+            let (mut signal, derivatives) = source.get_with_derivative(point);
+
+            // Add the slope of this octave to the slope of the octaves so far.
+            for (total, derivative) in gradient.iter_mut().zip(derivatives) {
+                *total += derivative;
+            }
+            let slope = gradient.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+            // Scale the amplitude appropriately for this frequency, and damp it
+            // on steep slopes.
+            signal *= attenuation;
+            signal /= 1.0 + self.erosion * slope;
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= self.persistence;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            point = point.map(|x| x * self.lacunarity);
+        }
+
+        // Scale the result into the [-1,1] range
+        result * self.scale_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fbm, FiniteDifference};
+
+    fn grid() -> impl Iterator<Item = [f64; 2]> {
+        (0..1024).map(|i| [(i % 32) as f64 * 0.173 - 2.7, (i / 32) as f64 * 0.191])
+    }
+
+    #[test]
+    fn zero_erosion_matches_fbm() {
+        let eroded = ErodedMulti::<Simplex>::new(7).set_erosion(0.0);
+        let fbm = Fbm::<Simplex>::new(7);
+
+        for point in grid() {
+            assert_eq!(eroded.get(point), fbm.get(point));
+        }
+    }
+
+    #[test]
+    fn erosion_smooths_the_noise() {
+        let fbm = ErodedMulti::<Simplex>::new(7).set_erosion(0.0);
+        let eroded = fbm.clone().set_erosion(1.0);
+
+        // Sum the differences between neighboring samples as a measure of the
+        // roughness.
+        let roughness = |noise: &ErodedMulti| {
+            grid()
+                .map(|[x, y]| (noise.get([x + 0.01, y]) - noise.get([x, y])).abs())
+                .sum::<f64>()
+        };
+
+        assert!(grid().all(|point| (-1.0..=1.0).contains(&eroded.get(point))));
+        assert!(roughness(&eroded) < roughness(&fbm) * 0.8);
+    }
+
+    #[test]
+    fn analytic_derivatives_match_finite_differences() {
+        let analytic = ErodedMulti::<Simplex>::new(7);
+        let approximated = ErodedMulti::<FiniteDifference<Simplex>>::new(7);
+
+        for point in grid() {
+            assert!((analytic.get(point) - approximated.get(point)).abs() < 1e-4);
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
-    math::vectors::*,
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    math::{pow_sequential, vectors::*},
+    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -20,8 +20,24 @@ use alloc::vec::Vec;
 /// and ever-decreasing amplitude.
 ///
 /// fBm is commonly referred to as Perlin noise.
+///
+/// The octaves are generated with [`Perlin`] unless another source type is
+/// given, e.g. `Fbm::<Worley>::new(seed)`.
+///
+/// The sum of the octaves is divided by the sum of the magnitudes of their
+/// amplitudes, which keeps it within [-1, 1] for sources that are, and then
+/// clamped to [-1, 1] for sources that exceed it, such as
+/// [`Worley`](crate::Worley) distances.
 #[derive(Clone, Debug)]
-pub struct Fbm<T> {
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "FbmConfig",
+        bound(serialize = "", deserialize = "T: Default + Seedable")
+    )
+)]
+pub struct Fbm<T = Perlin> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
@@ -51,10 +67,46 @@ pub struct Fbm<T> {
     pub persistence: f64,
 
     seed: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     sources: Vec<T>,
+    octave_amplitudes: Vec<f64>,
+    octave_frequencies: Vec<f64>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     scale_factor: f64,
 }
 
+/// Serialized form of [`Fbm`]. The sources are rebuilt from the seed rather
+/// than stored, so sources replaced with `set_sources` are not preserved.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct FbmConfig {
+    octaves: usize,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    seed: u32,
+    #[serde(default)]
+    octave_amplitudes: Vec<f64>,
+    #[serde(default)]
+    octave_frequencies: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<FbmConfig> for Fbm<T>
+where
+    T: Default + Seedable,
+{
+    fn from(config: FbmConfig) -> Self {
+        Self::new(config.seed)
+            .set_octaves(config.octaves)
+            .set_frequency(config.frequency)
+            .set_lacunarity(config.lacunarity)
+            .set_persistence(config.persistence)
+            .set_octave_amplitudes(config.octave_amplitudes)
+            .set_octave_frequencies(config.octave_frequencies)
+    }
+}
+
 impl<T> Fbm<T>
 where
     T: Default + Seedable,
@@ -74,9 +126,12 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            octave_amplitudes: Vec::new(),
+            octave_frequencies: Vec::new(),
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
                 Self::DEFAULT_OCTAVE_COUNT,
+                &[],
             ),
         }
     }
@@ -85,13 +140,138 @@ where
         Self { sources, ..self }
     }
 
-    fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
-        let denom = (1..=octaves).fold(0.0, |acc, x| acc + persistence.powi(x as i32));
+    /// Sets the amplitude of each octave, overriding the geometric falloff of
+    /// `persistence ^ (x + 1)` for octave `x`.
+    ///
+    /// This allows envelopes that don't decay geometrically, such as boosting
+    /// mid-frequency detail. Octaves beyond the end of the list keep their
+    /// geometric amplitude, and an empty list restores the default. The output
+    /// is normalized by the sum of the magnitudes of the amplitudes.
+    pub fn set_octave_amplitudes(self, octave_amplitudes: Vec<f64>) -> Self {
+        Self {
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                self.octaves,
+                &octave_amplitudes,
+            ),
+            octave_amplitudes,
+            ..self
+        }
+    }
+
+    /// Sets the amplitude envelope of the octaves. This is the same as
+    /// [`set_octave_amplitudes`](Self::set_octave_amplitudes).
+    pub fn set_amplitude_envelope(self, envelope: Vec<f64>) -> Self {
+        self.set_octave_amplitudes(envelope)
+    }
+
+    /// Sets the frequency of each octave, overriding the geometric series of
+    /// `frequency * lacunarity ^ x` for octave `x`.
+    ///
+    /// Octaves beyond the end of the list keep their geometric frequency, and
+    /// an empty list restores the default.
+    pub fn set_octave_frequencies(self, octave_frequencies: Vec<f64>) -> Self {
+        Self {
+            octave_frequencies,
+            ..self
+        }
+    }
+
+    /// Returns the factor that scales the output into the [-1, 1] range.
+    ///
+    /// The output is divided by the sum of the magnitudes of the amplitudes,
+    /// which bounds it even for negative persistences or amplitudes, where the
+    /// amplitudes alone can cancel out.
+    fn calc_scale_factor(persistence: f64, octaves: usize, octave_amplitudes: &[f64]) -> f64 {
+        let denom = (1..=octaves).fold(0.0, |acc, x| {
+            acc + octave_amplitudes
+                .get(x - 1)
+                .copied()
+                .unwrap_or_else(|| pow_sequential(persistence, x))
+                .abs()
+        });
 
         1.0 / denom
     }
 }
 
+impl<T> Fbm<T> {
+    /// Returns the amplitude that `octave` is scaled by, before the output is
+    /// normalized into the [-1, 1] range.
+    pub fn octave_amplitude(&self, octave: usize) -> f64 {
+        self.octave_amplitudes
+            .get(octave)
+            .copied()
+            .unwrap_or_else(|| pow_sequential(self.persistence, octave + 1))
+    }
+
+    /// Returns the frequency that `octave` is sampled at.
+    pub fn octave_frequency(&self, octave: usize) -> f64 {
+        self.octave_frequencies
+            .get(octave)
+            .copied()
+            .unwrap_or_else(|| self.frequency * pow_sequential(self.lacunarity, octave))
+    }
+
+    /// Evaluates a slice of points one octave at a time, so that each source is
+    /// sampled with a single batched call instead of once per point.
+    ///
+    /// The arithmetic is performed in the same order as `get`, so the results
+    /// are identical.
+    fn get_many_by_octave<const DIM: usize>(&self, points: &[[f64; DIM]], out: &mut [f64])
+    where
+        T: NoiseFn<f64, DIM>,
+    {
+        assert_eq!(points.len(), out.len());
+
+        let mut octave_points: Vec<[f64; DIM]> = points
+            .iter()
+            .map(|point| point.map(|x| x * self.frequency))
+            .collect();
+        let mut signals = vec![0.0; points.len()];
+        let mut override_points = Vec::new();
+
+        let mut attenuation = self.persistence;
+
+        out.iter_mut().for_each(|value| *value = 0.0);
+
+        for (x, source) in self.sources[..self.octaves].iter().enumerate() {
+            // Get the signal for every point at this octave.
+            match self.octave_frequencies.get(x) {
+                Some(&frequency) => {
+                    override_points.clear();
+                    override_points.extend(points.iter().map(|point| point.map(|x| x * frequency)));
+                    source.get_many(&override_points, &mut signals);
+                }
+                None => source.get_many(&octave_points, &mut signals),
+            }
+
+            // Scale the amplitude appropriately for this frequency and add the
+            // signal to the result.
+            let amplitude = self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
+            for (value, signal) in out.iter_mut().zip(&signals) {
+                *value += signal * amplitude;
+            }
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= self.persistence;
+
+            // Increase the frequency for the next octave.
+            for point in &mut octave_points {
+                point.iter_mut().for_each(|x| *x *= self.lacunarity);
+            }
+        }
+
+        // Scale the result into the [-1,1] range
+        out.iter_mut()
+            .for_each(|value| *value = (*value * self.scale_factor).clamp(-1.0, 1.0));
+    }
+}
+
 impl<T> Default for Fbm<T>
 where
     T: Default + Seedable,
@@ -114,7 +294,11 @@ where
         Self {
             octaves,
             sources: super::build_sources(self.seed, octaves),
-            scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                octaves,
+                &self.octave_amplitudes,
+            ),
             ..self
         }
     }
@@ -130,7 +314,11 @@ where
     fn set_persistence(self, persistence: f64) -> Self {
         Self {
             persistence,
-            scale_factor: Self::calc_scale_factor(persistence, self.octaves),
+            scale_factor: Self::calc_scale_factor(
+                persistence,
+                self.octaves,
+                &self.octave_amplitudes,
+            ),
             ..self
         }
     }
@@ -163,7 +351,8 @@ where
     T: NoiseFn<f64, 2>,
 {
     fn get(&self, point: [f64; 2]) -> f64 {
-        let mut point = Vector2::from(point);
+        let origin = Vector2::from(point);
+        let mut point = origin;
 
         let mut result = 0.0;
 
@@ -172,11 +361,19 @@ where
         point *= self.frequency;
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            // Get the signal, at the overridden frequency if there is one.
+            let octave_point = match self.octave_frequencies.get(x) {
+                Some(&frequency) => origin * frequency,
+                None => point,
+            };
+            let mut signal = self.sources[x].get(octave_point.into_array());
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -189,7 +386,11 @@ where
         }
 
         // Scale the result into the [-1,1] range
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
+    }
+
+    fn get_many(&self, points: &[[f64; 2]], out: &mut [f64]) {
+        self.get_many_by_octave(points, out)
     }
 }
 
@@ -199,7 +400,8 @@ where
     T: NoiseFn<f64, 3>,
 {
     fn get(&self, point: [f64; 3]) -> f64 {
-        let mut point = Vector3::from(point);
+        let origin = Vector3::from(point);
+        let mut point = origin;
 
         let mut result = 0.0;
 
@@ -208,11 +410,19 @@ where
         point *= self.frequency;
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            // Get the signal, at the overridden frequency if there is one.
+            let octave_point = match self.octave_frequencies.get(x) {
+                Some(&frequency) => origin * frequency,
+                None => point,
+            };
+            let mut signal = self.sources[x].get(octave_point.into_array());
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -225,7 +435,11 @@ where
         }
 
         // Scale the result into the [-1,1] range
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
+    }
+
+    fn get_many(&self, points: &[[f64; 3]], out: &mut [f64]) {
+        self.get_many_by_octave(points, out)
     }
 }
 
@@ -235,7 +449,8 @@ where
     T: NoiseFn<f64, 4>,
 {
     fn get(&self, point: [f64; 4]) -> f64 {
-        let mut point = Vector4::from(point);
+        let origin = Vector4::from(point);
+        let mut point = origin;
 
         let mut result = 0.0;
 
@@ -244,11 +459,19 @@ where
         point *= self.frequency;
 
         for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point.into_array());
+            // Get the signal, at the overridden frequency if there is one.
+            let octave_point = match self.octave_frequencies.get(x) {
+                Some(&frequency) => origin * frequency,
+                None => point,
+            };
+            let mut signal = self.sources[x].get(octave_point.into_array());
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= attenuation;
+            signal *= self
+                .octave_amplitudes
+                .get(x)
+                .copied()
+                .unwrap_or(attenuation);
 
             // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
             attenuation *= self.persistence;
@@ -261,6 +484,115 @@ where
         }
 
         // Scale the result into the [-1,1] range
-        result * self.scale_factor
+        (result * self.scale_factor).clamp(-1.0, 1.0)
+    }
+
+    fn get_many(&self, points: &[[f64; 4]], out: &mut [f64]) {
+        self.get_many_by_octave(points, out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OpenSimplex, Worley};
+
+    fn grid<const DIM: usize>() -> Vec<[f64; DIM]> {
+        (0..256)
+            .map(|i| {
+                let mut point = [0.0; DIM];
+                for (axis, x) in point.iter_mut().enumerate() {
+                    *x = (i * (axis + 3)) as f64 * 0.173 - 21.7;
+                }
+                point
+            })
+            .collect()
+    }
+
+    fn assert_get_many_matches_get<N, const DIM: usize>(noise: &N)
+    where
+        N: NoiseFn<f64, DIM>,
+    {
+        let points = grid::<DIM>();
+        let mut out = vec![0.0; points.len()];
+        noise.get_many(&points, &mut out);
+
+        for (point, value) in points.iter().zip(&out) {
+            assert_eq!(noise.get(*point), *value);
+        }
+    }
+
+    #[test]
+    fn get_many_matches_get() {
+        let fbm = Fbm::<Perlin>::new(7);
+        assert_get_many_matches_get::<_, 2>(&fbm);
+        assert_get_many_matches_get::<_, 3>(&fbm);
+        assert_get_many_matches_get::<_, 4>(&fbm);
+
+        let fbm = Fbm::<Worley>::new(7).set_octaves(3);
+        assert_get_many_matches_get::<_, 2>(&fbm);
+        assert_get_many_matches_get::<_, 3>(&fbm);
+        assert_get_many_matches_get::<_, 4>(&fbm);
+    }
+
+    #[test]
+    fn geometric_envelope_matches_default() {
+        let fbm = Fbm::<Perlin>::new(7);
+        let persistence = Fbm::<Perlin>::DEFAULT_PERSISTENCE;
+        let envelope = (1..=fbm.octaves)
+            .map(|x| persistence.powi(x as i32))
+            .collect();
+        let enveloped = fbm.clone().set_octave_amplitudes(envelope);
+
+        for point in grid::<3>() {
+            assert!((fbm.get(point) - enveloped.get(point)).abs() < 1e-12);
+        }
+
+        // Enveloped noise must still batch identically.
+        assert_get_many_matches_get::<_, 3>(&enveloped.set_octave_amplitudes(vec![0.2, 1.0]));
+    }
+
+    #[test]
+    fn octave_frequencies_override_geometric_series() {
+        let fbm = Fbm::<Perlin>::new(7).set_octaves(3);
+        let frequencies = (0..3).map(|x| fbm.octave_frequency(x)).collect();
+        let geometric = fbm.clone().set_octave_frequencies(frequencies);
+
+        for point in grid::<3>() {
+            assert!((fbm.get(point) - geometric.get(point)).abs() < 1e-12);
+        }
+
+        // Only the first octave is overridden; the rest stay geometric.
+        let shaped = fbm.clone().set_octave_frequencies(vec![0.25]);
+        assert_eq!(shaped.octave_frequency(0), 0.25);
+        assert_eq!(shaped.octave_frequency(1), fbm.octave_frequency(1));
+        assert_eq!(shaped.octave_frequency(2), fbm.octave_frequency(2));
+
+        let single = fbm
+            .clone()
+            .set_octave_frequencies(vec![0.25])
+            .set_octave_amplitudes(vec![1.0, 0.0, 0.0]);
+        for point in grid::<2>() {
+            let expected = fbm.sources[0].get(point.map(|x| x * 0.25));
+            assert!((single.get(point) - expected).abs() < 1e-12);
+        }
+
+        assert_get_many_matches_get::<_, 2>(&shaped);
+        assert_get_many_matches_get::<_, 4>(&shaped.set_octave_amplitudes(vec![0.2, 1.0]));
+    }
+
+    #[test]
+    fn source_type_defaults_to_perlin() {
+        let fbm: Fbm = Fbm::new(3);
+        let perlin = Fbm::<Perlin>::new(3);
+
+        for point in grid::<2>() {
+            assert_eq!(fbm.get(point), perlin.get(point));
+        }
+
+        let simplex = Fbm::<OpenSimplex>::new(3).set_sources(vec![OpenSimplex::new(9); 6]);
+        assert!(grid::<2>()
+            .iter()
+            .any(|&point| simplex.get(point) != fbm.get(point)));
     }
 }
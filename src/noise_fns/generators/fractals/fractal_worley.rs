@@ -0,0 +1,299 @@
+use crate::{
+    core::worley::ReturnType,
+    math::{pow_sequential, vectors::*},
+    noise_fns::{MultiFractal, NoiseFn, Seedable, Worley},
+};
+use alloc::vec::Vec;
+
+/// Noise function that outputs fractal cellular noise.
+///
+/// This noise function stacks octaves of [`Worley`] distance noise of
+/// ever-increasing frequency and ever-decreasing amplitude, in the same way as
+/// fBm, which gives cells subdivided by smaller cells, suitable for rocks,
+/// cracked ground and lava.
+///
+/// Unlike `Fbm<Worley>`, each octave outputs the distance to the nearest seed
+/// point, normalized across dimensions and clamped into the [-1, 1] range, so
+/// that every octave contributes with the same range and the sum is bounded
+/// like the other fractals.
+#[derive(Clone)]
+pub struct FractalWorley {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    ///
+    /// The frequency of each successive octave is equal to the product of the
+    /// previous octave's frequency and the lacunarity value.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    ///
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence value. Increasing the
+    /// persistence produces "rougher" noise.
+    pub persistence: f64,
+
+    seed: u32,
+    sources: Vec<Worley>,
+    scale_factor: f64,
+}
+
+impl FractalWorley {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 4;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            sources: Self::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            scale_factor: Self::calc_scale_factor(
+                Self::DEFAULT_PERSISTENCE,
+                Self::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    fn build_sources(seed: u32, octaves: usize) -> Vec<Worley> {
+        super::build_sources::<Worley>(seed, octaves)
+            .into_iter()
+            .map(|source| {
+                source
+                    .set_return_type(ReturnType::Distance)
+                    .set_normalize_by_dimension(true)
+            })
+            .collect()
+    }
+
+    fn calc_scale_factor(persistence: f64, octaves: usize) -> f64 {
+        let denom = (1..=octaves).fold(0.0, |acc, x| acc + pow_sequential(persistence, x));
+
+        1.0 / denom
+    }
+}
+
+impl Default for FractalWorley {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl MultiFractal for FractalWorley {
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: Self::build_sources(self.seed, octaves),
+            scale_factor: Self::calc_scale_factor(self.persistence, octaves),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            scale_factor: Self::calc_scale_factor(persistence, self.octaves),
+            ..self
+        }
+    }
+}
+
+impl Seedable for FractalWorley {
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: Self::build_sources(seed, self.octaves),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// 2-dimensional FractalWorley noise
+impl NoiseFn<f64, 2> for FractalWorley {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let mut point = Vector2::from(point);
+
+        let mut result = 0.0;
+
+        let mut attenuation = self.persistence;
+
+        point *= self.frequency;
+
+        for x in 0..self.octaves {
+            // Get the signal, clamping the distance into the [-1,1] range.
+            let mut signal = self.sources[x].get(point.into_array()).clamp(-1.0, 1.0);
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= attenuation;
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= self.persistence;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            point *= self.lacunarity;
+        }
+
+        // Scale the result into the [-1,1] range
+        result * self.scale_factor
+    }
+}
+
+/// 3-dimensional FractalWorley noise
+impl NoiseFn<f64, 3> for FractalWorley {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let mut point = Vector3::from(point);
+
+        let mut result = 0.0;
+
+        let mut attenuation = self.persistence;
+
+        point *= self.frequency;
+
+        for x in 0..self.octaves {
+            // Get the signal, clamping the distance into the [-1,1] range.
+            let mut signal = self.sources[x].get(point.into_array()).clamp(-1.0, 1.0);
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= attenuation;
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= self.persistence;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            point *= self.lacunarity;
+        }
+
+        // Scale the result into the [-1,1] range
+        result * self.scale_factor
+    }
+}
+
+/// 4-dimensional FractalWorley noise
+impl NoiseFn<f64, 4> for FractalWorley {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let mut point = Vector4::from(point);
+
+        let mut result = 0.0;
+
+        let mut attenuation = self.persistence;
+
+        point *= self.frequency;
+
+        for x in 0..self.octaves {
+            // Get the signal, clamping the distance into the [-1,1] range.
+            let mut signal = self.sources[x].get(point.into_array()).clamp(-1.0, 1.0);
+
+            // Scale the amplitude appropriately for this frequency.
+            signal *= attenuation;
+
+            // Increase the attenuation for the next octave, to be equal to persistence ^ (x + 1)
+            attenuation *= self.persistence;
+
+            // Add the signal to the result.
+            result += signal;
+
+            // Increase the frequency for the next octave.
+            point *= self.lacunarity;
+        }
+
+        // Scale the result into the [-1,1] range
+        result * self.scale_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of local minima over a grid, each of which marks a cell center
+    /// of one of the octaves.
+    fn cell_count(noise: &FractalWorley) -> usize {
+        let size = 96;
+        let grid: Vec<f64> = (0..size * size)
+            .map(|i| {
+                let value = noise.get([(i % size) as f64 * 0.05, (i / size) as f64 * 0.05]);
+                assert!((-1.0..=1.0).contains(&value));
+                value
+            })
+            .collect();
+
+        let mut count = 0;
+        for y in 1..size - 1 {
+            for x in 1..size - 1 {
+                let value = grid[x + y * size];
+                let neighbors = [
+                    grid[x - 1 + y * size],
+                    grid[x + 1 + y * size],
+                    grid[x + (y - 1) * size],
+                    grid[x + (y + 1) * size],
+                ];
+                if neighbors.iter().all(|&neighbor| value < neighbor) {
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    #[test]
+    fn output_is_bounded_and_multi_scale() {
+        let single = FractalWorley::new(4).set_octaves(1);
+        let fractal = FractalWorley::new(4).set_octaves(3);
+
+        // The finer octaves add cells within the cells of the first octave.
+        let single_cells = cell_count(&single);
+        let fractal_cells = cell_count(&fractal);
+        assert!(single_cells > 0);
+        assert!(fractal_cells > single_cells * 4);
+
+        for i in 0..256 {
+            let t = i as f64 * 0.173 - 20.0;
+            assert!((-1.0..=1.0).contains(&fractal.get([t, -t * 0.7, t * 0.3])));
+            assert!((-1.0..=1.0).contains(&fractal.get([t, -t * 0.7, t * 0.3, 2.0])));
+        }
+    }
+}
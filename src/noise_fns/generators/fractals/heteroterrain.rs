@@ -0,0 +1,371 @@
+use crate::{
+    math::vectors::*,
+    noise_fns::{MultiFractal, NoiseFn, Seedable},
+};
+use alloc::vec::Vec;
+
+/// Noise function that outputs heterogeneous terrain multifractal noise.
+///
+/// This is Musgrave's "hetero terrain" multifractal. Each octave is offset and
+/// then scaled by the running result before being added to it, so areas that
+/// are already low stay smooth while high areas pick up more and more detail.
+/// This produces a mix of flat plains and rough mountains, instead of the
+/// uniform roughness of fBm noise.
+///
+/// The output is scaled by the largest value it can reach, so that it ranges
+/// from -1.0 to 1.0 as long as the offset is at least 1.0. Most of the values
+/// lie in the lower part of that range, which forms the plains. Smaller
+/// offsets produce lower, flatter terrain but may fall below -1.0.
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "HeteroTerrainConfig",
+        bound(serialize = "", deserialize = "T: Default + Seedable")
+    )
+)]
+pub struct HeteroTerrain<T> {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    ///
+    /// The frequency of each successive octave is equal to the product of the
+    /// previous octave's frequency and the lacunarity value.
+    ///
+    /// A lacunarity of 2.0 results in the frequency doubling every octave. For
+    /// almost all cases, 2.0 is a good value to use.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    ///
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence value. Increasing the
+    /// persistence produces "rougher" noise.
+    pub persistence: f64,
+
+    /// Value added to the output of each octave before it is weighted. This
+    /// raises the overall height of the terrain, and with it the amount of
+    /// detail that the higher octaves add.
+    pub offset: f64,
+
+    seed: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    sources: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scale_factor: f64,
+}
+
+/// Serialized form of [`HeteroTerrain`]. The sources are rebuilt from the seed
+/// rather than stored, so sources replaced with `set_sources` are not preserved.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct HeteroTerrainConfig {
+    octaves: usize,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    offset: f64,
+    seed: u32,
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<HeteroTerrainConfig> for HeteroTerrain<T>
+where
+    T: Default + Seedable,
+{
+    fn from(config: HeteroTerrainConfig) -> Self {
+        Self::new(config.seed)
+            .set_octaves(config.octaves)
+            .set_frequency(config.frequency)
+            .set_lacunarity(config.lacunarity)
+            .set_persistence(config.persistence)
+            .set_offset(config.offset)
+    }
+}
+
+impl<T> HeteroTerrain<T>
+where
+    T: Default + Seedable,
+{
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_OFFSET: f64 = 1.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            offset: Self::DEFAULT_OFFSET,
+            sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
+            scale_factor: Self::calc_scale_factor(
+                Self::DEFAULT_PERSISTENCE,
+                Self::DEFAULT_OFFSET,
+                Self::DEFAULT_OCTAVE_COUNT,
+            ),
+        }
+    }
+
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self {
+            offset,
+            scale_factor: Self::calc_scale_factor(self.persistence, offset, self.octaves),
+            ..self
+        }
+    }
+
+    pub fn set_sources(self, sources: Vec<T>) -> Self {
+        Self { sources, ..self }
+    }
+
+    fn calc_scale_factor(persistence: f64, offset: f64, octaves: usize) -> f64 {
+        // The result is largest when every octave outputs 1.0.
+        let mut amplitude = 1.0;
+        let mut result = 1.0 + offset;
+
+        for _ in 1..octaves {
+            amplitude *= persistence;
+            result += (1.0 + offset) * amplitude * result;
+        }
+
+        2.0 / result
+    }
+}
+
+impl<T> Default for HeteroTerrain<T>
+where
+    T: Default + Seedable,
+{
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl<T> MultiFractal for HeteroTerrain<T>
+where
+    T: Default + Seedable,
+{
+    fn set_octaves(self, mut octaves: usize) -> Self {
+        if self.octaves == octaves {
+            return self;
+        }
+
+        octaves = octaves.clamp(1, Self::MAX_OCTAVES);
+        Self {
+            octaves,
+            sources: super::build_sources(self.seed, octaves),
+            scale_factor: Self::calc_scale_factor(self.persistence, self.offset, octaves),
+            ..self
+        }
+    }
+
+    fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    fn set_lacunarity(self, lacunarity: f64) -> Self {
+        Self { lacunarity, ..self }
+    }
+
+    fn set_persistence(self, persistence: f64) -> Self {
+        Self {
+            persistence,
+            scale_factor: Self::calc_scale_factor(persistence, self.offset, self.octaves),
+            ..self
+        }
+    }
+}
+
+impl<T> Seedable for HeteroTerrain<T>
+where
+    T: Default + Seedable,
+{
+    fn set_seed(self, seed: u32) -> Self {
+        if self.seed == seed {
+            return self;
+        }
+
+        Self {
+            seed,
+            sources: super::build_sources(seed, self.octaves),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+/// 2-dimensional `HeteroTerrain` noise
+impl<T> NoiseFn<f64, 2> for HeteroTerrain<T>
+where
+    T: NoiseFn<f64, 2>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let mut point = Vector2::from(point);
+
+        point *= self.frequency;
+
+        // The first octave sets the base height of the terrain.
+        let mut result = self.sources[0].get(point.into_array()) + self.offset;
+
+        let mut amplitude = 1.0;
+
+        for x in 1..self.octaves {
+            // Increase the frequency.
+            point *= self.lacunarity;
+
+            // Decrease the amplitude, to be equal to persistence ^ x.
+            amplitude *= self.persistence;
+
+            // Get the offset value for this octave.
+            let mut increment = (self.sources[x].get(point.into_array()) + self.offset) * amplitude;
+
+            // Weight it by the height so far, so that low areas stay smooth.
+            increment *= result;
+
+            result += increment;
+        }
+
+        // Scale the result to [0, 2], then shift it to [-1, 1].
+        result * self.scale_factor - 1.0
+    }
+}
+
+/// 3-dimensional `HeteroTerrain` noise
+impl<T> NoiseFn<f64, 3> for HeteroTerrain<T>
+where
+    T: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let mut point = Vector3::from(point);
+
+        point *= self.frequency;
+
+        // The first octave sets the base height of the terrain.
+        let mut result = self.sources[0].get(point.into_array()) + self.offset;
+
+        let mut amplitude = 1.0;
+
+        for x in 1..self.octaves {
+            // Increase the frequency.
+            point *= self.lacunarity;
+
+            // Decrease the amplitude, to be equal to persistence ^ x.
+            amplitude *= self.persistence;
+
+            // Get the offset value for this octave.
+            let mut increment = (self.sources[x].get(point.into_array()) + self.offset) * amplitude;
+
+            // Weight it by the height so far, so that low areas stay smooth.
+            increment *= result;
+
+            result += increment;
+        }
+
+        // Scale the result to [0, 2], then shift it to [-1, 1].
+        result * self.scale_factor - 1.0
+    }
+}
+
+/// 4-dimensional `HeteroTerrain` noise
+impl<T> NoiseFn<f64, 4> for HeteroTerrain<T>
+where
+    T: NoiseFn<f64, 4>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let mut point = Vector4::from(point);
+
+        point *= self.frequency;
+
+        // The first octave sets the base height of the terrain.
+        let mut result = self.sources[0].get(point.into_array()) + self.offset;
+
+        let mut amplitude = 1.0;
+
+        for x in 1..self.octaves {
+            // Increase the frequency.
+            point *= self.lacunarity;
+
+            // Decrease the amplitude, to be equal to persistence ^ x.
+            amplitude *= self.persistence;
+
+            // Get the offset value for this octave.
+            let mut increment = (self.sources[x].get(point.into_array()) + self.offset) * amplitude;
+
+            // Weight it by the height so far, so that low areas stay smooth.
+            increment *= result;
+
+            result += increment;
+        }
+
+        // Scale the result to [0, 2], then shift it to [-1, 1].
+        result * self.scale_factor - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    /// Source that outputs the same value everywhere.
+    #[derive(Clone, Copy, Default)]
+    struct Flat(f64);
+
+    impl Seedable for Flat {
+        fn set_seed(self, _seed: u32) -> Self {
+            self
+        }
+
+        fn seed(&self) -> u32 {
+            0
+        }
+    }
+
+    impl<const DIM: usize> NoiseFn<f64, DIM> for Flat {
+        fn get(&self, _point: [f64; DIM]) -> f64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn single_octave_is_offset_source() {
+        let noise = HeteroTerrain::<Perlin>::new(7)
+            .set_octaves(1)
+            .set_offset(1.5);
+        let perlin = Perlin::new(super::super::octave_seed(7, 0));
+
+        for i in 0..32 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * -0.21 + 1.3];
+            let expected = (perlin.get(point) + 1.5) * 2.0 / 2.5 - 1.0;
+            assert!((noise.get(point) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn output_reaches_the_bounds() {
+        let flat = |value| HeteroTerrain::default().set_sources(alloc::vec![Flat(value); 6]);
+
+        assert!((flat(1.0).get([0.5, 0.5, 0.5]) - 1.0).abs() < 1e-12);
+        assert_eq!(flat(-1.0).get([0.5, 0.5, 0.5]), -1.0);
+    }
+}
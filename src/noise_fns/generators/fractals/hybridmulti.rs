@@ -1,6 +1,6 @@
 use crate::{
     math::vectors::*,
-    noise_fns::{MultiFractal, NoiseFn, Seedable},
+    noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable},
 };
 use alloc::vec::Vec;
 
@@ -9,7 +9,7 @@ use alloc::vec::Vec;
 /// The result of this multifractal noise is that valleys in the noise should
 /// have smooth bottoms at all altitudes.
 #[derive(Clone, Debug)]
-pub struct HybridMulti<T> {
+pub struct HybridMulti<T = Perlin> {
     /// Total number of frequency octaves to generate the noise with.
     ///
     /// The number of octaves control the _amount of detail_ in the noise
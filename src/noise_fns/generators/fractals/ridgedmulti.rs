@@ -1,8 +1,40 @@
 use crate::{
-    math::vectors::*,
+    math::{pow_sequential, vectors::*},
     noise_fns::{MultiFractal, NoiseFn, Seedable},
 };
 use alloc::vec::Vec;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// How [`RidgedMulti`] derives the weight of each octave from the signal of
+/// the previous one. The weight is always clamped to [0, 1].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AttenuationMode {
+    /// `signal / attenuation`, the classic ridged multifractal weight.
+    #[default]
+    Linear,
+
+    /// `1 - exp(-signal / attenuation)`, which eases into full weight, so that
+    /// the detail on the highest ridges is damped.
+    Exponential,
+
+    /// `signal ^ attenuation`, which suppresses the detail away from the
+    /// ridges more strongly than the linear weight.
+    Power,
+}
+
+impl AttenuationMode {
+    fn weight(self, signal: f64, attenuation: f64) -> f64 {
+        let weight = match self {
+            Self::Linear => signal / attenuation,
+            Self::Exponential => 1.0 - (-signal / attenuation).exp(),
+            Self::Power => signal.powf(attenuation),
+        };
+
+        weight.clamp(0.0, 1.0)
+    }
+}
 
 /// Noise function that outputs ridged-multifractal noise.
 ///
@@ -21,6 +53,14 @@ use alloc::vec::Vec;
 /// Ridged-multifractal noise is often used to generate craggy mountainous
 /// terrain or marble-like textures.
 #[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        from = "RidgedMultiConfig",
+        bound(serialize = "", deserialize = "T: Default + Seedable")
+    )
+)]
 pub struct RidgedMulti<T> {
     /// Total number of frequency octaves to generate the noise with.
     ///
@@ -56,11 +96,60 @@ pub struct RidgedMulti<T> {
     /// half the height of the previous.
     pub attenuation: f64,
 
+    /// The value that the magnitude of each octave is subtracted from to form
+    /// the ridges, as in `offset - |signal|`. Raising it above the default of
+    /// 1.0 widens and flattens the ridges, and lowering it sharpens them.
+    pub offset: f64,
+
+    /// How the weight of each octave is derived from the previous signal.
+    pub attenuation_mode: AttenuationMode,
+
     seed: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
     sources: Vec<T>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     scale_factor: f64,
 }
 
+/// Serialized form of [`RidgedMulti`]. The sources are rebuilt from the seed rather
+/// than stored, so sources replaced with `set_sources` are not preserved.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RidgedMultiConfig {
+    octaves: usize,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+    attenuation: f64,
+    #[serde(default = "default_offset")]
+    offset: f64,
+    #[serde(default)]
+    attenuation_mode: AttenuationMode,
+    seed: u32,
+}
+
+#[cfg(feature = "serde")]
+fn default_offset() -> f64 {
+    1.0
+}
+
+#[cfg(feature = "serde")]
+impl<T> From<RidgedMultiConfig> for RidgedMulti<T>
+where
+    T: Default + Seedable,
+{
+    fn from(config: RidgedMultiConfig) -> Self {
+        Self::new(config.seed)
+            .set_octaves(config.octaves)
+            .set_frequency(config.frequency)
+            .set_lacunarity(config.lacunarity)
+            .set_persistence(config.persistence)
+            .set_attenuation(config.attenuation)
+            .set_offset(config.offset)
+            .set_attenuation_mode(config.attenuation_mode)
+    }
+}
+
 impl<T> RidgedMulti<T>
 where
     T: Default + Seedable,
@@ -71,6 +160,7 @@ where
     pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 1.0;
     pub const DEFAULT_ATTENUATION: f64 = 2.0;
+    pub const DEFAULT_OFFSET: f64 = 1.0;
     pub const MAX_OCTAVES: usize = 32;
 
     pub fn new(seed: u32) -> Self {
@@ -81,10 +171,14 @@ where
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
             attenuation: Self::DEFAULT_ATTENUATION,
+            offset: Self::DEFAULT_OFFSET,
+            attenuation_mode: AttenuationMode::Linear,
             sources: super::build_sources(seed, Self::DEFAULT_OCTAVE_COUNT),
             scale_factor: Self::calc_scale_factor(
                 Self::DEFAULT_PERSISTENCE,
                 Self::DEFAULT_ATTENUATION,
+                Self::DEFAULT_OFFSET,
+                AttenuationMode::Linear,
                 Self::DEFAULT_OCTAVE_COUNT,
             ),
         }
@@ -93,7 +187,41 @@ where
     pub fn set_attenuation(self, attenuation: f64) -> Self {
         Self {
             attenuation,
-            scale_factor: Self::calc_scale_factor(self.persistence, attenuation, self.octaves),
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                attenuation,
+                self.offset,
+                self.attenuation_mode,
+                self.octaves,
+            ),
+            ..self
+        }
+    }
+
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self {
+            offset,
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                self.attenuation,
+                offset,
+                self.attenuation_mode,
+                self.octaves,
+            ),
+            ..self
+        }
+    }
+
+    pub fn set_attenuation_mode(self, attenuation_mode: AttenuationMode) -> Self {
+        Self {
+            attenuation_mode,
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                self.attenuation,
+                self.offset,
+                attenuation_mode,
+                self.octaves,
+            ),
             ..self
         }
     }
@@ -102,21 +230,32 @@ where
         Self { sources, ..self }
     }
 
-    fn calc_scale_factor(persistence: f64, attenuation: f64, octaves: usize) -> f64 {
+    fn calc_scale_factor(
+        persistence: f64,
+        attenuation: f64,
+        offset: f64,
+        attenuation_mode: AttenuationMode,
+        octaves: usize,
+    ) -> f64 {
         let mut denom = 0.0;
 
+        // The largest squared ridge value, reached where the source is 0.0, or
+        // where it is ±1.0 if the offset is below 0.5.
+        let peak = offset.abs().max((offset - 1.0).abs());
+        let peak = peak * peak;
+
         // Do octave 0
         let mut amplitude = 1.0;
         let mut weight = 1.0;
-        let mut signal = weight * amplitude;
+        let mut signal = peak * weight * amplitude;
 
         denom += signal;
 
         if octaves >= 1 {
             denom += (1..=octaves).fold(0.0, |acc, x| {
                 amplitude *= persistence;
-                weight = (signal / attenuation.powi(x as i32)).clamp(0.0, 1.0);
-                signal = weight * amplitude;
+                weight = attenuation_mode.weight(signal, pow_sequential(attenuation, x));
+                signal = peak * weight * amplitude;
                 acc + signal
             });
         }
@@ -147,7 +286,13 @@ where
         Self {
             octaves,
             sources: super::build_sources(self.seed, octaves),
-            scale_factor: Self::calc_scale_factor(self.persistence, self.attenuation, octaves),
+            scale_factor: Self::calc_scale_factor(
+                self.persistence,
+                self.attenuation,
+                self.offset,
+                self.attenuation_mode,
+                octaves,
+            ),
             ..self
         }
     }
@@ -163,7 +308,13 @@ where
     fn set_persistence(self, persistence: f64) -> Self {
         Self {
             persistence,
-            scale_factor: Self::calc_scale_factor(persistence, self.attenuation, self.octaves),
+            scale_factor: Self::calc_scale_factor(
+                persistence,
+                self.attenuation,
+                self.offset,
+                self.attenuation_mode,
+                self.octaves,
+            ),
             ..self
         }
     }
@@ -211,7 +362,7 @@ where
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = self.offset - signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -221,11 +372,9 @@ where
             // the ridges.
             signal *= weight;
 
-            // Weight successive contributions by the previous signal.
-            weight = signal / self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = weight.clamp(0.0, 1.0);
+            // Weight successive contributions by the previous signal. The
+            // weight is clamped to [0,1] to prevent the result from diverging.
+            weight = self.attenuation_mode.weight(signal, self.attenuation);
 
             // Scale the amplitude appropriately for this frequency.
             signal *= attenuation;
@@ -275,7 +424,7 @@ where
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = self.offset - signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -285,11 +434,9 @@ where
             // the ridges.
             signal *= weight;
 
-            // Weight successive contributions by the previous signal.
-            weight = signal / self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = weight.clamp(0.0, 1.0);
+            // Weight successive contributions by the previous signal. The
+            // weight is clamped to [0,1] to prevent the result from diverging.
+            weight = self.attenuation_mode.weight(signal, self.attenuation);
 
             // Scale the amplitude appropriately for this frequency.
             signal *= attenuation;
@@ -339,7 +486,7 @@ where
 
             // Make the ridges.
             signal = signal.abs();
-            signal = 1.0 - signal;
+            signal = self.offset - signal;
 
             // Square the signal to increase the sharpness of the ridges.
             signal *= signal;
@@ -349,11 +496,9 @@ where
             // the ridges.
             signal *= weight;
 
-            // Weight successive contributions by the previous signal.
-            weight = signal / self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = weight.clamp(0.0, 1.0);
+            // Weight successive contributions by the previous signal. The
+            // weight is clamped to [0,1] to prevent the result from diverging.
+            weight = self.attenuation_mode.weight(signal, self.attenuation);
 
             // Scale the amplitude appropriately for this frequency.
             signal *= attenuation;
@@ -381,3 +526,49 @@ where
         result - 1.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn offset_moves_ridges() {
+        let default = RidgedMulti::<Perlin>::new(7);
+        let unit = default.clone().set_offset(1.0);
+        let sharp = default.clone().set_offset(0.8);
+
+        let mut differs = false;
+        for i in 0..256 {
+            let point = [i as f64 * 0.173, i as f64 * -0.071, i as f64 * 0.037];
+            let value = default.get(point);
+            assert_eq!(unit.get(point), value);
+
+            differs |= sharp.get(point) != value;
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn attenuation_mode_changes_weights() {
+        let linear = RidgedMulti::<Perlin>::new(7);
+        let explicit = linear.clone().set_attenuation_mode(AttenuationMode::Linear);
+        let exponential = linear
+            .clone()
+            .set_attenuation_mode(AttenuationMode::Exponential);
+        let power = linear.clone().set_attenuation_mode(AttenuationMode::Power);
+
+        let (mut exponential_differs, mut power_differs) = (false, false);
+        for i in 0..256 {
+            let point = [i as f64 * 0.173, i as f64 * -0.071, i as f64 * 0.037];
+            let value = linear.get(point);
+            assert_eq!(explicit.get(point), value);
+
+            let power_value = power.get(point);
+            assert!((-1.0..=1.0).contains(&power_value), "{}", power_value);
+            power_differs |= power_value != value;
+            exponential_differs |= exponential.get(point) != value;
+        }
+        assert!(exponential_differs && power_differs);
+    }
+}
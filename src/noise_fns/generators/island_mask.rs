@@ -0,0 +1,88 @@
+use crate::noise_fns::NoiseFn;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that outputs a smooth falloff for masking island continents.
+///
+/// The mask covers a `width` by `height` rectangle centered on the origin. It
+/// outputs 1.0 at the center and falls off to 0.0 at the corners of the
+/// rectangle, staying at 0.0 beyond them. Multiplying fractal terrain by this
+/// mask turns the edges of the map into ocean.
+///
+/// The `strength` controls how quickly the mask falls off. Higher values keep
+/// the mask close to 1.0 over a larger part of the rectangle and produce a
+/// steeper coastline. A strength of 1.0 falls off linearly.
+///
+/// The mask is applied in the _xy_ plane, so the _z_ and _u_ coordinates of
+/// 3d and 4d input values are ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct IslandMask {
+    /// Width of the masked rectangle.
+    pub width: f64,
+
+    /// Height of the masked rectangle.
+    pub height: f64,
+
+    /// Steepness of the falloff.
+    pub strength: f64,
+}
+
+impl IslandMask {
+    pub const DEFAULT_WIDTH: f64 = 2.0;
+    pub const DEFAULT_HEIGHT: f64 = 2.0;
+    pub const DEFAULT_STRENGTH: f64 = 2.0;
+
+    pub fn new() -> Self {
+        Self {
+            width: Self::DEFAULT_WIDTH,
+            height: Self::DEFAULT_HEIGHT,
+            strength: Self::DEFAULT_STRENGTH,
+        }
+    }
+
+    pub fn set_size(self, width: f64, height: f64) -> Self {
+        Self {
+            width,
+            height,
+            ..self
+        }
+    }
+
+    pub fn set_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+
+    fn mask(&self, x: f64, y: f64) -> f64 {
+        // Normalize the point so that the rectangle covers [-1, 1] on both axes,
+        // then scale the distance so that the corners are at 1.0.
+        let x = x * 2.0 / self.width;
+        let y = y * 2.0 / self.height;
+        let distance = (x * x + y * y).sqrt() * core::f64::consts::FRAC_1_SQRT_2;
+
+        1.0 - distance.min(1.0).powf(self.strength)
+    }
+}
+
+impl Default for IslandMask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseFn<f64, 2> for IslandMask {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.mask(point[0], point[1])
+    }
+}
+
+impl NoiseFn<f64, 3> for IslandMask {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.mask(point[0], point[1])
+    }
+}
+
+impl NoiseFn<f64, 4> for IslandMask {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.mask(point[0], point[1])
+    }
+}
@@ -4,14 +4,14 @@
 
 use crate::{
     core::open_simplex::{open_simplex_2d, open_simplex_3d, open_simplex_4d},
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NoiseFn, Seedable, Seedable64},
     permutationtable::PermutationTable,
 };
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
 #[derive(Clone, Copy, Debug)]
 pub struct OpenSimplex {
-    seed: u32,
+    seed: u64,
     perm_table: PermutationTable,
 }
 
@@ -20,7 +20,7 @@ impl OpenSimplex {
 
     pub fn new(seed: u32) -> Self {
         Self {
-            seed,
+            seed: seed.into(),
             perm_table: PermutationTable::new(seed),
         }
     }
@@ -35,6 +35,16 @@ impl Default for OpenSimplex {
 impl Seedable for OpenSimplex {
     /// Sets the seed value for Open Simplex noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for OpenSimplex {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -43,11 +53,11 @@ impl Seedable for OpenSimplex {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: PermutationTable::new_u64(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
@@ -1,14 +1,62 @@
 use crate::{
     core::perlin::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    gradient::GradientTable,
+    noise_fns::{NoiseFn, Seedable, Seedable64},
+    permutationtable::{NoiseHasher, PermutationTable},
 };
+#[cfg(feature = "simd")]
+use crate::{
+    core::perlin_simd::*,
+    math::vectors::{Vector2, Vector3},
+};
+#[cfg(feature = "twofloat")]
+use crate::{math::double_double::DoubleDouble, permutationtable::OffsetHasher};
 
 /// Noise function that outputs 1/2/3/4-dimensional Perlin noise.
 #[derive(Clone, Copy, Debug)]
-pub struct Perlin {
-    seed: u32,
-    perm_table: PermutationTable,
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Perlin<NH = PermutationTable> {
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hasher: NH,
+    unit_gradients: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gradients_2d: Option<GradientTable<2>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gradients_3d: Option<GradientTable<3>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    gradients_4d: Option<GradientTable<4>>,
+}
+
+/// Serialized form of [`Perlin`]. The permutation table is rebuilt from the
+/// seed rather than stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PerlinConfig {
+    seed: u64,
+    #[serde(default)]
+    unit_gradients: bool,
+}
+
+#[cfg(feature = "serde")]
+impl From<PerlinConfig> for Perlin {
+    fn from(config: PerlinConfig) -> Self {
+        Self::default()
+            .set_seed_u64(config.seed)
+            .use_unit_gradients(config.unit_gradients)
+    }
+}
+
+/// Only the default hasher can be deserialized, since a custom hasher is not
+/// stored.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Perlin {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        PerlinConfig::deserialize(deserializer).map(Self::from)
+    }
 }
 
 impl Perlin {
@@ -16,10 +64,96 @@ impl Perlin {
 
     pub fn new(seed: u32) -> Self {
         Self {
-            seed,
-            perm_table: PermutationTable::new(seed),
+            seed: seed.into(),
+            ..Self::with_hasher(PermutationTable::new(seed))
+        }
+    }
+}
+
+impl<NH> Perlin<NH>
+where
+    NH: NoiseHasher,
+{
+    /// Creates Perlin noise that hashes the lattice points with `hasher`
+    /// instead of a seeded [`PermutationTable`].
+    ///
+    /// The permutation table repeats every 256 lattice cells along each axis,
+    /// so a hasher with a longer period avoids visible repetition over large
+    /// coordinate ranges. The noise only uses the low bits of the hash values.
+    pub fn with_hasher(hasher: NH) -> Self {
+        Self {
+            seed: Perlin::DEFAULT_SEED.into(),
+            hasher,
+            unit_gradients: false,
+            gradients_2d: None,
+            gradients_3d: None,
+            gradients_4d: None,
+        }
+    }
+
+    pub fn hasher(&self) -> &NH {
+        &self.hasher
+    }
+
+    /// Enables or disables unit-length gradients for the 2/3/4-dimensional
+    /// noise.
+    ///
+    /// The default gradients are not unit length, so the output is clamped to
+    /// the [-1, 1] range. Unit-length gradients have a known maximum which is
+    /// scaled exactly to the [-1, 1] range, matching the range of classic
+    /// Perlin noise.
+    pub fn use_unit_gradients(self, unit_gradients: bool) -> Self {
+        Self {
+            unit_gradients,
+            ..self
+        }
+    }
+
+    pub fn unit_gradients(&self) -> bool {
+        self.unit_gradients
+    }
+
+    /// Uses the given gradients for the 2-dimensional noise instead of the
+    /// built-in set. This takes precedence over
+    /// [`use_unit_gradients`](Self::use_unit_gradients).
+    ///
+    /// See [`GradientTable`] for how the gradients are picked and scaled.
+    pub fn set_gradients_2d(self, gradients: &'static [[f64; 2]]) -> Self {
+        Self {
+            gradients_2d: Some(GradientTable::new(gradients)),
+            ..self
+        }
+    }
+
+    /// Uses the given gradients for the 3-dimensional noise instead of the
+    /// built-in set.
+    pub fn set_gradients_3d(self, gradients: &'static [[f64; 3]]) -> Self {
+        Self {
+            gradients_3d: Some(GradientTable::new(gradients)),
+            ..self
+        }
+    }
+
+    /// Uses the given gradients for the 4-dimensional noise instead of the
+    /// built-in set.
+    pub fn set_gradients_4d(self, gradients: &'static [[f64; 4]]) -> Self {
+        Self {
+            gradients_4d: Some(GradientTable::new(gradients)),
+            ..self
         }
     }
+
+    pub fn gradients_2d(&self) -> Option<&'static [[f64; 2]]> {
+        self.gradients_2d.map(|table| table.gradients())
+    }
+
+    pub fn gradients_3d(&self) -> Option<&'static [[f64; 3]]> {
+        self.gradients_3d.map(|table| table.gradients())
+    }
+
+    pub fn gradients_4d(&self) -> Option<&'static [[f64; 4]]> {
+        self.gradients_4d.map(|table| table.gradients())
+    }
 }
 
 impl Default for Perlin {
@@ -31,6 +165,16 @@ impl Default for Perlin {
 impl Seedable for Perlin {
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for Perlin {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -39,39 +183,300 @@ impl Seedable for Perlin {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            hasher: PermutationTable::new_u64(seed),
+            ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
 
 /// 1-dimensional perlin noise
-impl NoiseFn<f64, 1> for Perlin {
+impl<NH> NoiseFn<f64, 1> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 1]) -> f64 {
-        perlin_1d(point[0], &self.perm_table)
+        perlin_1d(point[0], &self.hasher)
     }
 }
 
 /// 2-dimensional perlin noise
-impl NoiseFn<f64, 2> for Perlin {
+impl<NH> NoiseFn<f64, 2> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 2]) -> f64 {
-        perlin_2d(point.into(), &self.perm_table)
+        if let Some(table) = &self.gradients_2d {
+            perlin_table(point, &self.hasher, table)
+        } else if self.unit_gradients {
+            perlin_unit_2d(point.into(), &self.hasher)
+        } else {
+            perlin_2d(point.into(), &self.hasher)
+        }
+    }
+
+    fn get_many(&self, points: &[[f64; 2]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = &self.hasher;
+        if let Some(table) = &self.gradients_2d {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = perlin_table(*point, hasher, table);
+            }
+            return;
+        }
+
+        let noise = if self.unit_gradients {
+            perlin_unit_2d
+        } else {
+            perlin_2d
+        };
+
+        #[cfg(feature = "simd")]
+        let (points, out) = if self.unit_gradients {
+            (points, out)
+        } else {
+            let mut chunks = points.chunks_exact(4);
+            let mut out_chunks = out.chunks_exact_mut(4);
+            for (chunk, values) in (&mut chunks).zip(&mut out_chunks) {
+                let chunk = [chunk[0], chunk[1], chunk[2], chunk[3]];
+                values.copy_from_slice(&perlin_2d_x4(chunk.map(Vector2::from), hasher));
+            }
+            (chunks.remainder(), out_chunks.into_remainder())
+        };
+
+        for (point, value) in points.iter().zip(out.iter_mut()) {
+            *value = noise((*point).into(), hasher);
+        }
     }
 }
 
 /// 3-dimensional perlin noise
-impl NoiseFn<f64, 3> for Perlin {
+impl<NH> NoiseFn<f64, 3> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 3]) -> f64 {
-        perlin_3d(point.into(), &self.perm_table)
+        if let Some(table) = &self.gradients_3d {
+            perlin_table(point, &self.hasher, table)
+        } else if self.unit_gradients {
+            perlin_unit_3d(point.into(), &self.hasher)
+        } else {
+            perlin_3d(point.into(), &self.hasher)
+        }
+    }
+
+    fn get_many(&self, points: &[[f64; 3]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = &self.hasher;
+        if let Some(table) = &self.gradients_3d {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = perlin_table(*point, hasher, table);
+            }
+            return;
+        }
+
+        let noise = if self.unit_gradients {
+            perlin_unit_3d
+        } else {
+            perlin_3d
+        };
+
+        #[cfg(feature = "simd")]
+        let (points, out) = if self.unit_gradients {
+            (points, out)
+        } else {
+            let mut chunks = points.chunks_exact(4);
+            let mut out_chunks = out.chunks_exact_mut(4);
+            for (chunk, values) in (&mut chunks).zip(&mut out_chunks) {
+                let chunk = [chunk[0], chunk[1], chunk[2], chunk[3]];
+                values.copy_from_slice(&perlin_3d_x4(chunk.map(Vector3::from), hasher));
+            }
+            (chunks.remainder(), out_chunks.into_remainder())
+        };
+
+        for (point, value) in points.iter().zip(out.iter_mut()) {
+            *value = noise((*point).into(), hasher);
+        }
     }
 }
 
 /// 4-dimensional perlin noise
-impl NoiseFn<f64, 4> for Perlin {
+impl<NH> NoiseFn<f64, 4> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 4]) -> f64 {
-        perlin_4d(point.into(), &self.perm_table)
+        if let Some(table) = &self.gradients_4d {
+            perlin_table(point, &self.hasher, table)
+        } else if self.unit_gradients {
+            perlin_unit_4d(point.into(), &self.hasher)
+        } else {
+            perlin_4d(point.into(), &self.hasher)
+        }
+    }
+
+    fn get_many(&self, points: &[[f64; 4]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = &self.hasher;
+        if let Some(table) = &self.gradients_4d {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = perlin_table(*point, hasher, table);
+            }
+            return;
+        }
+
+        let noise = if self.unit_gradients {
+            perlin_unit_4d
+        } else {
+            perlin_4d
+        };
+        for (point, value) in points.iter().zip(out.iter_mut()) {
+            *value = noise((*point).into(), hasher);
+        }
+    }
+}
+
+/// 2-dimensional Perlin noise at extended precision coordinates
+#[cfg(feature = "twofloat")]
+impl<NH> NoiseFn<DoubleDouble, 2> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [DoubleDouble; 2]) -> f64 {
+        let (cell, offset) = perlin_lattice_split(point);
+        let hasher = OffsetHasher {
+            hasher: &self.hasher,
+            offset: cell,
+        };
+
+        if let Some(table) = &self.gradients_2d {
+            perlin_table(offset, &hasher, table)
+        } else if self.unit_gradients {
+            perlin_unit_2d(offset.into(), &hasher)
+        } else {
+            perlin_2d(offset.into(), &hasher)
+        }
+    }
+}
+
+/// 3-dimensional Perlin noise at extended precision coordinates
+#[cfg(feature = "twofloat")]
+impl<NH> NoiseFn<DoubleDouble, 3> for Perlin<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [DoubleDouble; 3]) -> f64 {
+        let (cell, offset) = perlin_lattice_split(point);
+        let hasher = OffsetHasher {
+            hasher: &self.hasher,
+            offset: cell,
+        };
+
+        if let Some(table) = &self.gradients_3d {
+            perlin_table(offset, &hasher, table)
+        } else if self.unit_gradients {
+            perlin_unit_3d(offset.into(), &hasher)
+        } else {
+            perlin_3d(offset.into(), &hasher)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn custom_gradients_replace_builtin_set() {
+        // The built-in 2D gradients, in the order of the hash values.
+        static BUILTIN_2D: [[f64; 2]; 4] = [[1.0, 1.0], [-1.0, 1.0], [1.0, -1.0], [-1.0, -1.0]];
+        static AXES_3D: [[f64; 3]; 6] = [
+            [1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [0.0, -2.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0],
+        ];
+
+        let perlin = Perlin::new(4);
+        let custom = perlin
+            .set_gradients_2d(&BUILTIN_2D)
+            .set_gradients_3d(&AXES_3D);
+
+        let mut differs = false;
+        for i in 0..500 {
+            let x = i as f64 * 0.173 - 41.0;
+            let y = i as f64 * 0.061 + 7.0;
+
+            // The built-in noise does not account for its gradients being
+            // sqrt(2) long and clamps instead, while the table is scaled down
+            // by their length.
+            let expected = perlin.get([x, y]);
+            if expected.abs() < 1.0 {
+                let value = custom.get([x, y]) * core::f64::consts::SQRT_2;
+                assert!((value - expected).abs() < 1e-12);
+            }
+
+            let value = custom.get([x, y, x - y]);
+            assert!((-1.0..=1.0).contains(&value), "{} out of range", value);
+            differs |= (value - perlin.get([x, y, x - y])).abs() > 1e-6;
+        }
+        assert!(differs);
+    }
+
+    #[test]
+    fn one_dimensional_noise_stays_in_range() {
+        let perlin = Perlin::new(5);
+
+        for i in -512..512 {
+            let x = i as f64 * 0.173;
+            let value = perlin.get([x]);
+
+            assert!((-1.0..=1.0).contains(&value), "{} out of range", value);
+        }
+
+        // Negative inputs use the lattice cell below them, so the noise is
+        // zero on every integer and continuous across zero.
+        assert_eq!(perlin.get([-2.0]), 0.0);
+        assert!(perlin.get([-1e-9]).abs() < 1e-6);
+    }
+
+    #[cfg(feature = "twofloat")]
+    #[test]
+    fn extended_precision_keeps_detail_far_from_origin() {
+        let perlin = Perlin::new(3);
+
+        // The permutation table repeats every 256 cells, and 1e12 is a multiple
+        // of 256, so the noise around 1e12 repeats the noise around the origin.
+        let far = 1e12;
+        let mut lost_detail = false;
+        for i in 0..64 {
+            let x = i as f64 * 0.000_37 + 0.2;
+            let y = i as f64 * 0.011 - 0.4;
+
+            let near = perlin.get([x, y]);
+            let extended = perlin.get([DoubleDouble::new(far, x), DoubleDouble::from(y)]);
+            assert!((extended - near).abs() < 1e-12);
+
+            let near = perlin.get([x, y, 0.7]);
+            let extended = perlin.get([
+                DoubleDouble::from(x),
+                DoubleDouble::new(far, y),
+                DoubleDouble::new(-far, 0.7),
+            ]);
+            assert!((extended - near).abs() < 1e-12);
+
+            lost_detail |= (perlin.get([far + x, y]) - perlin.get([x, y])).abs() > 1e-6;
+        }
+
+        // Plain f64 coordinates round the fine steps away at this distance.
+        assert!(lost_detail);
     }
 }
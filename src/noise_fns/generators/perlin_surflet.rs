@@ -1,6 +1,6 @@
 use crate::{
     core::perlin_surflet::*,
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NoiseFn, Seedable, Seedable64},
     permutationtable::PermutationTable,
 };
 
@@ -10,7 +10,7 @@ use crate::{
 /// calculate the values at a point using wavelets instead of interpolated gradients.
 #[derive(Clone, Copy, Debug)]
 pub struct PerlinSurflet {
-    seed: u32,
+    seed: u64,
     perm_table: PermutationTable,
 }
 
@@ -19,7 +19,7 @@ impl PerlinSurflet {
 
     pub fn new(seed: u32) -> Self {
         Self {
-            seed,
+            seed: seed.into(),
             perm_table: PermutationTable::new(seed),
         }
     }
@@ -34,6 +34,16 @@ impl Default for PerlinSurflet {
 impl Seedable for PerlinSurflet {
     /// Sets the seed value for Perlin noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for PerlinSurflet {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -42,11 +52,11 @@ impl Seedable for PerlinSurflet {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: PermutationTable::new_u64(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
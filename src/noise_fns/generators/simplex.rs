@@ -1,15 +1,17 @@
 use crate::{
     core::simplex::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    noise_fns::{NoiseFn, NoiseFnWithDerivative, Seedable, Seedable64},
+    permutationtable::{NoiseHasher, PermutationTable},
 };
+#[cfg(feature = "twofloat")]
+use crate::{math::double_double::DoubleDouble, permutationtable::OffsetHasher};
 
 /// Noise function that outputs N-dimensional Simplex noise.
 ///
 #[derive(Clone, Copy, Debug)]
-pub struct Simplex {
-    seed: u32,
-    hasher: PermutationTable,
+pub struct Simplex<NH = PermutationTable> {
+    seed: u64,
+    hasher: NH,
 }
 
 impl Simplex {
@@ -17,12 +19,31 @@ impl Simplex {
 
     pub fn new(seed: u32) -> Self {
         Simplex {
-            seed,
+            seed: seed.into(),
             hasher: PermutationTable::new(seed),
         }
     }
 }
 
+impl<NH> Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    /// Creates Simplex noise that hashes the lattice points with `hasher`
+    /// instead of a seeded [`PermutationTable`]. See
+    /// [`Perlin::with_hasher`](crate::Perlin::with_hasher).
+    pub fn with_hasher(hasher: NH) -> Self {
+        Simplex {
+            seed: Simplex::DEFAULT_SEED.into(),
+            hasher,
+        }
+    }
+
+    pub fn hasher(&self) -> &NH {
+        &self.hasher
+    }
+}
+
 impl Default for Simplex {
     fn default() -> Self {
         Self::new(Self::DEFAULT_SEED)
@@ -32,25 +53,38 @@ impl Default for Simplex {
 impl Seedable for Simplex {
     /// Sets the seed value for Simplex noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for Simplex {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
         }
 
         // Otherwise, regenerate the permutation table based on the new seed.
-        Simplex {
+        Self {
             seed,
-            hasher: PermutationTable::new(seed),
+            hasher: PermutationTable::new_u64(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
 
 /// 2-dimensional Simplex noise
-impl NoiseFn<f64, 2> for Simplex {
+impl<NH> NoiseFn<f64, 2> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 2]) -> f64 {
         let (result, _) = simplex_2d(point.into(), &self.hasher);
 
@@ -58,8 +92,27 @@ impl NoiseFn<f64, 2> for Simplex {
     }
 }
 
+/// 2-dimensional Simplex noise with its analytic derivatives
+impl<NH> NoiseFnWithDerivative<f64, 2> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get_derivative(&self, point: [f64; 2]) -> [f64; 2] {
+        let (_, derivative) = simplex_2d(point.into(), &self.hasher);
+
+        derivative
+    }
+
+    fn get_with_derivative(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        simplex_2d(point.into(), &self.hasher)
+    }
+}
+
 /// 3-dimensional Simplex noise
-impl NoiseFn<f64, 3> for Simplex {
+impl<NH> NoiseFn<f64, 3> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 3]) -> f64 {
         let (result, _) = simplex_3d(point.into(), &self.hasher);
 
@@ -67,11 +120,109 @@ impl NoiseFn<f64, 3> for Simplex {
     }
 }
 
+/// 3-dimensional Simplex noise with its analytic derivatives
+impl<NH> NoiseFnWithDerivative<f64, 3> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get_derivative(&self, point: [f64; 3]) -> [f64; 3] {
+        let (_, derivative) = simplex_3d(point.into(), &self.hasher);
+
+        derivative
+    }
+
+    fn get_with_derivative(&self, point: [f64; 3]) -> (f64, [f64; 3]) {
+        simplex_3d(point.into(), &self.hasher)
+    }
+}
+
 /// 4-dimensional Simplex noise
-impl NoiseFn<f64, 4> for Simplex {
+impl<NH> NoiseFn<f64, 4> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 4]) -> f64 {
         let (result, _) = simplex_4d(point.into(), &self.hasher);
 
         result
     }
 }
+
+/// 4-dimensional Simplex noise with its analytic derivatives
+impl<NH> NoiseFnWithDerivative<f64, 4> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get_derivative(&self, point: [f64; 4]) -> [f64; 4] {
+        let (_, derivative) = simplex_4d(point.into(), &self.hasher);
+
+        derivative
+    }
+
+    fn get_with_derivative(&self, point: [f64; 4]) -> (f64, [f64; 4]) {
+        simplex_4d(point.into(), &self.hasher)
+    }
+}
+
+/// 2-dimensional Simplex noise at extended precision coordinates
+#[cfg(feature = "twofloat")]
+impl<NH> NoiseFn<DoubleDouble, 2> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [DoubleDouble; 2]) -> f64 {
+        let (cell, offset) = simplex_lattice_split(point);
+        let hasher = OffsetHasher {
+            hasher: &self.hasher,
+            offset: cell,
+        };
+        let (result, _) = simplex_2d(offset.into(), &hasher);
+
+        result
+    }
+}
+
+/// 3-dimensional Simplex noise at extended precision coordinates
+#[cfg(feature = "twofloat")]
+impl<NH> NoiseFn<DoubleDouble, 3> for Simplex<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [DoubleDouble; 3]) -> f64 {
+        let (cell, offset) = simplex_lattice_split(point);
+        let hasher = OffsetHasher {
+            hasher: &self.hasher,
+            offset: cell,
+        };
+        let (result, _) = simplex_3d(offset.into(), &hasher);
+
+        result
+    }
+}
+
+#[cfg(all(test, feature = "twofloat"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_precision_keeps_detail_far_from_origin() {
+        let simplex = Simplex::new(3);
+
+        // Offset the points by the unskewed position of a skewed lattice cell
+        // about 1e12 from the origin. Its coordinates are multiples of 256, the
+        // period of the permutation table, so the noise there repeats the noise
+        // around the origin.
+        let cell = 256.0 * 3_906_250_000.0;
+        let unskew = DoubleDouble::from(cell * 2.0) * ((1.0 - 1.0 / 3f64.sqrt()) / 2.0);
+        let origin = DoubleDouble::from(cell) - unskew;
+
+        for i in 0..64 {
+            let x = i as f64 * 0.000_37 + 0.2;
+            let y = i as f64 * 0.011 - 0.4;
+
+            let near = simplex.get([x, y]);
+            let extended = simplex.get([origin + x, origin + y]);
+            assert!((extended - near).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{core::spheres::RadialProfile, noise_fns::NoiseFn};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that outputs concentric spheres.
+///
+/// This noise function outputs concentric spherical shells centered on the
+/// origin, or on the point set with [`set_center`](Self::set_center), similar
+/// to the layers of an onion. The radius is measured over every input
+/// coordinate, so 4-dimensional inputs produce hyperspherical shells, and
+/// 2-dimensional inputs a slice through the spheres at _z_ = 0.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spheres {
+    /// Frequency of the concentric objects.
+    pub frequency: f64,
+
+    /// Shape of the value along the radius of the spheres.
+    pub profile: RadialProfile,
+
+    /// Center of the spheres, in _x_, _y_, _z_, _u_ order. Inputs with fewer
+    /// coordinates are treated as having 0.0 in the missing ones.
+    pub center: [f64; 4],
+
+    /// Offset added to the radial distance after scaling by the frequency, in
+    /// units of one shell.
+    pub phase: f64,
+}
+
+impl Spheres {
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_PROFILE: RadialProfile = RadialProfile::Triangle;
+    pub const DEFAULT_CENTER: [f64; 4] = [0.0; 4];
+    pub const DEFAULT_PHASE: f64 = 0.0;
+
+    pub fn new() -> Self {
+        Self {
+            frequency: Self::DEFAULT_FREQUENCY,
+            profile: Self::DEFAULT_PROFILE,
+            center: Self::DEFAULT_CENTER,
+            phase: Self::DEFAULT_PHASE,
+        }
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    /// Sets the radial profile, choosing between sharp shells, smooth shells,
+    /// or a single falloff from the center.
+    pub fn set_profile(self, profile: RadialProfile) -> Self {
+        Self { profile, ..self }
+    }
+
+    /// Sets the first `N` coordinates of the center, in _x_, _y_, _z_, _u_
+    /// order, leaving the others unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is greater than 4.
+    pub fn set_center<const N: usize>(mut self, center: [f64; N]) -> Self {
+        assert!(N <= 4, "Spheres supports at most 4 axes, got {}", N);
+
+        self.center[..N].copy_from_slice(&center);
+        self
+    }
+
+    /// Sets the phase, which shifts every shell outwards by that fraction of
+    /// the spacing between shells.
+    pub fn set_phase(self, phase: f64) -> Self {
+        Self { phase, ..self }
+    }
+
+    fn value(&self, point: &[f64]) -> f64 {
+        let distance_squared: f64 = self
+            .center
+            .iter()
+            .enumerate()
+            .map(|(axis, center)| {
+                let offset = point.get(axis).copied().unwrap_or(0.0) - center;
+                offset * offset
+            })
+            .sum();
+
+        self.profile
+            .map(distance_squared.sqrt() * self.frequency + self.phase)
+    }
+}
+
+impl Default for Spheres {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseFn<f64, 2> for Spheres {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.value(&point)
+    }
+}
+
+impl NoiseFn<f64, 3> for Spheres {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.value(&point)
+    }
+}
+
+impl NoiseFn<f64, 4> for Spheres {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.value(&point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{core::spheres::spheres_3d, math::vectors::Vector3};
+
+    #[test]
+    fn matches_the_core_function_at_the_origin() {
+        let spheres = Spheres::new().set_frequency(2.0);
+
+        for i in 0..32 {
+            let point = [
+                i as f64 * 0.37 - 5.0,
+                i as f64 * -0.21 + 1.3,
+                i as f64 * 0.11,
+            ];
+            let expected = spheres_3d(Vector3::from(point), 2.0);
+            assert!((spheres.get(point) - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn shells_are_centered_on_the_center() {
+        let spheres = Spheres::new().set_center([1.0, 2.0, 3.0]);
+
+        assert_eq!(spheres.get([1.0, 2.0, 3.0]), 1.0);
+        assert!((spheres.get([1.0, 2.5, 3.0]) + 1.0).abs() < 1e-12);
+        assert!((spheres.get([1.0, 2.0, 2.0]) - 1.0).abs() < 1e-12);
+        assert!((spheres.get([1.0, 2.0, 3.0, 0.5]) + 1.0).abs() < 1e-12);
+    }
+}
@@ -1,13 +1,13 @@
 use crate::{
     core::super_simplex::*,
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NoiseFn, Seedable, Seedable64},
     permutationtable::PermutationTable,
 };
 
 /// Noise function that outputs 2/3-dimensional Super Simplex noise.
 #[derive(Clone, Copy, Debug)]
 pub struct SuperSimplex {
-    seed: u32,
+    seed: u64,
     perm_table: PermutationTable,
 }
 
@@ -16,7 +16,7 @@ impl SuperSimplex {
 
     pub fn new(seed: u32) -> Self {
         Self {
-            seed,
+            seed: seed.into(),
             perm_table: PermutationTable::new(seed),
         }
     }
@@ -31,6 +31,16 @@ impl Default for SuperSimplex {
 impl Seedable for SuperSimplex {
     /// Sets the seed value for Super Simplex noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for SuperSimplex {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -39,11 +49,11 @@ impl Seedable for SuperSimplex {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: PermutationTable::new_u64(seed),
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
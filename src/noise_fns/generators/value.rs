@@ -1,25 +1,67 @@
 use crate::{
-    core::value::{value_2d, value_3d, value_4d},
-    noise_fns::{NoiseFn, Seedable},
+    core::value::{
+        value_1d_with_interpolation, value_2d_with_interpolation, value_3d_with_interpolation,
+        value_4d_with_interpolation, Interpolation,
+    },
+    noise_fns::{NoiseFn, Seedable, Seedable64},
     permutationtable::PermutationTable,
 };
 
-/// Noise function that outputs 2/3/4-dimensional Value noise.
+/// Noise function that outputs 1/2/3/4-dimensional Value noise.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "ValueConfig")
+)]
 pub struct Value {
-    seed: u32,
+    /// Curve used to blend between the lattice values.
+    pub interpolation: Interpolation,
+
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
     perm_table: PermutationTable,
 }
 
+/// Serialized form of [`Value`]. The permutation table is rebuilt from the
+/// seed rather than stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ValueConfig {
+    seed: u64,
+    #[serde(default)]
+    interpolation: Interpolation,
+}
+
+#[cfg(feature = "serde")]
+impl From<ValueConfig> for Value {
+    fn from(config: ValueConfig) -> Self {
+        Self::default()
+            .set_seed_u64(config.seed)
+            .set_interpolation(config.interpolation)
+    }
+}
+
 impl Value {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_INTERPOLATION: Interpolation = Interpolation::Quintic;
 
     pub fn new(seed: u32) -> Self {
         Self {
-            seed,
+            interpolation: Self::DEFAULT_INTERPOLATION,
+            seed: seed.into(),
             perm_table: PermutationTable::new(seed),
         }
     }
+
+    /// Sets the curve used to blend between the lattice values. `Linear` is
+    /// the cheapest but leaves visible creases along the lattice lines.
+    pub fn set_interpolation(self, interpolation: Interpolation) -> Self {
+        Self {
+            interpolation,
+            ..self
+        }
+    }
 }
 
 impl Default for Value {
@@ -31,6 +73,16 @@ impl Default for Value {
 impl Seedable for Value {
     /// Sets the seed value for Value noise
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for Value {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -39,32 +91,98 @@ impl Seedable for Value {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: PermutationTable::new_u64(seed),
+            ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
 
+/// 1-dimensional value noise
+impl NoiseFn<f64, 1> for Value {
+    fn get(&self, point: [f64; 1]) -> f64 {
+        value_1d_with_interpolation(point[0], &self.perm_table, self.interpolation)
+    }
+}
+
 /// 2-dimensional value noise
 impl NoiseFn<f64, 2> for Value {
     fn get(&self, point: [f64; 2]) -> f64 {
-        value_2d(point.into(), &self.perm_table)
+        value_2d_with_interpolation(point.into(), &self.perm_table, self.interpolation)
     }
 }
 
 /// 3-dimensional value noise
 impl NoiseFn<f64, 3> for Value {
     fn get(&self, point: [f64; 3]) -> f64 {
-        value_3d(point.into(), &self.perm_table)
+        value_3d_with_interpolation(point.into(), &self.perm_table, self.interpolation)
     }
 }
 
 /// 4-dimensional value noise
 impl NoiseFn<f64, 4> for Value {
     fn get(&self, point: [f64; 4]) -> f64 {
-        value_4d(point.into(), &self.perm_table)
+        value_4d_with_interpolation(point.into(), &self.perm_table, self.interpolation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_dimensional_noise_stays_in_range() {
+        let value = Value::new(5);
+
+        for i in -512..512 {
+            let x = i as f64 * 0.173;
+            let output = value.get([x]);
+
+            assert!((-1.0..=1.0).contains(&output));
+        }
+    }
+
+    #[test]
+    fn one_dimensional_noise_is_continuous_across_zero() {
+        let value = Value::new(5);
+
+        assert!((value.get([-1e-9]) - value.get([0.0])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn interpolation_only_changes_values_between_lattice_points() {
+        let quintic = Value::new(5);
+        let cubic = Value::new(5).set_interpolation(Interpolation::Cubic);
+        let linear = Value::new(5).set_interpolation(Interpolation::Linear);
+
+        for i in -8..8 {
+            let lattice = [i as f64, (i * 3) as f64];
+            assert_eq!(linear.get(lattice), quintic.get(lattice));
+            assert_eq!(cubic.get(lattice), quintic.get(lattice));
+        }
+
+        // Halfway between lattice points every curve gives both sides equal
+        // weight, so the curves only differ away from the midpoints.
+        assert_eq!(linear.get([0.5, 0.5]), quintic.get([0.5, 0.5]));
+
+        let point = [0.2, 0.7];
+        assert_ne!(linear.get(point), quintic.get(point));
+        assert_ne!(cubic.get(point), quintic.get(point));
+    }
+
+    #[test]
+    fn linear_interpolation_is_piecewise_linear() {
+        let linear = Value::new(5).set_interpolation(Interpolation::Linear);
+
+        let a = linear.get([0.0]);
+        let b = linear.get([1.0]);
+
+        for i in 0..=10 {
+            let t = i as f64 / 10.0;
+            assert!((linear.get([t]) - (a + (b - a) * t)).abs() < 1e-12);
+        }
     }
 }
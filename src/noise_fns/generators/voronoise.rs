@@ -0,0 +1,223 @@
+use crate::{
+    core::voronoise::{voronoise_2d, voronoise_3d},
+    math::vectors::{Vector2, Vector3},
+    noise_fns::{NoiseFn, Seedable, Seedable64},
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+
+/// Noise function that outputs voronoise, a blend between value noise and
+/// cellular noise.
+///
+/// Every cell holds a random value at a jittered point, and the output is an
+/// average of the values of the nearby cells weighted by the distance to their
+/// points. Two parameters span the whole family of patterns:
+///
+/// | `jitter` | `smoothness` | Result                 |
+/// |----------|--------------|------------------------|
+/// | 0.0      | 0.0          | Cell grid              |
+/// | 0.0      | 1.0          | Value noise            |
+/// | 1.0      | 0.0          | Voronoi cells          |
+/// | 1.0      | 1.0          | Smooth voronoise       |
+///
+/// See [`crate::core::voronoise`] for details.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Voronoise<NH = PermutationTable> {
+    /// Frequency of the cells, in cells per unit length.
+    pub frequency: f64,
+
+    /// How far the point of each cell can move from the cell corner, from 0.0
+    /// for a regular grid to 1.0 for points anywhere in the cell. This is the
+    /// `u` parameter of the original.
+    pub jitter: f64,
+
+    /// How many of the nearby points contribute to the output, from 0.0 for
+    /// only the nearest one to 1.0 for all of them with smoothly falling
+    /// weights. This is the `v` parameter of the original.
+    pub smoothness: f64,
+
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hasher: NH,
+}
+
+/// Serialized form of [`Voronoise`]. The permutation table is rebuilt from the
+/// seed rather than stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct VoronoiseConfig {
+    frequency: f64,
+    jitter: f64,
+    smoothness: f64,
+    seed: u64,
+}
+
+#[cfg(feature = "serde")]
+impl From<VoronoiseConfig> for Voronoise {
+    fn from(config: VoronoiseConfig) -> Self {
+        Self::default()
+            .set_seed_u64(config.seed)
+            .set_frequency(config.frequency)
+            .set_jitter(config.jitter)
+            .set_smoothness(config.smoothness)
+    }
+}
+
+/// Only the default hasher can be deserialized, since a custom hasher is not
+/// stored.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Voronoise {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        VoronoiseConfig::deserialize(deserializer).map(Self::from)
+    }
+}
+
+impl Voronoise {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_JITTER: f64 = 1.0;
+    pub const DEFAULT_SMOOTHNESS: f64 = 0.5;
+
+    pub fn new(seed: u32) -> Self {
+        Self {
+            seed: seed.into(),
+            ..Self::with_hasher(PermutationTable::new(seed))
+        }
+    }
+}
+
+impl<NH> Voronoise<NH>
+where
+    NH: NoiseHasher,
+{
+    /// Creates voronoise that places and colors the cells with `hasher`
+    /// instead of a seeded [`PermutationTable`]. See
+    /// [`Perlin::with_hasher`](crate::Perlin::with_hasher).
+    pub fn with_hasher(hasher: NH) -> Self {
+        Self {
+            frequency: Voronoise::DEFAULT_FREQUENCY,
+            jitter: Voronoise::DEFAULT_JITTER,
+            smoothness: Voronoise::DEFAULT_SMOOTHNESS,
+            seed: Voronoise::DEFAULT_SEED.into(),
+            hasher,
+        }
+    }
+
+    pub fn hasher(&self) -> &NH {
+        &self.hasher
+    }
+
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self { frequency, ..self }
+    }
+
+    pub fn set_jitter(self, jitter: f64) -> Self {
+        Self { jitter, ..self }
+    }
+
+    pub fn set_smoothness(self, smoothness: f64) -> Self {
+        Self { smoothness, ..self }
+    }
+}
+
+impl Default for Voronoise {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl Seedable for Voronoise {
+    fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for Voronoise {
+    fn set_seed_u64(self, seed: u64) -> Self {
+        // If the new seed is the same as the current seed, just return self.
+        if self.seed == seed {
+            return self;
+        }
+
+        // Otherwise, regenerate the permutation table based on the new seed.
+        Self {
+            seed,
+            hasher: PermutationTable::new_u64(seed),
+            ..self
+        }
+    }
+
+    fn seed_u64(&self) -> u64 {
+        self.seed
+    }
+}
+
+/// 2-dimensional voronoise
+impl<NH> NoiseFn<f64, 2> for Voronoise<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        voronoise_2d(
+            Vector2::from(point) * self.frequency,
+            &self.hasher,
+            self.jitter,
+            self.smoothness,
+        )
+    }
+}
+
+/// 3-dimensional voronoise
+impl<NH> NoiseFn<f64, 3> for Voronoise<NH>
+where
+    NH: NoiseHasher,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        voronoise_3d(
+            Vector3::from(point) * self.frequency,
+            &self.hasher,
+            self.jitter,
+            self.smoothness,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sharp_grid_is_constant_within_cells() {
+        let noise = Voronoise::new(5).set_jitter(0.0).set_smoothness(0.0);
+
+        // Each cell takes the value of its nearest corner, so the quarter of a
+        // cell around a corner is nearly flat.
+        for corner in 0..8 {
+            let corner = [corner as f64, (corner * 3) as f64];
+            let center = noise.get(corner);
+            for &(dx, dy) in &[(0.1, 0.1), (-0.2, 0.15), (0.05, -0.25)] {
+                let value = noise.get([corner[0] + dx, corner[1] + dy]);
+                assert!((value - center).abs() < 1e-6, "{} != {}", value, center);
+            }
+        }
+    }
+
+    #[test]
+    fn smooth_noise_is_continuous() {
+        let noise = Voronoise::new(5).set_smoothness(1.0);
+
+        for i in 0..256 {
+            let point = [i as f64 * 0.173, i as f64 * -0.071];
+            let value = noise.get(point);
+            assert!((-1.0..=1.0).contains(&value));
+            assert!((noise.get([point[0] + 1e-4, point[1]]) - value).abs() < 1e-2);
+        }
+    }
+}
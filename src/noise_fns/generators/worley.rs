@@ -1,16 +1,24 @@
 use crate::{
     core::worley::*,
     math::vectors::*,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    noise_fns::{NoiseFn, Seedable, Seedable64},
+    permutationtable::{NoiseHasher, PermutationTable, WrapHasher},
 };
 use alloc::rc::Rc;
 
 /// Noise function that outputs Worley noise.
+///
+/// With the `serde` feature enabled, the distance function is serialized as a
+/// tag naming one of the built-in [`distance_functions`]. Custom distance
+/// functions can't be serialized and are replaced with the default euclidean
+/// distance when deserialized, as is a function assigned directly to the
+/// `distance_function` field instead of through `set_distance_function`.
 #[derive(Clone)]
-pub struct Worley {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Worley<NH = PermutationTable> {
     /// Specifies the distance function to use when calculating the boundaries of
     /// the cell.
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub distance_function: Rc<DistanceFunction>,
 
     /// Signifies whether the distance from the borders of the cell should be returned, or the
@@ -20,33 +28,253 @@ pub struct Worley {
     /// Frequency of the seed points.
     pub frequency: f64,
 
-    seed: u32,
-    perm_table: PermutationTable,
+    /// Signifies whether the frequency and the distance are scaled so that the
+    /// feature density matches across dimensions. See
+    /// [`set_normalize_by_dimension`](Self::set_normalize_by_dimension).
+    pub normalize_by_dimension: bool,
+
+    /// Scale of the offset of each seed point from its cell corner. See
+    /// [`set_jitter`](Self::set_jitter).
+    pub jitter: f64,
+
+    /// Distance that seed points are relaxed apart to from the seed points of
+    /// neighboring cells. See [`set_min_spacing`](Self::set_min_spacing).
+    pub min_spacing: f64,
+
+    /// Number of cells after which the cells repeat on each axis, in _x_, _y_,
+    /// _z_, _u_ order. See [`set_wrap`](Self::set_wrap).
+    pub wrap: Option<[i64; 4]>,
+
+    seed: u64,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    hasher: NH,
+    #[cfg_attr(feature = "serde", serde(rename = "distance_function"))]
+    distance_function_kind: DistanceFunctionKind,
+    /// The distance function that `distance_function_kind` was taken from, so
+    /// that a function assigned directly to `distance_function` is recognized
+    /// as custom.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    kind_function: Rc<DistanceFunction>,
 }
 
 type DistanceFunction = dyn Fn(&[f64], &[f64]) -> f64;
 
+/// Tag identifying which of the built-in distance functions is in use, so that
+/// it can be serialized and called without going through the `dyn Fn`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+enum DistanceFunctionKind {
+    Euclidean,
+    EuclideanSquared,
+    Manhattan,
+    Chebyshev,
+    Quadratic,
+    Custom,
+}
+
+impl DistanceFunctionKind {
+    fn of<F: 'static>(_function: &F) -> Self {
+        use core::any::TypeId;
+
+        // Every function item has its own type, so the built-in functions can
+        // be recognized by their type alone.
+        fn type_id_of<G: 'static>(_function: &G) -> TypeId {
+            TypeId::of::<G>()
+        }
+
+        let id = TypeId::of::<F>();
+        if id == type_id_of(&distance_functions::euclidean) {
+            Self::Euclidean
+        } else if id == type_id_of(&distance_functions::euclidean_squared) {
+            Self::EuclideanSquared
+        } else if id == type_id_of(&distance_functions::manhattan) {
+            Self::Manhattan
+        } else if id == type_id_of(&distance_functions::chebyshev) {
+            Self::Chebyshev
+        } else if id == type_id_of(&distance_functions::quadratic) {
+            Self::Quadratic
+        } else {
+            Self::Custom
+        }
+    }
+}
+
+/// Evaluates `$body` with `$function` bound to the distance function of
+/// `$worley`. The built-in functions are bound as function items rather than
+/// through the `dyn Fn`, so that their calls in a loop can be inlined.
+macro_rules! with_distance_function {
+    ($worley:expr, |$function:ident| $body:expr) => {
+        match $worley.current_distance_function_kind() {
+            DistanceFunctionKind::Euclidean => {
+                let $function = distance_functions::euclidean;
+                $body
+            }
+            DistanceFunctionKind::EuclideanSquared => {
+                let $function = distance_functions::euclidean_squared;
+                $body
+            }
+            DistanceFunctionKind::Manhattan => {
+                let $function = distance_functions::manhattan;
+                $body
+            }
+            DistanceFunctionKind::Chebyshev => {
+                let $function = distance_functions::chebyshev;
+                $body
+            }
+            DistanceFunctionKind::Quadratic => {
+                let $function = distance_functions::quadratic;
+                $body
+            }
+            DistanceFunctionKind::Custom => {
+                let $function = &*$worley.distance_function;
+                $body
+            }
+        }
+    };
+}
+
+/// Serialized form of [`Worley`]. The permutation table is rebuilt from the
+/// seed rather than stored.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct WorleyConfig {
+    return_type: ReturnType,
+    frequency: f64,
+    #[serde(default)]
+    normalize_by_dimension: bool,
+    #[serde(default = "default_jitter")]
+    jitter: f64,
+    #[serde(default)]
+    min_spacing: f64,
+    #[serde(default)]
+    wrap: Option<[i64; 4]>,
+    seed: u64,
+    #[serde(default = "default_distance_function_kind")]
+    distance_function: DistanceFunctionKind,
+}
+
+#[cfg(feature = "serde")]
+fn default_jitter() -> f64 {
+    Worley::DEFAULT_JITTER
+}
+
+#[cfg(feature = "serde")]
+fn default_distance_function_kind() -> DistanceFunctionKind {
+    DistanceFunctionKind::Euclidean
+}
+
+#[cfg(feature = "serde")]
+impl From<WorleyConfig> for Worley {
+    fn from(config: WorleyConfig) -> Self {
+        let worley = Self::default()
+            .set_seed_u64(config.seed)
+            .set_return_type(config.return_type)
+            .set_frequency(config.frequency)
+            .set_normalize_by_dimension(config.normalize_by_dimension)
+            .set_jitter(config.jitter)
+            .set_min_spacing(config.min_spacing)
+            .set_wrap(config.wrap);
+
+        match config.distance_function {
+            DistanceFunctionKind::Euclidean | DistanceFunctionKind::Custom => worley,
+            DistanceFunctionKind::EuclideanSquared => {
+                worley.set_distance_function(distance_functions::euclidean_squared)
+            }
+            DistanceFunctionKind::Manhattan => {
+                worley.set_distance_function(distance_functions::manhattan)
+            }
+            DistanceFunctionKind::Chebyshev => {
+                worley.set_distance_function(distance_functions::chebyshev)
+            }
+            DistanceFunctionKind::Quadratic => {
+                worley.set_distance_function(distance_functions::quadratic)
+            }
+        }
+    }
+}
+
+/// Only the default hasher can be deserialized, since a custom hasher is not
+/// stored.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Worley {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        WorleyConfig::deserialize(deserializer).map(Self::from)
+    }
+}
+
 impl Worley {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_JITTER: f64 = 1.0;
+    pub const DEFAULT_MIN_SPACING: f64 = 0.0;
+
+    /// Mean distance to the nearest seed point in 3D, relative to 2D.
+    ///
+    /// Measured over uniformly distributed sample points using the euclidean
+    /// distance function, where the mean distance is about 0.410 in 2D, 0.497
+    /// in 3D and 0.569 in 4D, in units of the seed point spacing.
+    pub const DIMENSION_SCALE_3D: f64 = 1.214;
+
+    /// Mean distance to the nearest seed point in 4D, relative to 2D. See
+    /// [`DIMENSION_SCALE_3D`](Self::DIMENSION_SCALE_3D).
+    pub const DIMENSION_SCALE_4D: f64 = 1.389;
 
     pub fn new(seed: u32) -> Self {
         Self {
-            perm_table: PermutationTable::new(seed),
-            seed,
-            distance_function: Rc::new(distance_functions::euclidean),
+            seed: seed.into(),
+            ..Self::with_hasher(PermutationTable::new(seed))
+        }
+    }
+}
+
+impl<NH> Worley<NH>
+where
+    NH: NoiseHasher,
+{
+    /// Creates Worley noise that hashes the cells with `hasher` instead of a
+    /// seeded [`PermutationTable`]. See
+    /// [`Perlin::with_hasher`](crate::Perlin::with_hasher).
+    pub fn with_hasher(hasher: NH) -> Self {
+        let distance_function: Rc<DistanceFunction> = Rc::new(distance_functions::euclidean);
+
+        Self {
+            hasher,
+            seed: Worley::DEFAULT_SEED.into(),
+            kind_function: distance_function.clone(),
+            distance_function,
             return_type: ReturnType::Value,
-            frequency: Self::DEFAULT_FREQUENCY,
+            frequency: Worley::DEFAULT_FREQUENCY,
+            normalize_by_dimension: false,
+            jitter: Worley::DEFAULT_JITTER,
+            min_spacing: Worley::DEFAULT_MIN_SPACING,
+            wrap: None,
+            distance_function_kind: DistanceFunctionKind::Euclidean,
         }
     }
 
+    pub fn hasher(&self) -> &NH {
+        &self.hasher
+    }
+
     /// Sets the distance function used by the Worley cells.
     pub fn set_distance_function<F>(self, function: F) -> Self
     where
         F: Fn(&[f64], &[f64]) -> f64 + 'static,
     {
+        let distance_function_kind = DistanceFunctionKind::of(&function);
+        let distance_function: Rc<DistanceFunction> = Rc::new(function);
+
         Self {
-            distance_function: Rc::new(function),
+            distance_function_kind,
+            kind_function: distance_function.clone(),
+            distance_function,
             ..self
         }
     }
@@ -64,6 +292,188 @@ impl Worley {
     pub fn set_frequency(self, frequency: f64) -> Self {
         Self { frequency, ..self }
     }
+
+    /// Enables or disables normalizing the feature density across dimensions.
+    ///
+    /// Seed points are further apart on average in higher dimensions, so the
+    /// same settings produce larger cells and larger distances in 3D and 4D
+    /// than in 2D. When enabled, the frequency is multiplied by
+    /// [`DIMENSION_SCALE_3D`](Worley::DIMENSION_SCALE_3D) or
+    /// [`DIMENSION_SCALE_4D`](Worley::DIMENSION_SCALE_4D), and the distance is
+    /// divided by the same factor, so the mean cell size and the mean distance
+    /// match those of 2D. The factors are measured for the euclidean distance
+    /// function.
+    pub fn set_normalize_by_dimension(self, normalize_by_dimension: bool) -> Self {
+        Self {
+            normalize_by_dimension,
+            ..self
+        }
+    }
+
+    /// Sets the scale of the offset of each seed point from its cell corner.
+    ///
+    /// A jitter of 0.0 places the seed points on a regular grid, and the
+    /// default of 1.0 spreads them up to half a cell from their corners. The
+    /// jitter is clamped to 0.0 to 1.0, since the nearest seed point search
+    /// assumes seed points stay within half a cell of their corners, and the
+    /// search for the second-nearest one grows with the jitter.
+    pub fn set_jitter(self, jitter: f64) -> Self {
+        Self {
+            jitter: jitter.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Sets the distance, in cells, that seed points are kept apart from the
+    /// seed points of neighboring cells.
+    ///
+    /// Seed points placed independently in each cell often end up close to
+    /// those of the neighboring cells, giving clumps of small cells. With a
+    /// positive spacing, each seed point is pushed away from the neighboring
+    /// seed points nearer than the spacing, which spreads the seed points out
+    /// more evenly, similar to a Poisson disk distribution. The relaxation is a
+    /// single deterministic pass, so the spacing is approximate rather than
+    /// guaranteed. The default of 0.0 leaves the seed points in place, and
+    /// values above 1.0 have no further effect.
+    pub fn set_min_spacing(self, min_spacing: f64) -> Self {
+        Self {
+            min_spacing: min_spacing.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Makes the cells repeat after the given number of cells on each axis, so
+    /// that the noise tiles seamlessly, or stops them repeating with `None`.
+    ///
+    /// The periods are counted in cells, which are `1 / frequency` units wide,
+    /// so the output tiles every `period / frequency` units. Points near the
+    /// edge of a tile see the wrapped seed points across the edge, so there is
+    /// no seam. Axes past `N`, or with a period of 0, are not wrapped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is more than 4 or a period is negative.
+    pub fn set_wrap<const N: usize>(self, wrap: Option<[i64; N]>) -> Self {
+        assert!(N <= 4, "Worley noise has at most 4 axes, got {}", N);
+
+        let wrap = wrap.map(|periods| {
+            assert!(
+                periods.iter().all(|&period| period >= 0),
+                "Worley wrap periods can't be negative, got {:?}",
+                periods
+            );
+
+            let mut wrap = [0; 4];
+            wrap[..N].copy_from_slice(&periods);
+            wrap
+        });
+
+        Self { wrap, ..self }
+    }
+
+    /// Returns the position of the seed point nearest to `point`, the point
+    /// whose distance and cell value are output by the `Distance` and `Value`
+    /// return types.
+    ///
+    /// The position is in the same coordinate space as `point`, accounting for
+    /// the frequency, so it can be used to snap objects to cell centers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `DIM` is not 2, 3 or 4.
+    pub fn get_cell_point<const DIM: usize>(&self, point: [f64; DIM]) -> [f64; DIM] {
+        let frequency = self.frequency * self.dimension_scale(DIM);
+        let hasher = &self.cell_hasher();
+        let distance_function = &*self.distance_function;
+
+        let cell_point: &[f64] = match DIM {
+            2 => &worley_2d_cell_point(
+                hasher,
+                distance_function,
+                Vector2::new(point[0], point[1]) * frequency,
+                self.jitter,
+                self.min_spacing,
+            )
+            .into_array(),
+            3 => &worley_3d_cell_point(
+                hasher,
+                distance_function,
+                Vector3::new(point[0], point[1], point[2]) * frequency,
+                self.jitter,
+                self.min_spacing,
+            )
+            .into_array(),
+            4 => &worley_4d_cell_point(
+                hasher,
+                distance_function,
+                Vector4::new(point[0], point[1], point[2], point[3]) * frequency,
+                self.jitter,
+                self.min_spacing,
+            )
+            .into_array(),
+            _ => panic!("Worley cell points are only available in 2, 3 and 4 dimensions"),
+        };
+
+        let mut result = [0.0; DIM];
+        for (x, cell_x) in result.iter_mut().zip(cell_point) {
+            *x = cell_x / frequency;
+        }
+
+        result
+    }
+
+    fn cell_hasher(&self) -> WrapHasher<'_, NH> {
+        WrapHasher {
+            hasher: &self.hasher,
+            period: self.wrap,
+        }
+    }
+
+    /// Returns the kind of the distance function in use, which is custom if a
+    /// function was assigned directly to the `distance_function` field.
+    fn current_distance_function_kind(&self) -> DistanceFunctionKind {
+        if Rc::ptr_eq(&self.distance_function, &self.kind_function) {
+            self.distance_function_kind
+        } else {
+            DistanceFunctionKind::Custom
+        }
+    }
+
+    fn dimension_scale(&self, dim: usize) -> f64 {
+        match dim {
+            3 if self.normalize_by_dimension => Worley::DIMENSION_SCALE_3D,
+            4 if self.normalize_by_dimension => Worley::DIMENSION_SCALE_4D,
+            _ => 1.0,
+        }
+    }
+
+    /// Divides a distance output by the dimension scale, keeping it in the
+    /// same range as the unscaled output.
+    fn rescale_distance(&self, value: f64, scale: f64) -> f64 {
+        if self.rescales_distance(scale) {
+            (value + 1.0) / scale - 1.0
+        } else {
+            value
+        }
+    }
+
+    /// Applies [`rescale_distance`](Self::rescale_distance) to each of
+    /// `values`, checking the return type only once.
+    fn rescale_distances(&self, values: &mut [f64], scale: f64) {
+        if self.rescales_distance(scale) {
+            for value in values {
+                *value = (*value + 1.0) / scale - 1.0;
+            }
+        }
+    }
+
+    fn rescales_distance(&self, scale: f64) -> bool {
+        scale != 1.0
+            && matches!(
+                self.return_type,
+                ReturnType::Distance | ReturnType::Distance2 | ReturnType::Distance2Sub
+            )
+    }
 }
 
 impl Default for Worley {
@@ -75,6 +485,16 @@ impl Default for Worley {
 impl Seedable for Worley {
     /// Sets the seed value used by the Worley cells.
     fn set_seed(self, seed: u32) -> Self {
+        self.set_seed_u64(seed.into())
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed as u32
+    }
+}
+
+impl Seedable64 for Worley {
+    fn set_seed_u64(self, seed: u64) -> Self {
         // If the new seed is the same as the current seed, just return self.
         if self.seed == seed {
             return self;
@@ -82,47 +502,351 @@ impl Seedable for Worley {
 
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
-            perm_table: PermutationTable::new(seed),
             seed,
+            hasher: PermutationTable::new_u64(seed),
             ..self
         }
     }
 
-    fn seed(&self) -> u32 {
+    fn seed_u64(&self) -> u64 {
         self.seed
     }
 }
 
-impl NoiseFn<f64, 2> for Worley {
+impl<NH> NoiseFn<f64, 2> for Worley<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 2]) -> f64 {
-        worley_2d(
-            &self.perm_table,
+        worley_2d_with_spacing(
+            &self.cell_hasher(),
             &*self.distance_function,
             self.return_type,
             Vector2::from(point) * self.frequency,
+            self.jitter,
+            self.min_spacing,
         )
     }
+
+    fn get_many(&self, points: &[[f64; 2]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = self.cell_hasher();
+        with_distance_function!(self, |distance_function| {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = worley_2d_with_spacing(
+                    &hasher,
+                    distance_function,
+                    self.return_type,
+                    Vector2::from(*point) * self.frequency,
+                    self.jitter,
+                    self.min_spacing,
+                );
+            }
+        });
+    }
 }
 
-impl NoiseFn<f64, 3> for Worley {
+impl<NH> NoiseFn<f64, 3> for Worley<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 3]) -> f64 {
-        worley_3d(
-            &self.perm_table,
+        let scale = self.dimension_scale(3);
+        let value = worley_3d_with_spacing(
+            &self.cell_hasher(),
             &*self.distance_function,
             self.return_type,
-            Vector3::from(point) * self.frequency,
-        )
+            Vector3::from(point) * (self.frequency * scale),
+            self.jitter,
+            self.min_spacing,
+        );
+
+        self.rescale_distance(value, scale)
+    }
+
+    fn get_many(&self, points: &[[f64; 3]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = self.cell_hasher();
+        let scale = self.dimension_scale(3);
+        let frequency = self.frequency * scale;
+        with_distance_function!(self, |distance_function| {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = worley_3d_with_spacing(
+                    &hasher,
+                    distance_function,
+                    self.return_type,
+                    Vector3::from(*point) * frequency,
+                    self.jitter,
+                    self.min_spacing,
+                );
+            }
+        });
+
+        self.rescale_distances(out, scale);
     }
 }
 
 #[allow(clippy::cognitive_complexity)]
-impl NoiseFn<f64, 4> for Worley {
+impl<NH> NoiseFn<f64, 4> for Worley<NH>
+where
+    NH: NoiseHasher,
+{
     fn get(&self, point: [f64; 4]) -> f64 {
-        worley_4d(
-            &self.perm_table,
+        let scale = self.dimension_scale(4);
+        let value = worley_4d_with_spacing(
+            &self.cell_hasher(),
             &*self.distance_function,
             self.return_type,
-            Vector4::from(point) * self.frequency,
-        )
+            Vector4::from(point) * (self.frequency * scale),
+            self.jitter,
+            self.min_spacing,
+        );
+
+        self.rescale_distance(value, scale)
+    }
+
+    fn get_many(&self, points: &[[f64; 4]], out: &mut [f64]) {
+        assert_eq!(points.len(), out.len());
+
+        let hasher = self.cell_hasher();
+        let scale = self.dimension_scale(4);
+        let frequency = self.frequency * scale;
+        with_distance_function!(self, |distance_function| {
+            for (point, value) in points.iter().zip(out.iter_mut()) {
+                *value = worley_4d_with_spacing(
+                    &hasher,
+                    distance_function,
+                    self.return_type,
+                    Vector4::from(*point) * frequency,
+                    self.jitter,
+                    self.min_spacing,
+                );
+            }
+        });
+
+        self.rescale_distances(out, scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn mean_distance<const DIM: usize>(worley: &Worley) -> f64
+    where
+        Worley: NoiseFn<f64, DIM>,
+    {
+        // Deterministic, well-spread sample points from a xorshift generator.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 11) as f64 / (1u64 << 53) as f64 * 200.0 - 100.0
+        };
+
+        let samples = 20_000;
+        let mut sum = 0.0;
+        for _ in 0..samples {
+            let mut point = [0.0; DIM];
+            point.iter_mut().for_each(|x| *x = next());
+
+            // Convert the output back into the distance in input units.
+            sum += (worley.get(point) + 1.0) * 0.5 / worley.frequency;
+        }
+
+        sum / samples as f64
+    }
+
+    #[test]
+    fn normalized_mean_distance_matches_across_dimensions() {
+        let worley = Worley::new(1)
+            .set_return_type(ReturnType::Distance)
+            .set_frequency(2.0);
+        let normalized = worley.clone().set_normalize_by_dimension(true);
+
+        let plain_2d = mean_distance::<2>(&worley);
+        let plain_3d = mean_distance::<3>(&worley);
+        let normalized_2d = mean_distance::<2>(&normalized);
+        let normalized_3d = mean_distance::<3>(&normalized);
+        let normalized_4d = mean_distance::<4>(&normalized);
+
+        assert_eq!(plain_2d, normalized_2d);
+        assert!((plain_3d / plain_2d - Worley::DIMENSION_SCALE_3D).abs() < 0.03);
+        assert!((normalized_3d / normalized_2d - 1.0).abs() < 0.03);
+        assert!((normalized_4d / normalized_2d - 1.0).abs() < 0.03);
+    }
+
+    #[test]
+    fn zero_jitter_gives_regular_grid() {
+        let worley = Worley::new(1)
+            .set_return_type(ReturnType::Distance)
+            .set_jitter(0.0);
+
+        for i in -4..4 {
+            let x = i as f64;
+            let y = (i * 3) as f64;
+
+            assert_eq!(worley.get([x, y]), -1.0);
+            assert_eq!(worley.get([x + 0.25, y]), -0.5);
+            assert_eq!(worley.get([x, y, 0.5]), 0.0);
+        }
+
+        let edges = worley.set_return_type(ReturnType::Distance2Sub);
+        assert_eq!(edges.get([0.5, 0.25]), -1.0);
+    }
+
+    #[test]
+    fn jitter_is_clamped_to_unit_range() {
+        assert_eq!(Worley::new(1).set_jitter(4.0).jitter, 1.0);
+        assert_eq!(Worley::new(1).set_jitter(-0.5).jitter, 0.0);
+        assert_eq!(Worley::new(1).set_jitter(0.25).jitter, 0.25);
+    }
+
+    #[test]
+    fn default_jitter_is_unchanged() {
+        let worley = Worley::new(1).set_return_type(ReturnType::Distance);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.21 + 1.3];
+            let expected = worley_2d(
+                &PermutationTable::new(1),
+                distance_functions::euclidean,
+                ReturnType::Distance,
+                Vector2::from(point),
+            );
+
+            assert_eq!(worley.get(point), expected);
+        }
+    }
+
+    #[test]
+    fn cell_point_is_nearest_seed_point() {
+        let worley = Worley::new(3)
+            .set_return_type(ReturnType::Distance)
+            .set_frequency(2.5);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.21 + 1.3];
+            let cell_point = worley.get_cell_point(point);
+
+            // The output distance is measured in the scaled space.
+            let distance = distance_functions::euclidean(&point, &cell_point) * worley.frequency;
+            assert!((worley.get(point) - (distance * 2.0 - 1.0)).abs() < 1e-12);
+
+            // Every point snaps to a seed point which is its own nearest seed.
+            let snapped = worley.get_cell_point(cell_point);
+            assert!(distance_functions::euclidean(&snapped, &cell_point) < 1e-12);
+        }
+
+        let point = [0.4, -1.7, 2.2];
+        let cell_point = worley.get_cell_point(point);
+        let distance = distance_functions::euclidean(&point, &cell_point) * worley.frequency;
+        assert!((worley.get(point) - (distance * 2.0 - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wrapped_cells_tile() {
+        // Periods of 4 and 3 cells at a frequency of 2 tile every 2 and 1.5
+        // units.
+        let worley = Worley::new(9).set_frequency(2.0).set_wrap(Some([4, 3]));
+        let distance = worley.clone().set_return_type(ReturnType::Distance);
+
+        for i in 0..64 {
+            // Points on both sides of the tile edges at 0.
+            let point = [i as f64 * 0.0625 - 2.0, i as f64 * -0.047 + 1.5];
+            for offset in [[2.0, 0.0], [0.0, 1.5], [-4.0, 3.0]] {
+                let shifted = [point[0] + offset[0], point[1] + offset[1]];
+
+                assert_eq!(worley.get(point), worley.get(shifted));
+                assert!((distance.get(point) - distance.get(shifted)).abs() < 1e-9);
+            }
+        }
+
+        // Unlisted axes are left unwrapped.
+        let worley = worley.set_wrap(Some([4]));
+        assert_eq!(worley.wrap, Some([4, 0, 0, 0]));
+        assert_eq!(worley.get([0.3, 0.2, 0.1]), worley.get([2.3, 0.2, 0.1]));
+        assert_ne!(worley.get([0.3, 0.2, 0.1]), worley.get([0.3, 0.2, 2.1]));
+    }
+
+    #[test]
+    fn unsigned_value_is_unmapped_value() {
+        let signed = Worley::new(4).set_return_type(ReturnType::Value);
+        let unsigned = signed.clone().set_return_type(ReturnType::ValueUnsigned);
+
+        for i in 0..64 {
+            let x = i as f64 * 0.37 - 5.0;
+            let pairs = [
+                (signed.get([x, 1.3]), unsigned.get([x, 1.3])),
+                (signed.get([x, 1.3, -0.4]), unsigned.get([x, 1.3, -0.4])),
+                (
+                    signed.get([x, 1.3, -0.4, 2.2]),
+                    unsigned.get([x, 1.3, -0.4, 2.2]),
+                ),
+            ];
+
+            for (signed, unsigned) in pairs {
+                assert!((0.0..=1.0).contains(&unsigned));
+                assert!((unsigned * 2.0 - 1.0 - signed).abs() < 1e-12);
+            }
+        }
+    }
+
+    fn assert_get_many_matches_get<const DIM: usize>(worley: &Worley)
+    where
+        Worley: NoiseFn<f64, DIM>,
+    {
+        let points: Vec<[f64; DIM]> = (0..64)
+            .map(|i| {
+                let mut point = [0.0; DIM];
+                for (axis, x) in point.iter_mut().enumerate() {
+                    *x = i as f64 * (0.37 + axis as f64 * 0.11) - 5.0;
+                }
+                point
+            })
+            .collect();
+        let mut out = vec![0.0; points.len()];
+        worley.get_many(&points, &mut out);
+
+        for (point, value) in points.iter().zip(&out) {
+            assert_eq!(worley.get(*point), *value);
+        }
+    }
+
+    #[test]
+    fn get_many_matches_get() {
+        let mut worleys = Vec::new();
+        for return_type in [
+            ReturnType::Value,
+            ReturnType::Distance,
+            ReturnType::Distance2Sub,
+        ] {
+            let worley = Worley::new(2)
+                .set_return_type(return_type)
+                .set_normalize_by_dimension(true);
+
+            worleys.push(worley.clone());
+            worleys.push(
+                worley
+                    .clone()
+                    .set_distance_function(distance_functions::manhattan),
+            );
+            worleys.push(worley.set_distance_function(distance_functions::minkowski(3.0)));
+        }
+
+        // A function assigned to the field directly replaces the built-in one.
+        let mut assigned = Worley::new(2).set_return_type(ReturnType::Distance);
+        assigned.distance_function = Rc::new(distance_functions::chebyshev);
+        worleys.push(assigned);
+
+        for worley in &worleys {
+            assert_get_many_matches_get::<2>(worley);
+            assert_get_many_matches_get::<3>(worley);
+            assert_get_many_matches_get::<4>(worley);
+        }
     }
 }
@@ -1,9 +1,26 @@
-pub use self::{abs::*, clamp::*, curve::*, exponent::*, negate::*, scale_bias::*, terrace::*};
+pub use self::{
+    abs::*, add_constant::*, bias::*, clamp::*, curve::*, exponent::*, gain::*, mul_constant::*,
+    negate::*, quantize::*, scale_bias::*, smoothstep::*, terrace::*,
+};
 
 mod abs;
+mod add_constant;
+mod bias;
 mod clamp;
 mod curve;
 mod exponent;
+mod gain;
+mod mul_constant;
 mod negate;
+mod quantize;
 mod scale_bias;
+mod smoothstep;
 mod terrace;
+
+/// Checks a source range passed to `set_source_range` on the modifiers that
+/// rescale their input.
+fn source_range(min: f64, max: f64) -> (f64, f64) {
+    assert!(min != max, "source range must not be empty");
+
+    (min, max)
+}
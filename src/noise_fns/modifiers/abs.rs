@@ -3,6 +3,9 @@ use core::marker::PhantomData;
 
 /// Noise function that outputs the absolute value of the output value from the
 /// source function.
+///
+/// The output value is folded around the `center`, so the output is the
+/// distance of the source's output value from the center.
 #[derive(Clone)]
 pub struct Abs<T, Source, const DIM: usize>
 where
@@ -11,6 +14,9 @@ where
     /// Outputs a value.
     pub source: Source,
 
+    /// Value that the output value is folded around. Default is 0.0.
+    pub center: f64,
+
     phantom: PhantomData<T>,
 }
 
@@ -18,12 +24,21 @@ impl<T, Source, const DIM: usize> Abs<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
+    pub const DEFAULT_CENTER: f64 = 0.0;
+
     pub fn new(source: Source) -> Self {
         Self {
             source,
+            center: Self::DEFAULT_CENTER,
             phantom: PhantomData,
         }
     }
+
+    /// Sets the value that the output value is folded around, which lets
+    /// billow-like creases form at levels other than zero.
+    pub fn set_center(self, center: f64) -> Self {
+        Self { center, ..self }
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Abs<T, Source, DIM>
@@ -31,6 +46,27 @@ where
     Source: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        (self.source.get(point)).abs()
+        (self.source.get(point) - self.center).abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+
+    #[test]
+    fn folds_around_center() {
+        let source = Perlin::new(1);
+        let abs = Abs::new(source);
+        let centered = Abs::new(source).set_center(0.25);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291];
+            let value = source.get(point);
+
+            assert_eq!(abs.get(point), value.abs());
+            assert_eq!(centered.get(point), (value - 0.25).abs());
+        }
     }
 }
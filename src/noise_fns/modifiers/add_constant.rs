@@ -0,0 +1,66 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+
+/// Noise function that adds a constant to the output value from the source
+/// function.
+///
+/// This is equivalent to `Add::new(source, Constant::new(constant))`, but
+/// stores the constant directly instead of calling a second noise function.
+/// [`ScaleBias`](super::ScaleBias) applies both at once.
+#[derive(Clone)]
+pub struct AddConstant<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Constant added to the output value from the source function.
+    pub constant: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> AddConstant<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(source: Source, constant: f64) -> Self {
+        Self {
+            source,
+            constant,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_constant(self, constant: f64) -> Self {
+        Self { constant, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for AddConstant<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.source.get(point) + self.constant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Add, Constant, Perlin};
+
+    #[test]
+    fn matches_combiner_with_constant() {
+        let perlin = Perlin::new(1);
+        let modifier = AddConstant::new(perlin, 0.25);
+        let combiner = Add::new(perlin, Constant::new(0.25));
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21];
+            assert_eq!(modifier.get(point), combiner.get(point));
+        }
+    }
+}
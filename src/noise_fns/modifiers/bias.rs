@@ -0,0 +1,137 @@
+use crate::{
+    math::{from_unit, to_unit},
+    noise_fns::NoiseFn,
+};
+use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that applies Ken Perlin's bias function to the output value
+/// from the source function.
+///
+/// The output value is normalized from -1.0 to 1.0 onto 0.0 to 1.0, mapped
+/// with `bias(t, b) = t ^ (ln(b) / ln(0.5))` and rescaled back. The bias moves
+/// the midpoint of the output to `b`, so values below 0.5 darken the output and
+/// values above 0.5 lighten it, while both ends stay in place. Output values
+/// outside of -1.0 to 1.0 are clamped to the range. For sources with another
+/// range, set it with [`set_source_range`](Self::set_source_range).
+#[derive(Clone)]
+pub struct Bias<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Value that the midpoint of the normalized output is mapped to, between
+    /// 0.0 and 1.0. Default is 0.5, which leaves the output unchanged.
+    pub bias: f64,
+
+    source_range: (f64, f64),
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Bias<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_BIAS: f64 = 0.5;
+
+    pub const DEFAULT_SOURCE_RANGE: (f64, f64) = (-1.0, 1.0);
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            bias: Self::DEFAULT_BIAS,
+            source_range: Self::DEFAULT_SOURCE_RANGE,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_bias(self, bias: f64) -> Self {
+        Self { bias, ..self }
+    }
+
+    /// Sets the range that the output of the source function spans.
+    ///
+    /// The output is rescaled from this range onto 0.0 to 1.0 before the bias
+    /// is applied, so the range has to match the source. Many sources do not
+    /// span -1.0 to 1.0: [`Worley`](crate::Worley) distances are mostly
+    /// positive, and fractals such as [`Fbm`](crate::Fbm) with a
+    /// [`Worley`](crate::Worley) source inherit that. With the default range
+    /// their midpoint is moved to the wrong value and their output is clamped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` equals `max`.
+    pub fn set_source_range(self, min: f64, max: f64) -> Self {
+        Self {
+            source_range: super::source_range(min, max),
+            ..self
+        }
+    }
+
+    /// Returns the range that the output of the source function is assumed to
+    /// span. Default is -1.0 to 1.0.
+    pub fn source_range(&self) -> (f64, f64) {
+        self.source_range
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Bias<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let value = to_unit(self.source.get(point), self.source_range).clamp(0.0, 1.0);
+
+        from_unit(bias(value, self.bias), self.source_range)
+    }
+}
+
+/// Ken Perlin's bias function, for `t` and `b` between 0.0 and 1.0.
+pub(super) fn bias(t: f64, b: f64) -> f64 {
+    t.powf(b.ln() / 0.5f64.ln())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn biased(value: f64, bias: f64) -> f64 {
+        Bias::new(Constant::new(value))
+            .set_bias(bias)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn moves_midpoint_to_bias() {
+        for b in [0.2, 0.5, 0.8] {
+            assert!((biased(0.0, b) - (b * 2.0 - 1.0)).abs() < 1e-12);
+            assert_eq!(biased(-1.0, b), -1.0);
+            assert_eq!(biased(1.0, b), 1.0);
+        }
+
+        assert!((biased(0.3, 0.5) - 0.3).abs() < 1e-12);
+        assert!(biased(-0.5, 0.25) < -0.5);
+        assert!(biased(-0.5, 0.75) > -0.5);
+        assert_eq!(biased(-4.0, 0.75), -1.0);
+    }
+
+    #[test]
+    fn source_range_replaces_unit_range() {
+        let biased = |value: f64| {
+            Bias::new(Constant::new(value))
+                .set_bias(0.8)
+                .set_source_range(0.0, 2.0)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(biased(0.0), 0.0);
+        assert!((biased(1.0) - 1.6).abs() < 1e-12);
+        assert_eq!(biased(2.0), 2.0);
+        assert_eq!(biased(3.0), 2.0);
+    }
+}
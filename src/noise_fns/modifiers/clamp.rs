@@ -1,8 +1,15 @@
 use crate::noise_fns::NoiseFn;
 use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 /// Noise function that clamps the output value from the source function to a
 /// range of values.
+///
+/// By default the clamp is hard, which leaves flat plateaus with sharp creases
+/// at their edges. With [`set_smoothness`](Self::set_smoothness), values within
+/// `smoothness` of a bound are instead squashed with `tanh`, so the output
+/// bends smoothly towards the bound and only reaches it asymptotically.
 #[derive(Clone)]
 pub struct Clamp<T, Source, const DIM: usize>
 where
@@ -14,6 +21,10 @@ where
     /// Bound of the clamping range. Default is -1.0 to 1.0.
     pub bounds: (f64, f64),
 
+    /// Width of the soft region inside each bound. Default is 0.0, a hard
+    /// clamp.
+    pub smoothness: f64,
+
     phantom: PhantomData<T>,
 }
 
@@ -21,10 +32,13 @@ impl<T, Source, const DIM: usize> Clamp<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
+    pub const DEFAULT_SMOOTHNESS: f64 = 0.0;
+
     pub fn new(source: Source) -> Self {
         Self {
             source,
             bounds: (-1.0, 1.0),
+            smoothness: Self::DEFAULT_SMOOTHNESS,
             phantom: PhantomData,
         }
     }
@@ -49,6 +63,12 @@ where
             ..self
         }
     }
+
+    /// Sets the width of the soft region inside each bound. It is limited to
+    /// half of the range, and 0.0 or less gives a hard clamp.
+    pub fn set_smoothness(self, smoothness: f64) -> Self {
+        Self { smoothness, ..self }
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Clamp<T, Source, DIM>
@@ -57,7 +77,57 @@ where
 {
     fn get(&self, point: [T; DIM]) -> f64 {
         let value = self.source.get(point);
+        let (lower, upper) = self.bounds;
+
+        let knee = self.smoothness.min((upper - lower) * 0.5);
+        if knee <= 0.0 {
+            return value.clamp(lower, upper);
+        }
+
+        // Past the start of the soft region, follow a tanh curve that leaves
+        // it with a slope of 1 and levels off at the bound.
+        if value > upper - knee {
+            upper - knee + knee * ((value - (upper - knee)) / knee).tanh()
+        } else if value < lower + knee {
+            lower + knee + knee * ((value - (lower + knee)) / knee).tanh()
+        } else {
+            value
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn clamped(value: f64, smoothness: f64) -> f64 {
+        Clamp::new(Constant::new(value))
+            .set_bounds(-1.0, 1.0)
+            .set_smoothness(smoothness)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn soft_clamp_approaches_bounds() {
+        assert_eq!(clamped(1.5, 0.0), 1.0);
+        assert_eq!(clamped(-1.5, 0.0), -1.0);
+
+        // Values outside the soft regions pass through.
+        assert_eq!(clamped(0.4, 0.5), 0.4);
+        assert_eq!(clamped(-0.4, 0.5), -0.4);
+
+        let mut previous = clamped(0.5, 0.5);
+        for i in 1..=100 {
+            let value = clamped(0.5 + i as f64 * 0.05, 0.5);
+            assert!(value > previous && value < 1.0, "{}", value);
+            assert_eq!(clamped(-0.5 - i as f64 * 0.05, 0.5), -value);
+            previous = value;
+        }
+        assert!(previous > 0.999);
 
-        value.clamp(self.bounds.0, self.bounds.1)
+        // The slope stays continuous where the soft region starts.
+        let step = 1e-6;
+        assert!(((clamped(0.5 + step, 0.5) - 0.5) / step - 1.0).abs() < 1e-3);
     }
 }
@@ -12,10 +12,12 @@ use core::marker::PhantomData;
 ///
 /// To add control points to the curve, use the `add_control_point` method.
 ///
-/// Since the curve is a cubic spline, an application must have a minimum of
-/// four control points to the curve. If there is less than four control
-/// points, the get() method panics. Each control point can have any input
-/// and output value, although no two control points can have the same input.
+/// The curve is a cubic spline, which needs a minimum of four control points.
+/// With fewer points the curve falls back to straight lines between them: two
+/// or three points are joined linearly, a single point gives a constant
+/// output, and no points at all pass the source value through unchanged. Each
+/// control point can have any input and output value, although no two control
+/// points can have the same input.
 #[derive(Clone)]
 pub struct Curve<T, Source, const DIM: usize>
 where
@@ -75,6 +77,38 @@ where
 
         self
     }
+
+    /// Returns the number of control points on the curve. At least four are
+    /// needed for cubic interpolation.
+    pub fn control_point_count(&self) -> usize {
+        self.control_points.len()
+    }
+
+    /// Maps `source_value` through straight lines between the control points,
+    /// for curves with too few points for cubic interpolation.
+    fn linear(&self, source_value: f64) -> f64 {
+        let (first, last) = match (self.control_points.first(), self.control_points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return source_value,
+        };
+
+        let index_pos = self
+            .control_points
+            .iter()
+            .position(|x| x.input > source_value);
+
+        match index_pos {
+            Some(0) => first.output,
+            None => last.output,
+            Some(index) => {
+                let lower = &self.control_points[index - 1];
+                let upper = &self.control_points[index];
+                let alpha = (source_value - lower.input) / (upper.input - lower.input);
+
+                interpolate::linear(lower.output, upper.output, alpha)
+            }
+        }
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Curve<T, Source, DIM>
@@ -82,12 +116,15 @@ where
     Source: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        // confirm that there's at least 4 control points in the vector.
-        assert!(self.control_points.len() >= 4);
-
         // get output value from the source function
         let source_value = self.source.get(point);
 
+        // Cubic interpolation needs 4 control points, so fall back to linear
+        // interpolation between fewer.
+        if self.control_points.len() < 4 {
+            return self.linear(source_value);
+        }
+
         // Find the first element in the control point array that has a input
         // value larger than the output value from the source function
         let index_pos = self
@@ -136,3 +173,34 @@ where
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn few_control_points_interpolate_linearly() {
+        let curved = |curve: &Curve<f64, Constant, 2>| curve.get([0.0, 0.0]);
+
+        let empty = Curve::new(Constant::new(0.3));
+        assert_eq!(empty.control_point_count(), 0);
+        assert_eq!(curved(&empty), 0.3);
+
+        let single = Curve::new(Constant::new(0.3)).add_control_point(0.0, -0.5);
+        assert_eq!(curved(&single), -0.5);
+
+        let three = |value| {
+            Curve::new(Constant::new(value))
+                .add_control_point(1.0, 0.0)
+                .add_control_point(-1.0, 1.0)
+                .add_control_point(0.0, -1.0)
+        };
+        assert_eq!(three(0.0).control_point_count(), 3);
+        assert_eq!(curved(&three(-0.5)), 0.0);
+        assert_eq!(curved(&three(0.25)), -0.75);
+        assert_eq!(curved(&three(1.0)), 0.0);
+        assert_eq!(curved(&three(-2.0)), 1.0);
+        assert_eq!(curved(&three(2.0)), 0.0);
+    }
+}
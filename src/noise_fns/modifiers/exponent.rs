@@ -1,5 +1,10 @@
-use crate::{math::scale_shift, noise_fns::NoiseFn};
+use crate::{
+    math::{from_unit, to_unit},
+    noise_fns::NoiseFn,
+};
 use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 /// Noise function that maps the output value from the source function onto an
 /// exponential curve.
@@ -7,7 +12,13 @@ use core::marker::PhantomData;
 /// Because most noise functions will output values that range from -1.0 to 1.0,
 /// this noise function first normalizes the output value (the range becomes 0.0
 /// to 1.0), maps that value onto an exponential curve, then rescales that
-/// value back to the original range.
+/// value back to the original range. For sources with another range, set it
+/// with [`set_source_range`](Self::set_source_range).
+///
+/// With [`set_symmetric`](Self::set_symmetric), the exponent is instead applied
+/// to the magnitude of the output value and the sign is kept, so the curve is
+/// symmetric about zero. This sharpens peaks and troughs alike, as is often
+/// wanted for ridged terrain.
 #[derive(Clone)]
 pub struct Exponent<T, Source, const DIM: usize>
 where
@@ -20,6 +31,12 @@ where
     /// is 1.0.
     pub exponent: f64,
 
+    /// Applies the exponent to the magnitude of the output value rather than
+    /// to the normalized value. Default is false.
+    pub symmetric: bool,
+
+    source_range: (f64, f64),
+
     phantom: PhantomData<T>,
 }
 
@@ -27,10 +44,14 @@ impl<T, Source, const DIM: usize> Exponent<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
+    pub const DEFAULT_SOURCE_RANGE: (f64, f64) = (-1.0, 1.0);
+
     pub fn new(source: Source) -> Self {
         Self {
             source,
             exponent: 1.0,
+            symmetric: false,
+            source_range: Self::DEFAULT_SOURCE_RANGE,
             phantom: PhantomData,
         }
     }
@@ -38,6 +59,37 @@ where
     pub fn set_exponent(self, exponent: f64) -> Self {
         Self { exponent, ..self }
     }
+
+    /// Enables or disables computing `sign(v) * |v|^exponent` instead of
+    /// mapping the normalized value onto the curve.
+    pub fn set_symmetric(self, symmetric: bool) -> Self {
+        Self { symmetric, ..self }
+    }
+
+    /// Sets the range that the output of the source function spans.
+    ///
+    /// The exponent is applied after the output is rescaled from this range
+    /// onto 0.0 to 1.0, so the curve starts at `min`. A source that spans
+    /// another range, such as the mostly positive distances of
+    /// [`Worley`](crate::Worley) or a fractal built on them, needs that range
+    /// set here. Otherwise the curve is anchored at -1.0, and any values below
+    /// -1.0 are folded back up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` equals `max`.
+    pub fn set_source_range(self, min: f64, max: f64) -> Self {
+        Self {
+            source_range: super::source_range(min, max),
+            ..self
+        }
+    }
+
+    /// Returns the range that the output of the source function is assumed to
+    /// span. Default is -1.0 to 1.0.
+    pub fn source_range(&self) -> (f64, f64) {
+        self.source_range
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Exponent<T, Source, DIM>
@@ -46,9 +98,61 @@ where
 {
     fn get(&self, point: [T; DIM]) -> f64 {
         let mut value = self.source.get(point);
-        value = (value + 1.0) / 2.0;
+        if self.symmetric {
+            return value.abs().powf(self.exponent).copysign(value);
+        }
+
+        value = to_unit(value, self.source_range);
         value = value.abs();
         value = value.powf(self.exponent);
-        scale_shift(value, 2.0)
+        from_unit(value, self.source_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn exponent(value: f64, symmetric: bool) -> f64 {
+        Exponent::new(Constant::new(value))
+            .set_exponent(2.0)
+            .set_symmetric(symmetric)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn symmetric_keeps_sign() {
+        assert_eq!(exponent(0.5, true), 0.25);
+        assert_eq!(exponent(-0.5, true), -0.25);
+        assert_eq!(exponent(0.0, true), 0.0);
+        assert_eq!(exponent(-1.0, true), -1.0);
+
+        // The default normalizes first, so it is not symmetric about zero.
+        assert_eq!(exponent(0.0, false), -0.5);
+        assert_eq!(exponent(0.5, false), 0.125);
+        assert_eq!(exponent(-0.5, false), -0.875);
+    }
+
+    #[test]
+    fn source_range_replaces_unit_range() {
+        let exponent = |value: f64| {
+            Exponent::new(Constant::new(value))
+                .set_exponent(2.0)
+                .set_source_range(0.0, 2.0)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(exponent(0.0), 0.0);
+        assert_eq!(exponent(1.0), 0.5);
+        assert_eq!(exponent(2.0), 2.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "source range")]
+    fn source_range_must_not_be_empty() {
+        Exponent::new(Constant::new(0.0))
+            .set_source_range(1.0, 1.0)
+            .get([0.0, 0.0]);
     }
 }
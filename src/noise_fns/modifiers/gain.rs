@@ -0,0 +1,142 @@
+use crate::{
+    math::{from_unit, to_unit},
+    noise_fns::NoiseFn,
+};
+use core::marker::PhantomData;
+
+use super::bias::bias;
+
+/// Noise function that applies Ken Perlin's gain function to the output value
+/// from the source function.
+///
+/// The output value is normalized from -1.0 to 1.0 onto 0.0 to 1.0, mapped
+/// with `gain(t, g)` and rescaled back. The gain function applies
+/// [`Bias`](super::Bias) with a bias of `1 - g` to each half of the range,
+/// mirrored around the midpoint, so values above 0.5 push the output towards
+/// the ends for more contrast and values below 0.5 pull it towards the middle
+/// for less. The ends and the midpoint stay in place. Output values outside of
+/// -1.0 to 1.0 are clamped to the range. For sources with another range, set it
+/// with [`set_source_range`](Self::set_source_range).
+#[derive(Clone)]
+pub struct Gain<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Amount of contrast around the midpoint, between 0.0 and 1.0. Default is
+    /// 0.5, which leaves the output unchanged.
+    pub gain: f64,
+
+    source_range: (f64, f64),
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Gain<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_GAIN: f64 = 0.5;
+
+    pub const DEFAULT_SOURCE_RANGE: (f64, f64) = (-1.0, 1.0);
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            gain: Self::DEFAULT_GAIN,
+            source_range: Self::DEFAULT_SOURCE_RANGE,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_gain(self, gain: f64) -> Self {
+        Self { gain, ..self }
+    }
+
+    /// Sets the range that the output of the source function spans.
+    ///
+    /// The contrast is added around the midpoint of this range, and the output
+    /// is clamped to it. For a source with another range, such as the mostly
+    /// positive distances of [`Worley`](crate::Worley) or a fractal built on
+    /// them, the default of -1.0 to 1.0 puts the midpoint at the wrong value
+    /// and cuts off part of the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` equals `max`.
+    pub fn set_source_range(self, min: f64, max: f64) -> Self {
+        Self {
+            source_range: super::source_range(min, max),
+            ..self
+        }
+    }
+
+    /// Returns the range that the output of the source function is assumed to
+    /// span. Default is -1.0 to 1.0.
+    pub fn source_range(&self) -> (f64, f64) {
+        self.source_range
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Gain<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let t = to_unit(self.source.get(point), self.source_range).clamp(0.0, 1.0);
+
+        let value = if t < 0.5 {
+            bias(2.0 * t, 1.0 - self.gain) / 2.0
+        } else {
+            1.0 - bias(2.0 - 2.0 * t, 1.0 - self.gain) / 2.0
+        };
+
+        from_unit(value, self.source_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn gained(value: f64, gain: f64) -> f64 {
+        Gain::new(Constant::new(value))
+            .set_gain(gain)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn changes_contrast_around_midpoint() {
+        for g in [0.2, 0.5, 0.8] {
+            assert_eq!(gained(-1.0, g), -1.0);
+            assert_eq!(gained(0.0, g), 0.0);
+            assert_eq!(gained(1.0, g), 1.0);
+
+            // The curve is symmetric around the midpoint.
+            assert!((gained(0.4, g) + gained(-0.4, g)).abs() < 1e-12);
+        }
+
+        assert!((gained(0.4, 0.5) - 0.4).abs() < 1e-12);
+        assert!(gained(0.4, 0.8) > 0.4);
+        assert!(gained(0.4, 0.2) < 0.4);
+    }
+
+    #[test]
+    fn source_range_replaces_unit_range() {
+        let gained = |value: f64| {
+            Gain::new(Constant::new(value))
+                .set_gain(0.8)
+                .set_source_range(0.0, 2.0)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(gained(0.0), 0.0);
+        assert_eq!(gained(1.0), 1.0);
+        assert_eq!(gained(2.0), 2.0);
+        assert!((gained(0.5) - 0.2).abs() < 1e-12);
+        assert!((gained(1.5) - 1.8).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+
+/// Noise function that multiplies the output value from the source function
+/// with a constant.
+///
+/// This is equivalent to `Multiply::new(source, Constant::new(constant))`, but
+/// stores the constant directly instead of calling a second noise function.
+/// [`ScaleBias`](super::ScaleBias) applies both at once.
+#[derive(Clone)]
+pub struct MulConstant<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Constant that the output value from the source function is
+    /// multiplied with.
+    pub constant: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> MulConstant<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub fn new(source: Source, constant: f64) -> Self {
+        Self {
+            source,
+            constant,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_constant(self, constant: f64) -> Self {
+        Self { constant, ..self }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for MulConstant<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.source.get(point) * self.constant
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Multiply, Perlin};
+
+    #[test]
+    fn matches_combiner_with_constant() {
+        let perlin = Perlin::new(1);
+        let modifier = MulConstant::new(perlin, -1.5);
+        let combiner = Multiply::new(perlin, Constant::new(-1.5));
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21];
+            assert_eq!(modifier.get(point), combiner.get(point));
+        }
+    }
+}
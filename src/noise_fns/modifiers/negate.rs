@@ -2,6 +2,11 @@ use crate::noise_fns::NoiseFn;
 use core::marker::PhantomData;
 
 /// Noise function that negates the output value from the source function.
+///
+/// The output is reflected about a pivot value, as `2 * pivot - value`. The
+/// default pivot of 0.0 is plain negation; another pivot flips a range that is
+/// not centered on zero, such as the output of a
+/// [`ScaleBias`](super::ScaleBias), onto itself.
 #[derive(Clone)]
 pub struct Negate<T, Source, const DIM: usize>
 where
@@ -10,6 +15,9 @@ where
     /// Outputs a value.
     pub source: Source,
 
+    /// Value that the output is reflected about. Default is 0.0.
+    pub pivot: f64,
+
     phantom: PhantomData<T>,
 }
 
@@ -17,12 +25,19 @@ impl<T, Source, const DIM: usize> Negate<T, Source, DIM>
 where
     Source: NoiseFn<T, DIM>,
 {
+    pub const DEFAULT_PIVOT: f64 = 0.0;
+
     pub fn new(source: Source) -> Self {
         Negate {
             source,
+            pivot: Self::DEFAULT_PIVOT,
             phantom: PhantomData,
         }
     }
+
+    pub fn set_pivot(self, pivot: f64) -> Self {
+        Self { pivot, ..self }
+    }
 }
 
 impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Negate<T, Source, DIM>
@@ -30,6 +45,22 @@ where
     Source: NoiseFn<T, DIM>,
 {
     fn get(&self, point: [T; DIM]) -> f64 {
-        -self.source.get(point)
+        2.0 * self.pivot - self.source.get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn reflects_about_pivot() {
+        let negate = Negate::new(Constant::new(0.25));
+        assert_eq!(negate.get([0.0, 0.0]), -0.25);
+        assert_eq!(negate.set_pivot(0.5).get([0.0, 0.0]), 0.75);
+
+        let negate = Negate::new(Constant::new(-1.0)).set_pivot(-1.0);
+        assert_eq!(negate.get([0.0, 0.0]), -1.0);
     }
 }
@@ -0,0 +1,112 @@
+use crate::noise_fns::NoiseFn;
+use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that snaps the output value from the source function to the
+/// nearest of a number of evenly spaced levels.
+///
+/// The levels span -1.0 to 1.0, including both ends, which gives flat plateaus
+/// with hard steps between them, as in contour maps or toon shading. Unlike
+/// [`Terrace`](super::Terrace), there is no interpolation between the levels.
+/// With a single step every value collapses to 0.0, and with many steps the
+/// output approaches the source's output. Output values outside of -1.0 to 1.0
+/// snap to the nearest end.
+#[derive(Clone)]
+pub struct Quantize<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// Number of levels to snap to. Default is 8.
+    pub steps: u32,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Quantize<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_STEPS: u32 = 8;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            steps: Self::DEFAULT_STEPS,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the number of levels, which is raised to 1 if it is 0.
+    pub fn set_steps(self, steps: u32) -> Self {
+        Self {
+            steps: steps.max(1),
+            ..self
+        }
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Quantize<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        if self.steps <= 1 {
+            return 0.0;
+        }
+
+        let value = self.source.get(point).clamp(-1.0, 1.0);
+        let intervals = f64::from(self.steps - 1);
+
+        // Round the position among the levels, then map it back to [-1, 1].
+        let level = ((value + 1.0) * 0.5 * intervals + 0.5).floor();
+
+        level / intervals * 2.0 - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Fbm, Perlin};
+
+    fn quantized(value: f64, steps: u32) -> f64 {
+        Quantize::new(Constant::new(value))
+            .set_steps(steps)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn snaps_to_nearest_level() {
+        assert_eq!(quantized(0.9, 1), 0.0);
+        assert_eq!(quantized(-0.9, 0), 0.0);
+
+        assert_eq!(quantized(0.1, 2), 1.0);
+        assert_eq!(quantized(-0.1, 2), -1.0);
+
+        assert_eq!(quantized(0.3, 3), 0.0);
+        assert_eq!(quantized(0.6, 3), 1.0);
+        assert_eq!(quantized(-2.0, 3), -1.0);
+        assert_eq!(quantized(2.0, 3), 1.0);
+
+        assert_eq!(quantized(0.2, 5), 0.0);
+        assert_eq!(quantized(0.3, 5), 0.5);
+    }
+
+    #[test]
+    fn many_steps_approach_source() {
+        let fbm = Fbm::<Perlin>::new(2);
+        let quantize = Quantize::new(&fbm).set_steps(1 << 20);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.173, i as f64 * -0.291, 0.5];
+            let value = fbm.get(point);
+            let snapped = quantize.get(point);
+
+            assert!((snapped - value).abs() <= 1.0 / f64::from(1 << 20));
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::noise_fns::NoiseFn;
 use core::marker::PhantomData;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 /// Noise function that applies a scaling factor and a bias to the output value
 /// from the source function.
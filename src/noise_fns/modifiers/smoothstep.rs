@@ -0,0 +1,153 @@
+use crate::{
+    math::{
+        from_unit,
+        s_curve::{cubic::Cubic, quintic::Quintic},
+        to_unit,
+    },
+    noise_fns::NoiseFn,
+};
+use core::marker::PhantomData;
+
+/// S-curve applied by the [`Smoothstep`] noise function.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SmoothstepCurve {
+    /// The cubic smoothstep curve, 3t<sup>2</sup> - 2t<sup>3</sup>, which has a
+    /// first derivative of zero at its ends.
+    #[default]
+    Cubic,
+
+    /// The quintic smootherstep curve, 6t<sup>5</sup> - 15t<sup>4</sup> +
+    /// 10t<sup>3</sup>, which also has a second derivative of zero at its ends.
+    Quintic,
+}
+
+/// Noise function that maps the output value from the source function onto an
+/// S-curve.
+///
+/// The output value is rescaled from -1.0 to 1.0 onto 0.0 to 1.0, mapped onto
+/// the curve and rescaled back, which pushes values towards -1.0 and 1.0 while
+/// keeping both ends in place. Output values outside of -1.0 to 1.0 are clamped
+/// to the range. For sources with another range, set it with
+/// [`set_source_range`](Self::set_source_range). This gives the same result as
+/// a [`Curve`](super::Curve) with many control points along the S-curve.
+#[derive(Clone)]
+pub struct Smoothstep<T, Source, const DIM: usize>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    /// Outputs a value.
+    pub source: Source,
+
+    /// S-curve to map the output value onto. Default is
+    /// [`SmoothstepCurve::Cubic`].
+    pub curve: SmoothstepCurve,
+
+    source_range: (f64, f64),
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source, const DIM: usize> Smoothstep<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_SOURCE_RANGE: (f64, f64) = (-1.0, 1.0);
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            curve: SmoothstepCurve::default(),
+            source_range: Self::DEFAULT_SOURCE_RANGE,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn set_curve(self, curve: SmoothstepCurve) -> Self {
+        Self { curve, ..self }
+    }
+
+    /// Sets the range that the output of the source function spans.
+    ///
+    /// The S-curve flattens out at the ends of this range, and values beyond
+    /// them are clamped. Set it to the range of the source when that is not
+    /// -1.0 to 1.0, for example to 0.0 and the largest distance for
+    /// [`Worley`](crate::Worley), or to the range of a fractal. Otherwise the
+    /// flat ends fall in the wrong place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min` equals `max`.
+    pub fn set_source_range(self, min: f64, max: f64) -> Self {
+        Self {
+            source_range: super::source_range(min, max),
+            ..self
+        }
+    }
+
+    /// Returns the range that the output of the source function is assumed to
+    /// span. Default is -1.0 to 1.0.
+    pub fn source_range(&self) -> (f64, f64) {
+        self.source_range
+    }
+}
+
+impl<T, Source, const DIM: usize> NoiseFn<T, DIM> for Smoothstep<T, Source, DIM>
+where
+    Source: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        let t = to_unit(self.source.get(point), self.source_range);
+
+        let value = match self.curve {
+            SmoothstepCurve::Cubic => t.map_cubic(),
+            SmoothstepCurve::Quintic => t.map_quintic(),
+        };
+
+        from_unit(value, self.source_range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn smoothstep(value: f64, curve: SmoothstepCurve) -> f64 {
+        Smoothstep::new(Constant::new(value))
+            .set_curve(curve)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn maps_onto_s_curve() {
+        for curve in [SmoothstepCurve::Cubic, SmoothstepCurve::Quintic] {
+            assert_eq!(smoothstep(-1.0, curve), -1.0);
+            assert_eq!(smoothstep(0.0, curve), 0.0);
+            assert_eq!(smoothstep(1.0, curve), 1.0);
+
+            // Out of range values clamp instead of overshooting.
+            assert_eq!(smoothstep(-3.0, curve), -1.0);
+            assert_eq!(smoothstep(2.5, curve), 1.0);
+        }
+
+        // t = 0.75 maps to 0.84375 on the cubic curve and 0.896484375 on the
+        // quintic curve.
+        assert_eq!(smoothstep(0.5, SmoothstepCurve::Cubic), 0.6875);
+        assert_eq!(smoothstep(0.5, SmoothstepCurve::Quintic), 0.79296875);
+    }
+
+    #[test]
+    fn source_range_replaces_unit_range() {
+        let smoothstep = |value: f64| {
+            Smoothstep::new(Constant::new(value))
+                .set_source_range(0.0, 2.0)
+                .get([0.0, 0.0])
+        };
+
+        assert_eq!(smoothstep(0.0), 0.0);
+        assert_eq!(smoothstep(1.0), 1.0);
+        assert_eq!(smoothstep(2.0), 2.0);
+        assert_eq!(smoothstep(1.5), 1.6875);
+        assert_eq!(smoothstep(-1.0), 0.0);
+    }
+}
@@ -149,3 +149,33 @@ where
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
     index.clamp(min as isize, max as isize) as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    fn terraced(value: f64, invert: bool) -> f64 {
+        Terrace::new(Constant::new(value))
+            .add_control_point(1.0)
+            .add_control_point(0.0)
+            .invert_terraces(invert)
+            .get([0.0, 0.0])
+    }
+
+    #[test]
+    fn inverting_flips_segment_curvature() {
+        // Without inversion the curve is flat at the start of each segment and
+        // lies below the straight line; inverted, it is flat at the end and
+        // lies above it.
+        assert_eq!(terraced(0.25, false), 0.0625);
+        assert_eq!(terraced(0.25, true), 0.4375);
+        assert_eq!(terraced(0.75, true), 1.0 - terraced(0.25, false));
+
+        for &invert in &[false, true] {
+            assert_eq!(terraced(0.0, invert), 0.0);
+            assert_eq!(terraced(1.0, invert), 1.0);
+            assert_eq!(terraced(-3.0, invert), 0.0);
+        }
+    }
+}
@@ -0,0 +1,242 @@
+//! Arithmetic operators for building noise graphs.
+//!
+//! `a + b`, `a - b` and `a * b` build a [`Sum`], [`Difference`] and
+//! [`Product`], which output the same values as [`Add`], [`Subtract`] and
+//! [`Multiply`], so a graph such as `base + detail * Constant::new(0.5)`
+//! outputs the same as spelling out the combiners. Unlike the combiners, they
+//! don't name the dimension of the input points, which the operators can't
+//! know. Both operands must be noise functions of the same dimension for the
+//! result to implement [`NoiseFn`](super::NoiseFn).
+
+use crate::noise_fns::*;
+use core::ops;
+
+/// Noise function built by `source1 + source2`, which outputs the sum of the
+/// output values of the two source functions like [`Add`].
+#[derive(Clone, Copy, Debug)]
+pub struct Sum<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Sum<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.source1.get(point) + self.source2.get(point)
+    }
+}
+
+/// Noise function built by `source1 - source2`, which outputs the output value
+/// of `source1` minus the output value of `source2` like [`Subtract`].
+#[derive(Clone, Copy, Debug)]
+pub struct Difference<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Difference<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.source1.get(point) - self.source2.get(point)
+    }
+}
+
+/// Noise function built by `source1 * source2`, which outputs the product of
+/// the output values of the two source functions like [`Multiply`].
+#[derive(Clone, Copy, Debug)]
+pub struct Product<Source1, Source2> {
+    /// Outputs a value.
+    pub source1: Source1,
+
+    /// Outputs a value.
+    pub source2: Source2,
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for Product<Source1, Source2>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        self.source1.get(point) * self.source2.get(point)
+    }
+}
+
+macro_rules! impl_ops {
+    ($(impl[$($generics:tt)*] $ty:ty $(where [$($bounds:tt)*])?;)*) => {
+        $(
+            impl<Rhs, $($generics)*> ops::Add<Rhs> for $ty $(where $($bounds)*)? {
+                type Output = Sum<Self, Rhs>;
+
+                fn add(self, rhs: Rhs) -> Self::Output {
+                    Sum {
+                        source1: self,
+                        source2: rhs,
+                    }
+                }
+            }
+
+            impl<Rhs, $($generics)*> ops::Sub<Rhs> for $ty $(where $($bounds)*)? {
+                type Output = Difference<Self, Rhs>;
+
+                fn sub(self, rhs: Rhs) -> Self::Output {
+                    Difference {
+                        source1: self,
+                        source2: rhs,
+                    }
+                }
+            }
+
+            impl<Rhs, $($generics)*> ops::Mul<Rhs> for $ty $(where $($bounds)*)? {
+                type Output = Product<Self, Rhs>;
+
+                fn mul(self, rhs: Rhs) -> Self::Output {
+                    Product {
+                        source1: self,
+                        source2: rhs,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_ops! {
+    // Generators
+    impl[] Checkerboard;
+    impl[] Constant;
+    impl[] Cylinders;
+    impl[] FlowNoise;
+    impl[F] FnNoise<F>;
+    impl[] FractalWorley;
+    impl[NH] Gabor<NH>;
+    impl[] IslandMask;
+    impl[] OpenSimplex;
+    impl[NH] Perlin<NH>;
+    impl[] PerlinSurflet;
+    impl[NH] Simplex<NH>;
+    impl[] Spheres;
+    impl[] SuperSimplex;
+    impl[] Value;
+    impl[NH] Voronoise<NH>;
+    impl[NH] Worley<NH>;
+    impl[T] BasicMulti<T>;
+    impl[T] Billow<T>;
+    impl[T] ErodedMulti<T>;
+    impl[T] Fbm<T>;
+    impl[T] HeteroTerrain<T>;
+    impl[T] HybridMulti<T>;
+    impl[T] RidgedMulti<T>;
+
+    // Combiners
+    impl[T, Source1, Source2, const DIM: usize] Add<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Multiply<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Subtract<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Divide<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Max<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Min<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] Power<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[Source1, Source2] Difference<Source1, Source2>;
+    impl[Source1, Source2] Product<Source1, Source2>;
+    impl[Source1, Source2] Sum<Source1, Source2>;
+    impl[T, const DIM: usize] WeightedSum<T, DIM>;
+
+    // Modifiers
+    impl[T, Source, const DIM: usize] Abs<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] AddConstant<T, Source, DIM>
+        where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Bias<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Clamp<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Curve<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Exponent<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Gain<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] MulConstant<T, Source, DIM>
+        where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Negate<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Quantize<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] ScaleBias<T, Source, DIM>;
+    impl[T, Source, const DIM: usize] Smoothstep<T, Source, DIM>
+        where [Source: NoiseFn<T, DIM>];
+    impl[T, Source, const DIM: usize] Terrace<T, Source, DIM> where [Source: NoiseFn<T, DIM>];
+
+    // Selectors
+    impl[T, Source1, Source2, Control, const DIM: usize] Blend<T, Source1, Source2, Control, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>, Control: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, const DIM: usize] MorphNoise<T, Source1, Source2, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>];
+    impl[T, Source1, Source2, Control, const DIM: usize] Select<T, Source1, Source2, Control, DIM>
+        where [Source1: NoiseFn<T, DIM>, Source2: NoiseFn<T, DIM>, Control: NoiseFn<T, DIM>];
+
+    // Transformers
+    impl[Source, const DIM: usize] AffineTransform<Source, DIM>;
+    impl[Source, XDisplace, YDisplace, ZDisplace, UDisplace]
+        Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace>;
+    impl[Source] RotatePoint<Source>;
+    impl[Source] ScalePoint<Source>;
+    impl[Source, const DIM: usize] Seamless<Source, DIM>;
+    impl[Source] SphericalCoords<Source>;
+    impl[Source] TimeLoop<Source>;
+    impl[Source] TranslatePoint<Source>;
+    impl[Source, F] Turbulence<Source, F> where [F: Default + Seedable];
+    impl[Source, XWarp, YWarp, ZWarp] Warp<Source, XWarp, YWarp, ZWarp>;
+
+    // Others
+    impl[Source] Cache<Source>;
+    impl[Source] FiniteDifference<Source>;
+    impl[Source] Upsample<Source>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operators_build_the_same_graph_as_combiners() {
+        let base = Perlin::new(1);
+        let detail = Worley::new(2);
+
+        let sugar = base + detail.clone() * Constant::new(0.5) - Value::new(3);
+        let spelled = Subtract::new(
+            Add::new(base, Multiply::new(detail, Constant::new(0.5))),
+            Value::new(3),
+        );
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21, 1.5];
+            assert_eq!(sugar.get(point), spelled.get(point));
+        }
+    }
+
+    #[test]
+    fn operators_chain_through_modifiers() {
+        let ridges = Abs::new(Perlin::new(1)) * Constant::new(2.0) + Constant::new(-1.0);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21];
+            let expected = Perlin::new(1).get(point).abs() * 2.0 - 1.0;
+            assert_eq!(ridges.get(point), expected);
+        }
+    }
+}
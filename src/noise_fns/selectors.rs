@@ -1,4 +1,5 @@
-pub use self::{blend::*, select::*};
+pub use self::{blend::*, morph::*, select::*};
 
 mod blend;
+mod morph;
 mod select;
@@ -0,0 +1,93 @@
+use crate::{math::interpolate, noise_fns::NoiseFn};
+use core::marker::PhantomData;
+
+/// Noise function that outputs a weighted blend of the output values from two
+/// source functions given a fixed blend factor.
+///
+/// Unlike [`Blend`](crate::Blend), where the weight is supplied by a control
+/// function and varies across space, the weight here is a single scalar that
+/// applies everywhere. This is useful for morphing smoothly between two styles
+/// of noise over time.
+#[derive(Clone)]
+pub struct MorphNoise<T, Source1, Source2, const DIM: usize>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    /// Outputs the value used when `t` is 0.0.
+    pub source1: Source1,
+
+    /// Outputs the value used when `t` is 1.0.
+    pub source2: Source2,
+
+    /// Blend factor between the two source functions, usually in the range
+    /// [0, 1]. A value of 0.0 outputs only `source1` and a value of 1.0
+    /// outputs only `source2`.
+    pub t: f64,
+
+    phantom: PhantomData<T>,
+}
+
+impl<T, Source1, Source2, const DIM: usize> MorphNoise<T, Source1, Source2, DIM>
+where
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    pub const DEFAULT_T: f64 = 0.0;
+
+    pub fn new(source1: Source1, source2: Source2) -> Self {
+        Self {
+            source1,
+            source2,
+            t: Self::DEFAULT_T,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Sets the blend factor between the two source functions.
+    pub fn set_t(self, t: f64) -> Self {
+        Self { t, ..self }
+    }
+}
+
+impl<T, Source1, Source2, const DIM: usize> NoiseFn<T, DIM> for MorphNoise<T, Source1, Source2, DIM>
+where
+    T: Copy,
+    Source1: NoiseFn<T, DIM>,
+    Source2: NoiseFn<T, DIM>,
+{
+    fn get(&self, point: [T; DIM]) -> f64 {
+        // At either end only one source contributes, so skip evaluating the
+        // other one.
+        if self.t == 0.0 {
+            self.source1.get(point)
+        } else if self.t == 1.0 {
+            self.source2.get(point)
+        } else {
+            interpolate::linear(self.source1.get(point), self.source2.get(point), self.t)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Worley};
+
+    #[test]
+    fn endpoints_output_sources_exactly() {
+        let perlin = Perlin::new(1);
+        let worley = Worley::new(2);
+        let morph = MorphNoise::new(perlin, worley.clone());
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.37 - 5.0, i as f64 * 0.21 + 1.3];
+
+            assert_eq!(morph.get(point), perlin.get(point));
+            assert_eq!(morph.clone().set_t(1.0).get(point), worley.get(point));
+
+            let halfway = morph.clone().set_t(0.5).get(point);
+            assert!((halfway - (perlin.get(point) + worley.get(point)) * 0.5).abs() < 1e-12);
+        }
+    }
+}
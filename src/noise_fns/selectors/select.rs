@@ -29,6 +29,11 @@ where
     pub bounds: (f64, f64),
 
     /// Edge falloff value. Default is 0.0.
+    ///
+    /// Around each bound, the output blends between the two sources over a
+    /// band that extends `falloff` to either side of the bound. The falloff is
+    /// limited to half the width of the selection range, so the bands of the
+    /// two bounds never overlap.
     pub falloff: f64,
 
     phantom: PhantomData<T>,
@@ -51,6 +56,13 @@ where
         }
     }
 
+    /// Sets the bounds of the selection range.
+    ///
+    /// The output is taken from `source2` while the control value is within
+    /// `[lower_bound, upper_bound]` and from `source1` on either side, for
+    /// example to place a biome only within a window of temperatures. To
+    /// select `source2` above a single threshold instead, pass
+    /// [`f64::INFINITY`] as the upper bound.
     pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
         Select {
             bounds: (lower_bound, upper_bound),
@@ -58,6 +70,10 @@ where
         }
     }
 
+    /// Sets the falloff value at the edges of the selection range.
+    ///
+    /// A falloff of 0.0 switches between the sources abruptly at the bounds,
+    /// and a positive falloff smooths the transition with a cubic S-curve.
     pub fn set_falloff(self, falloff: f64) -> Self {
         Select { falloff, ..self }
     }
@@ -74,22 +90,23 @@ where
     fn get(&self, point: [T; DIM]) -> f64 {
         let control_value = self.control.get(point);
         let (lower, upper) = self.bounds;
+        let falloff = self.falloff.min((upper - lower) * 0.5);
 
-        if self.falloff > 0.0 {
+        if falloff > 0.0 {
             match () {
-                _ if control_value < (lower - self.falloff) => self.source1.get(point),
-                _ if control_value < (lower + self.falloff) => {
-                    let lower_curve = lower - self.falloff;
-                    let upper_curve = lower + self.falloff;
+                _ if control_value < (lower - falloff) => self.source1.get(point),
+                _ if control_value < (lower + falloff) => {
+                    let lower_curve = lower - falloff;
+                    let upper_curve = lower + falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
                     interpolate::linear(self.source1.get(point), self.source2.get(point), alpha)
                 }
-                _ if control_value < (upper - self.falloff) => self.source2.get(point),
-                _ if control_value < (upper + self.falloff) => {
-                    let lower_curve = upper - self.falloff;
-                    let upper_curve = upper + self.falloff;
+                _ if control_value < (upper - falloff) => self.source2.get(point),
+                _ if control_value < (upper + falloff) => {
+                    let lower_curve = upper - falloff;
+                    let upper_curve = upper + falloff;
                     let alpha =
                         ((control_value - lower_curve) / (upper_curve - lower_curve)).map_cubic();
 
@@ -104,3 +121,70 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, NoiseFn};
+
+    /// Control function that outputs the x coordinate, so the control value
+    /// can be swept across the bounds.
+    struct Ramp;
+
+    impl NoiseFn<f64, 2> for Ramp {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn falloff_is_continuous_across_bounds() {
+        let hard = Select::new(Constant::new(-1.0), Constant::new(1.0), Ramp).set_bounds(-0.5, 0.5);
+
+        assert_eq!(hard.get([-0.51, 0.0]), -1.0);
+        assert_eq!(hard.get([-0.49, 0.0]), 1.0);
+
+        // The falloff is limited to half the selection range, 0.5 here.
+        for falloff in [0.1, 0.4, 2.0] {
+            let select = Select::new(Constant::new(-1.0), Constant::new(1.0), Ramp)
+                .set_bounds(-0.5, 0.5)
+                .set_falloff(falloff);
+
+            let step = 0.0005;
+            let mut previous = select.get([-2.0, 0.0]);
+            for i in 1..8000 {
+                let value = select.get([-2.0 + i as f64 * step, 0.0]);
+                assert!(
+                    (value - previous).abs() < 0.01,
+                    "jump at falloff {}",
+                    falloff
+                );
+                previous = value;
+            }
+
+            assert_eq!(select.get([-0.5, 0.0]), 0.0);
+            assert_eq!(select.get([0.5, 0.0]), 0.0);
+        }
+    }
+
+    #[test]
+    fn bounds_select_a_band() {
+        let band = Select::new(Constant::new(-1.0), Constant::new(1.0), Ramp)
+            .set_bounds(0.2, 0.6)
+            .set_falloff(0.05);
+
+        assert_eq!(band.get([0.1, 0.0]), -1.0);
+        assert_eq!(band.get([0.4, 0.0]), 1.0);
+        assert_eq!(band.get([0.7, 0.0]), -1.0);
+        assert!(band.get([0.22, 0.0]) > 0.0 && band.get([0.58, 0.0]) > 0.0);
+        assert!(band.get([0.18, 0.0]) < 0.0 && band.get([0.62, 0.0]) < 0.0);
+
+        let threshold = Select::new(Constant::new(-1.0), Constant::new(1.0), Ramp)
+            .set_bounds(0.2, f64::INFINITY)
+            .set_falloff(0.05);
+
+        assert_eq!(threshold.get([0.1, 0.0]), -1.0);
+        assert_eq!(threshold.get([0.2, 0.0]), 0.0);
+        assert_eq!(threshold.get([1e9, 0.0]), 1.0);
+    }
+}
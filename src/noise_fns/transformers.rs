@@ -1,7 +1,15 @@
-pub use self::{displace::*, rotate_point::*, scale_point::*, translate_point::*, turbulence::*};
+pub use self::{
+    affine_transform::*, displace::*, rotate_point::*, scale_point::*, seamless::*,
+    spherical_coords::*, time_loop::*, translate_point::*, turbulence::*, warp::*,
+};
 
+mod affine_transform;
 mod displace;
 mod rotate_point;
 mod scale_point;
+mod seamless;
+mod spherical_coords;
+mod time_loop;
 mod translate_point;
 mod turbulence;
+mod warp;
@@ -0,0 +1,100 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that applies an affine transformation to the input value
+/// before returning the output value from the source function.
+///
+/// The input point `p` is transformed into `matrix * p + translation`, with
+/// `matrix[row][column]`, so row `i` of the matrix gives the weights of the
+/// input coordinates for output coordinate `i`. This covers scaling, rotation
+/// and translation as well as skewing, shearing and mirroring, and any
+/// combination of them.
+#[derive(Clone, Debug)]
+pub struct AffineTransform<Source, const DIM: usize> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Linear part of the transformation. The default is the identity matrix.
+    pub matrix: [[f64; DIM]; DIM],
+
+    /// Offset added after the linear part. The default is the origin.
+    pub translation: [f64; DIM],
+}
+
+impl<Source, const DIM: usize> AffineTransform<Source, DIM> {
+    pub fn new(source: Source) -> Self {
+        let mut matrix = [[0.0; DIM]; DIM];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+
+        Self {
+            source,
+            matrix,
+            translation: [0.0; DIM],
+        }
+    }
+
+    /// Sets the linear part of the transformation, indexed as
+    /// `matrix[row][column]`.
+    pub fn set_matrix(self, matrix: [[f64; DIM]; DIM]) -> Self {
+        Self { matrix, ..self }
+    }
+
+    /// Sets the offset added to the input value after the linear part.
+    pub fn set_translation(self, translation: [f64; DIM]) -> Self {
+        Self {
+            translation,
+            ..self
+        }
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for AffineTransform<Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut transformed = self.translation;
+        for (value, row) in transformed.iter_mut().zip(&self.matrix) {
+            for (weight, x) in row.iter().zip(&point) {
+                *value += weight * x;
+            }
+        }
+
+        self.source.get(transformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FnNoise, Perlin};
+
+    #[test]
+    fn shears_the_input_in_2d() {
+        let x = FnNoise(|point: [f64; 2]| point[0]);
+        let y = FnNoise(|point: [f64; 2]| point[1]);
+        let shear = [[1.0, 0.5], [0.0, 1.0]];
+
+        let sheared_x = AffineTransform::new(x)
+            .set_matrix(shear)
+            .set_translation([3.0, -1.0]);
+        let sheared_y = AffineTransform::new(y)
+            .set_matrix(shear)
+            .set_translation([3.0, -1.0]);
+
+        assert_eq!(sheared_x.get([2.0, 4.0]), 2.0 + 0.5 * 4.0 + 3.0);
+        assert_eq!(sheared_y.get([2.0, 4.0]), 4.0 - 1.0);
+    }
+
+    #[test]
+    fn identity_leaves_the_source_unchanged() {
+        let perlin = Perlin::new(1);
+        let identity = AffineTransform::new(perlin);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21, 1.5];
+            assert_eq!(identity.get(point), perlin.get(point));
+        }
+    }
+}
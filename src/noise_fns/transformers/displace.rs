@@ -2,6 +2,10 @@ use crate::noise_fns::NoiseFn;
 
 /// Noise function that uses multiple source functions to displace each coordinate
 /// of the input value before returning the output value from the `source` function.
+///
+/// The output of each displacement function is multiplied by the strength of
+/// its axis, set with [`set_strength`](Self::set_strength), before it is added
+/// to the coordinate.
 #[derive(Clone)]
 pub struct Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace> {
     /// Source function that outputs a value
@@ -22,6 +26,10 @@ pub struct Displace<Source, XDisplace, YDisplace, ZDisplace, UDisplace> {
     /// Displacement function that displaces the _u_ coordinate of the input
     /// value. Only needed for 4d or higher noise.
     pub u_displace: UDisplace,
+
+    /// Multipliers for the outputs of the displacement functions, in _x_, _y_,
+    /// _z_, _u_ order. Default is 1.0 for every axis.
+    pub strength: [f64; 4],
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
@@ -40,8 +48,17 @@ impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace>
             y_displace,
             z_displace,
             u_displace,
+            strength: [1.0; 4],
         }
     }
+
+    /// Sets the strengths of the first `N` axes, leaving the others unchanged.
+    pub fn set_strength<const N: usize>(mut self, strength: [f64; N]) -> Self {
+        assert!(N <= 4, "Displace supports at most 4 axes, got {}", N);
+
+        self.strength[..N].copy_from_slice(&strength);
+        self
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<f64, 2>
@@ -55,8 +72,8 @@ where
         // Get the output values from the displacement functions and add them to
         // the corresponding coordinate in the input value. Since this is a 2d
         // function, we only need the x_displace and y_displace functions.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
+        let x = point[0] + self.x_displace.get(point) * self.strength[0];
+        let y = point[1] + self.y_displace.get(point) * self.strength[1];
 
         // get the output value using the offset input value instead of the
         // original input value.
@@ -77,9 +94,9 @@ where
         // the corresponding coordinate in the input value. Since this is a 3d
         // function, we only need the x_displace, y_displace, and z_displace
         // functions. Also, panic if there is no z_displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
+        let x = point[0] + self.x_displace.get(point) * self.strength[0];
+        let y = point[1] + self.y_displace.get(point) * self.strength[1];
+        let z = point[2] + self.z_displace.get(point) * self.strength[2];
 
         // get the output value using the offset input value instead of the
         // original input value.
@@ -101,13 +118,36 @@ where
         // the corresponding coordinate in the input value. Since this is a 4d
         // function, we need all of the displace functions. Panic if there is no z-
         // or u-displace function defined.
-        let x = point[0] + self.x_displace.get(point);
-        let y = point[1] + self.y_displace.get(point);
-        let z = point[2] + self.z_displace.get(point);
-        let u = point[3] + self.u_displace.get(point);
+        let x = point[0] + self.x_displace.get(point) * self.strength[0];
+        let y = point[1] + self.y_displace.get(point) * self.strength[1];
+        let z = point[2] + self.z_displace.get(point) * self.strength[2];
+        let u = point[3] + self.u_displace.get(point) * self.strength[3];
 
         // get the output value using the offset input value instead of the
         // original input value.
         self.source.get([x, y, z, u])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, FnNoise};
+
+    #[test]
+    fn strength_scales_each_axis() {
+        let source = FnNoise(|[x, y]: [f64; 2]| x * 10.0 + y);
+        let displace = Displace::new(
+            source,
+            Constant::new(0.5),
+            Constant::new(0.25),
+            Constant::new(0.0),
+            Constant::new(0.0),
+        );
+        assert_eq!(displace.get([1.0, 2.0]), 17.25);
+
+        let displace = displace.set_strength([2.0, -4.0]);
+        assert_eq!(displace.strength, [2.0, -4.0, 1.0, 1.0]);
+        assert_eq!(displace.get([1.0, 2.0]), 21.0);
+    }
+}
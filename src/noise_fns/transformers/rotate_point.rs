@@ -1,4 +1,6 @@
 use crate::noise_fns::NoiseFn;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
 
 /// Noise function that rotates the input value around the origin before
 /// returning the output value from the source function.
@@ -8,6 +10,12 @@ use crate::noise_fns::NoiseFn;
 ///
 /// The coordinate system of the input value is assumed to be "right-handed"
 /// (_x_ increases to the right, _y_ increases upward, and _z_ increases inward).
+///
+/// Four-dimensional space has six planes of rotation. The _x_, _y_ and _z_
+/// angles rotate the _yz_, _xz_ and _xy_ planes as in three dimensions, and
+/// the _xu_, _yu_ and _zu_ angles then rotate each of the first three axes
+/// towards the fourth, _u_ axis. A 4-dimensional point with the _u_ angles at
+/// zero is rotated exactly like its first three coordinates in 3D.
 #[derive(Clone)]
 pub struct RotatePoint<Source> {
     /// Source function that outputs a value
@@ -25,12 +33,26 @@ pub struct RotatePoint<Source> {
     /// default angle is set to 0.0 degrees.
     pub z_angle: f64,
 
-    /// _u_ rotation angle applied to the input value, in degrees. The
-    /// default angle is set to 0.0 degrees.
+    /// _u_ rotation angle, in degrees. It isn't used by any of the rotations,
+    /// since 4D rotations are described by the _xu_, _yu_ and _zu_ angles.
+    #[deprecated(note = "4D rotations use the `xu_angle`, `yu_angle` and `zu_angle` planes")]
     pub u_angle: f64,
+
+    /// Rotation angle of the _xu_ plane applied to the input value, in
+    /// degrees. The default angle is set to 0.0 degrees.
+    pub xu_angle: f64,
+
+    /// Rotation angle of the _yu_ plane applied to the input value, in
+    /// degrees. The default angle is set to 0.0 degrees.
+    pub yu_angle: f64,
+
+    /// Rotation angle of the _zu_ plane applied to the input value, in
+    /// degrees. The default angle is set to 0.0 degrees.
+    pub zu_angle: f64,
 }
 
 impl<Source> RotatePoint<Source> {
+    #[allow(deprecated)]
     pub fn new(source: Source) -> Self {
         Self {
             source,
@@ -38,6 +60,9 @@ impl<Source> RotatePoint<Source> {
             y_angle: 0.0,
             z_angle: 0.0,
             u_angle: 0.0,
+            xu_angle: 0.0,
+            yu_angle: 0.0,
+            zu_angle: 0.0,
         }
     }
 
@@ -59,14 +84,36 @@ impl<Source> RotatePoint<Source> {
         Self { z_angle, ..self }
     }
 
-    /// Sets the rotation angle around the _u_ axis to apply to the input
-    /// value.
+    /// Sets the _u_ rotation angle, which has no effect.
+    #[deprecated(note = "use `set_u_plane_angles` to rotate 4-dimensional input values")]
+    #[allow(deprecated)]
     pub fn set_u_angle(self, u_angle: f64) -> Self {
         Self { u_angle, ..self }
     }
 
+    /// Sets the rotation angle of the _xu_ plane to apply to 4-dimensional
+    /// input values.
+    pub fn set_xu_angle(self, xu_angle: f64) -> Self {
+        Self { xu_angle, ..self }
+    }
+
+    /// Sets the rotation angle of the _yu_ plane to apply to 4-dimensional
+    /// input values.
+    pub fn set_yu_angle(self, yu_angle: f64) -> Self {
+        Self { yu_angle, ..self }
+    }
+
+    /// Sets the rotation angle of the _zu_ plane to apply to 4-dimensional
+    /// input values.
+    pub fn set_zu_angle(self, zu_angle: f64) -> Self {
+        Self { zu_angle, ..self }
+    }
+
     /// Sets the rotation angles around all of the axes to apply to the input
-    /// value.
+    /// value. The _u_ angle has no effect, see
+    /// [`set_u_plane_angles`](Self::set_u_plane_angles) for rotating
+    /// 4-dimensional input values.
+    #[allow(deprecated)]
     pub fn set_angles(self, x_angle: f64, y_angle: f64, z_angle: f64, u_angle: f64) -> Self {
         Self {
             x_angle,
@@ -76,6 +123,55 @@ impl<Source> RotatePoint<Source> {
             ..self
         }
     }
+
+    /// Sets the rotation angles of the _xu_, _yu_ and _zu_ planes to apply to
+    /// 4-dimensional input values.
+    pub fn set_u_plane_angles(self, xu_angle: f64, yu_angle: f64, zu_angle: f64) -> Self {
+        Self {
+            xu_angle,
+            yu_angle,
+            zu_angle,
+            ..self
+        }
+    }
+
+    /// Rotates the first three coordinates of `point` by the _x_, _y_ and _z_
+    /// angles.
+    fn rotate_3d(&self, point: [f64; 3]) -> [f64; 3] {
+        let x_cos = self.x_angle.to_radians().cos();
+        let y_cos = self.y_angle.to_radians().cos();
+        let z_cos = self.z_angle.to_radians().cos();
+        let x_sin = self.x_angle.to_radians().sin();
+        let y_sin = self.y_angle.to_radians().sin();
+        let z_sin = self.z_angle.to_radians().sin();
+
+        let x1 = x_sin * y_sin * z_sin + y_cos * z_cos;
+        let y1 = x_cos * z_sin;
+        let z1 = y_sin * z_cos - y_cos * x_sin * z_sin;
+        let x2 = y_sin * x_sin * z_cos - y_cos * z_sin;
+        let y2 = x_cos * z_cos;
+        let z2 = -y_cos * x_sin * z_cos - y_sin * z_sin;
+        let x3 = -y_sin * x_cos;
+        let y3 = x_sin;
+        let z3 = y_cos * x_cos;
+
+        let x = (x1 * point[0]) + (y1 * point[1]) + (z1 * point[2]);
+        let y = (x2 * point[0]) + (y2 * point[1]) + (z2 * point[2]);
+        let z = (x3 * point[0]) + (y3 * point[1]) + (z3 * point[2]);
+
+        [x, y, z]
+    }
+}
+
+/// Rotates `a` towards `b` by `angle` degrees, returning the new `a` and `b`.
+fn rotate_plane(a: f64, b: f64, angle: f64) -> (f64, f64) {
+    if angle == 0.0 {
+        return (a, b);
+    }
+
+    let (sin, cos) = angle.to_radians().sin_cos();
+
+    (a * cos - b * sin, a * sin + b * cos)
 }
 
 impl<Source> NoiseFn<f64, 2> for RotatePoint<Source>
@@ -105,26 +201,7 @@ where
     fn get(&self, point: [f64; 3]) -> f64 {
         // In three dimensions, we could rotate around any of the x, y, or z
         // axes. Need a more complicated function to handle this case.
-        let x_cos = self.x_angle.to_radians().cos();
-        let y_cos = self.y_angle.to_radians().cos();
-        let z_cos = self.z_angle.to_radians().cos();
-        let x_sin = self.x_angle.to_radians().sin();
-        let y_sin = self.y_angle.to_radians().sin();
-        let z_sin = self.z_angle.to_radians().sin();
-
-        let x1 = x_sin * y_sin * z_sin + y_cos * z_cos;
-        let y1 = x_cos * z_sin;
-        let z1 = y_sin * z_cos - y_cos * x_sin * z_sin;
-        let x2 = y_sin * x_sin * z_cos - y_cos * z_sin;
-        let y2 = x_cos * z_cos;
-        let z2 = -y_cos * x_sin * z_cos - y_sin * z_sin;
-        let x3 = -y_sin * x_cos;
-        let y3 = x_sin;
-        let z3 = y_cos * x_cos;
-
-        let x = (x1 * point[0]) + (y1 * point[1]) + (z1 * point[2]);
-        let y = (x2 * point[0]) + (y2 * point[1]) + (z2 * point[2]);
-        let z = (x3 * point[0]) + (y3 * point[1]) + (z3 * point[2]);
+        let [x, y, z] = self.rotate_3d(point);
 
         // get the output value using the offset input value instead of the
         // original input value.
@@ -136,8 +213,80 @@ impl<Source> NoiseFn<f64, 4> for RotatePoint<Source>
 where
     Source: NoiseFn<f64, 4>,
 {
-    fn get(&self, _point: [f64; 4]) -> f64 {
-        // 4d rotations are hard.
-        unimplemented!();
+    fn get(&self, point: [f64; 4]) -> f64 {
+        // Rotate the three-dimensional part as in 3D, then turn each of its
+        // axes towards the u axis.
+        let [x, y, z] = self.rotate_3d([point[0], point[1], point[2]]);
+
+        let (x, u) = rotate_plane(x, point[3], self.xu_angle);
+        let (y, u) = rotate_plane(y, u, self.yu_angle);
+        let (z, u) = rotate_plane(z, u, self.zu_angle);
+
+        // get the output value using the offset input value instead of the
+        // original input value.
+        self.source.get([x, y, z, u])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FnNoise;
+
+    fn axis<const DIM: usize>(index: usize) -> FnNoise<impl Fn([f64; DIM]) -> f64> {
+        FnNoise(move |point: [f64; DIM]| point[index])
+    }
+
+    #[test]
+    fn four_dimensional_rotation_extends_three_dimensional() {
+        let angles = (30.0, -45.0, 110.0);
+
+        for index in 0..3 {
+            let rotate3 =
+                RotatePoint::new(axis::<3>(index)).set_angles(angles.0, angles.1, angles.2, 0.0);
+            let rotate4 =
+                RotatePoint::new(axis::<4>(index)).set_angles(angles.0, angles.1, angles.2, 0.0);
+
+            assert_eq!(
+                rotate3.get([0.3, -1.2, 2.5]),
+                rotate4.get([0.3, -1.2, 2.5, 0.0])
+            );
+        }
+
+        let u = RotatePoint::new(axis::<4>(3)).set_angles(angles.0, angles.1, angles.2, 0.0);
+        assert_eq!(u.get([0.3, -1.2, 2.5, 0.7]), 0.7);
+    }
+
+    #[test]
+    fn u_plane_angles_turn_axes_towards_u() {
+        let point = [1.0, 2.0, 3.0, 4.0];
+        let close = |a: f64, b: f64| (a - b).abs() < 1e-12;
+
+        let xu = |index| {
+            RotatePoint::new(axis::<4>(index))
+                .set_xu_angle(90.0)
+                .get(point)
+        };
+        assert!(close(xu(0), -4.0));
+        assert!(close(xu(3), 1.0));
+        assert!(close(xu(1), 2.0));
+
+        let zu = |index| {
+            RotatePoint::new(axis::<4>(index))
+                .set_zu_angle(90.0)
+                .get(point)
+        };
+        assert!(close(zu(2), -4.0));
+        assert!(close(zu(3), 3.0));
+
+        // Rotations preserve the length of the input.
+        let rotated: [f64; 4] = [0, 1, 2, 3].map(|index| {
+            RotatePoint::new(axis::<4>(index))
+                .set_angles(10.0, 20.0, 30.0, 0.0)
+                .set_u_plane_angles(40.0, -50.0, 60.0)
+                .get(point)
+        });
+        let length = |p: [f64; 4]| p.iter().map(|x| x * x).sum::<f64>();
+        assert!(close(length(rotated), length(point)));
     }
 }
@@ -44,19 +44,19 @@ impl<Source> ScalePoint<Source> {
         Self { x_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _y_ coordinate of the input
     /// value.
     pub fn set_y_scale(self, y_scale: f64) -> Self {
         Self { y_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _z_ coordinate of the input
     /// value.
     pub fn set_z_scale(self, z_scale: f64) -> Self {
         Self { z_scale, ..self }
     }
 
-    /// Sets the scaling factor to apply to the _x_ coordinate of the input
+    /// Sets the scaling factor to apply to the _u_ coordinate of the input
     /// value.
     pub fn set_u_scale(self, u_scale: f64) -> Self {
         Self { u_scale, ..self }
@@ -84,6 +84,31 @@ impl<Source> ScalePoint<Source> {
             ..self
         }
     }
+
+    /// Sets the scaling factors of the first `N` coordinates of the input
+    /// value, in _x_, _y_, _z_, _u_ order, leaving the others unchanged.
+    ///
+    /// This allows anisotropic scaling to be written as a single array, e.g.
+    /// `set_scales([4.0, 1.0])` to stretch 2-dimensional noise along _x_.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N` is greater than 4.
+    pub fn set_scales<const N: usize>(mut self, scales: [f64; N]) -> Self {
+        assert!(N <= 4, "ScalePoint supports at most 4 axes, got {}", N);
+
+        let mut axes = [
+            &mut self.x_scale,
+            &mut self.y_scale,
+            &mut self.z_scale,
+            &mut self.u_scale,
+        ];
+        for (axis, scale) in axes.iter_mut().zip(scales) {
+            **axis = scale;
+        }
+
+        self
+    }
 }
 
 impl<Source> NoiseFn<f64, 2> for ScalePoint<Source>
@@ -122,3 +147,21 @@ where
         ])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FnNoise, TranslatePoint};
+
+    #[test]
+    fn array_scales_compose_with_translation() {
+        let position = FnNoise(|point: [f64; 3]| point[0] + 10.0 * point[1] + 100.0 * point[2]);
+        let translated = TranslatePoint::new(position).set_all_translations(1.0, 2.0, 3.0, 0.0);
+        let scaled = ScalePoint::new(translated).set_scales([4.0, 0.5]);
+
+        // The point is scaled first and then translated, and z keeps its
+        // default scale of 1.
+        let expected = (0.5 * 4.0 + 1.0) + 10.0 * (6.0 * 0.5 + 2.0) + 100.0 * (-1.0 + 3.0);
+        assert_eq!(scaled.get([0.5, 6.0, -1.0]), expected);
+    }
+}
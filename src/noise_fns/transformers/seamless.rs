@@ -0,0 +1,115 @@
+use crate::{math, noise_fns::NoiseFn};
+
+/// Noise function that makes the output of the source function tile
+/// seamlessly.
+///
+/// The input point is first wrapped into the region from the origin to the
+/// period on each axis. The source function is then sampled at the wrapped
+/// point and at its copies shifted back by one period along every combination
+/// of axes, i.e. four samples in 2D and eight in 3D. The samples are blended so
+/// that the output approaches the same value from both sides of every period
+/// boundary, making `get([0.0, y])` equal to `get([period_x, y])`.
+///
+/// Blending lowers the contrast of the output towards the middle of the tile,
+/// so tiling works best with a period that spans several features of the
+/// source function.
+#[derive(Clone, Debug)]
+pub struct Seamless<Source, const DIM: usize> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Length of the tile along each axis.
+    pub period: [u32; DIM],
+}
+
+impl<Source, const DIM: usize> Seamless<Source, DIM> {
+    /// # Panics
+    ///
+    /// Panics if any element of `period` is zero.
+    pub fn new(source: Source, period: [u32; DIM]) -> Self {
+        assert!(period.iter().all(|&p| p > 0), "period must be positive");
+
+        Self { source, period }
+    }
+
+    /// Sets the length of the tile along each axis.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any element of `period` is zero.
+    pub fn set_period(self, period: [u32; DIM]) -> Self {
+        assert!(period.iter().all(|&p| p > 0), "period must be positive");
+
+        Self { period, ..self }
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Seamless<Source, DIM>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut wrapped = [0.0; DIM];
+        let mut blend = [0.0; DIM];
+        for axis in 0..DIM {
+            let period = f64::from(self.period[axis]);
+            wrapped[axis] = math::rem_euclid(point[axis], period);
+            blend[axis] = wrapped[axis] / period;
+        }
+
+        // Each bit of the corner selects whether that axis is shifted back by
+        // one period. The unshifted sample fades out as the point approaches
+        // the end of the period, where the shifted sample takes over.
+        let mut result = 0.0;
+        for corner in 0..1usize << DIM {
+            let mut sample = wrapped;
+            let mut weight = 1.0;
+            for axis in 0..DIM {
+                if corner & (1 << axis) != 0 {
+                    sample[axis] -= f64::from(self.period[axis]);
+                    weight *= blend[axis];
+                } else {
+                    weight *= 1.0 - blend[axis];
+                }
+            }
+
+            if weight != 0.0 {
+                result += self.source.get(sample) * weight;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Perlin, Value};
+
+    #[test]
+    fn output_wraps_at_period() {
+        let perlin = Seamless::new(Perlin::new(1), [4, 8]);
+        let value = Seamless::new(Value::new(1), [4, 8, 2]);
+
+        for i in 0..32 {
+            let t = i as f64 * 0.37 - 5.0;
+
+            assert_eq!(perlin.get([0.0, t]), perlin.get([4.0, t]));
+            assert_eq!(perlin.get([t, 0.0]), perlin.get([t, 8.0]));
+            assert_eq!(value.get([0.0, t, 0.3]), value.get([4.0, t, 0.3]));
+            assert_eq!(value.get([t, 0.7, 0.0]), value.get([t, 0.7, 2.0]));
+        }
+    }
+
+    #[test]
+    fn output_is_continuous_across_period() {
+        let perlin = Seamless::new(Perlin::new(1), [4, 8]);
+
+        for i in 0..32 {
+            let y = i as f64 * 0.37 - 5.0;
+
+            assert!((perlin.get([4.0 - 1e-9, y]) - perlin.get([0.0, y])).abs() < 1e-6);
+        }
+    }
+}
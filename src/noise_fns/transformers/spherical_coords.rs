@@ -0,0 +1,93 @@
+use crate::noise_fns::NoiseFn;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that samples a 3-dimensional source function over the
+/// surface of a sphere.
+///
+/// The 2-dimensional input value is interpreted as `[latitude, longitude]` in
+/// radians and converted into the point on a sphere of the given radius
+/// centered on the origin, with the poles on the _y_ axis. Sampling a
+/// [`PlaneMapBuilder`](crate::utils::PlaneMapBuilder) with bounds of
+/// `-π/2..π/2` and `-π..π` then gives an equirectangular texture that wraps
+/// seamlessly onto a sphere, using the same mapping as
+/// [`SphereMapBuilder`](crate::utils::SphereMapBuilder).
+///
+/// Because the source function is sampled on the sphere itself, the texture
+/// has no seam at the ±π longitude and no singularity at the poles. In the
+/// flat image however, each row near a pole covers a smaller and smaller
+/// circle of the sphere, so features are stretched horizontally there, and the
+/// rows at ±π/2 latitude hold a single value repeated across their width. This
+/// pinching disappears once the texture is wrapped back onto a sphere.
+#[derive(Clone, Debug)]
+pub struct SphericalCoords<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Radius of the sphere. Larger radii fit more features of the source
+    /// function around the sphere.
+    pub radius: f64,
+}
+
+impl<Source> SphericalCoords<Source> {
+    pub const DEFAULT_RADIUS: f64 = 1.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            radius: Self::DEFAULT_RADIUS,
+        }
+    }
+
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<Source> NoiseFn<f64, 2> for SphericalCoords<Source>
+where
+    Source: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let (lat_sin, lat_cos) = point[0].sin_cos();
+        let (lon_sin, lon_cos) = point[1].sin_cos();
+
+        let r = self.radius * lat_cos;
+
+        self.source
+            .get([r * lon_cos, self.radius * lat_sin, r * lon_sin])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FnNoise, Perlin};
+    use core::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn samples_the_surface_of_the_sphere() {
+        let length = FnNoise(|p: [f64; 3]| (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt());
+        let sphere = SphericalCoords::new(length).set_radius(2.5);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37 - 1.5, i as f64 * 0.41 - 3.0];
+            assert!((sphere.get(point) - 2.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn wraps_around_longitude_and_meets_at_the_poles() {
+        let sphere = SphericalCoords::new(Perlin::new(1)).set_radius(3.0);
+
+        for i in 0..16 {
+            let lat = i as f64 * 0.19 - 1.5;
+            assert!((sphere.get([lat, -PI]) - sphere.get([lat, PI])).abs() < 1e-9);
+        }
+
+        let pole = sphere.get([FRAC_PI_2, 0.0]);
+        for i in 0..16 {
+            assert!((sphere.get([FRAC_PI_2, i as f64 * 0.4]) - pole).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::{math, noise_fns::NoiseFn};
+use core::f64::consts::TAU;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Noise function that animates a 2-dimensional slice of a 4-dimensional
+/// source function so that the animation loops.
+///
+/// Moving the slice in a straight line along a third axis never repeats.
+/// Instead, the two extra coordinates of the source function are moved around a
+/// circle in their plane, `[x, y, radius * cos(a), radius * sin(a)]` with
+/// `a = 2π * time / period`, so the output at `time` and at `time + period` is
+/// identical and the animation loops seamlessly.
+///
+/// The radius sets how fast the animation changes: the slice travels
+/// `2π * radius` units through the source function in each period.
+#[derive(Clone, Debug)]
+pub struct TimeLoop<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Position in the animation.
+    pub time: f64,
+
+    /// Length of time after which the animation repeats.
+    pub period: f64,
+
+    /// Radius of the circle that the slice moves around.
+    pub radius: f64,
+}
+
+impl<Source> TimeLoop<Source> {
+    pub const DEFAULT_PERIOD: f64 = 1.0;
+    pub const DEFAULT_RADIUS: f64 = 1.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            time: 0.0,
+            period: Self::DEFAULT_PERIOD,
+            radius: Self::DEFAULT_RADIUS,
+        }
+    }
+
+    pub fn set_time(self, time: f64) -> Self {
+        Self { time, ..self }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `period` is not positive.
+    pub fn set_period(self, period: f64) -> Self {
+        assert!(period > 0.0, "period must be positive");
+
+        Self { period, ..self }
+    }
+
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<Source> NoiseFn<f64, 2> for TimeLoop<Source>
+where
+    Source: NoiseFn<f64, 4>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        // Wrapping the time first keeps the angle exact for times many periods
+        // away from zero.
+        let angle = TAU * math::rem_euclid(self.time, self.period) / self.period;
+        let (sin, cos) = angle.sin_cos();
+
+        self.source
+            .get([point[0], point[1], self.radius * cos, self.radius * sin])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Perlin;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn animation_loops_after_one_period() {
+        let animation = TimeLoop::new(Perlin::new(1))
+            .set_period(4.0)
+            .set_radius(0.8);
+
+        for i in 0..16 {
+            let point = [i as f64 * 0.37, i as f64 * -0.21];
+            let time = i as f64 * 0.3;
+
+            let start = animation.clone().set_time(time).get(point);
+            let end = animation.clone().set_time(time + 4.0).get(point);
+            assert!((start - end).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn animation_changes_smoothly() {
+        let animation = TimeLoop::new(Perlin::new(1));
+        let point = [0.3, 1.7];
+
+        let values: Vec<f64> = (0..=100)
+            .map(|i| animation.clone().set_time(i as f64 / 100.0).get(point))
+            .collect();
+
+        assert!(values.windows(2).all(|w| (w[0] - w[1]).abs() < 0.1));
+        assert!(values.iter().any(|&v| v != values[0]));
+    }
+}
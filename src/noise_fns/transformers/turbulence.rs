@@ -103,9 +103,9 @@ where
         Self {
             seed,
             x_distort_function: self.x_distort_function.set_seed(seed),
-            y_distort_function: self.y_distort_function.set_seed(seed + 1),
-            z_distort_function: self.z_distort_function.set_seed(seed + 2),
-            u_distort_function: self.u_distort_function.set_seed(seed + 3),
+            y_distort_function: self.y_distort_function.set_seed(seed.wrapping_add(1)),
+            z_distort_function: self.z_distort_function.set_seed(seed.wrapping_add(2)),
+            u_distort_function: self.u_distort_function.set_seed(seed.wrapping_add(3)),
             ..self
         }
     }
@@ -204,3 +204,26 @@ where
             .get([x_distort, y_distort, z_distort, u_distort])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FnNoise, Perlin};
+
+    #[test]
+    fn warp_is_fbm_scaled_by_power() {
+        let source = FnNoise(|[x, _]: [f64; 2]| x);
+        let turbulence = Turbulence::<_, Perlin>::new(source)
+            .set_power(0.5)
+            .set_roughness(4);
+        let fbm = Fbm::<Perlin>::new(0).set_octaves(4);
+
+        for i in 0..64 {
+            let point = [i as f64 * 0.173, i as f64 * -0.071];
+            let warp = fbm.get([point[0] + 12414.0 / 65536.0, point[1] + 65124.0 / 65536.0]);
+
+            assert_eq!(turbulence.get(point), point[0] + warp * 0.5);
+            assert_eq!(turbulence.clone().set_power(0.0).get(point), point[0]);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use crate::noise_fns::NoiseFn;
+
+/// Noise function that warps the domain of the source function, using a
+/// separate warp function for each axis.
+///
+/// For each input point, the warp functions are sampled at that point, and
+/// their outputs, scaled by the `strength`, are added to the corresponding
+/// coordinates before the source function is sampled. Warping noise with
+/// other noise gives the swirling, folded look of domain warping. Unlike
+/// [`Turbulence`](super::Turbulence), which builds its own displacement
+/// functions, the warp functions can be any noise functions.
+#[derive(Clone, Debug)]
+pub struct Warp<Source, XWarp, YWarp, ZWarp> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Warp function that offsets the _x_ coordinate of the input value.
+    pub x_warp: XWarp,
+
+    /// Warp function that offsets the _y_ coordinate of the input value.
+    pub y_warp: YWarp,
+
+    /// Warp function that offsets the _z_ coordinate of the input value. Only
+    /// needed for 3d noise.
+    pub z_warp: ZWarp,
+
+    /// Scale applied to the output of the warp functions before it is added to
+    /// the input coordinates.
+    pub strength: f64,
+}
+
+impl<Source, XWarp, YWarp, ZWarp> Warp<Source, XWarp, YWarp, ZWarp> {
+    pub const DEFAULT_STRENGTH: f64 = 1.0;
+
+    pub fn new(source: Source, x_warp: XWarp, y_warp: YWarp, z_warp: ZWarp) -> Self {
+        Self {
+            source,
+            x_warp,
+            y_warp,
+            z_warp,
+            strength: Self::DEFAULT_STRENGTH,
+        }
+    }
+
+    /// Sets how far the warp functions move the input coordinates.
+    pub fn set_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+}
+
+impl<Source, XWarp, YWarp, ZWarp> NoiseFn<f64, 2> for Warp<Source, XWarp, YWarp, ZWarp>
+where
+    Source: NoiseFn<f64, 2>,
+    XWarp: NoiseFn<f64, 2>,
+    YWarp: NoiseFn<f64, 2>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let x = point[0] + self.x_warp.get(point) * self.strength;
+        let y = point[1] + self.y_warp.get(point) * self.strength;
+
+        self.source.get([x, y])
+    }
+}
+
+impl<Source, XWarp, YWarp, ZWarp> NoiseFn<f64, 3> for Warp<Source, XWarp, YWarp, ZWarp>
+where
+    Source: NoiseFn<f64, 3>,
+    XWarp: NoiseFn<f64, 3>,
+    YWarp: NoiseFn<f64, 3>,
+    ZWarp: NoiseFn<f64, 3>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let x = point[0] + self.x_warp.get(point) * self.strength;
+        let y = point[1] + self.y_warp.get(point) * self.strength;
+        let z = point[2] + self.z_warp.get(point) * self.strength;
+
+        self.source.get([x, y, z])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Perlin};
+
+    #[test]
+    fn constant_warp_translates_source() {
+        let source = Perlin::new(2);
+        let warp = Warp::new(
+            source,
+            Constant::new(0.5),
+            Constant::new(-1.0),
+            Constant::new(0.25),
+        )
+        .set_strength(2.0);
+
+        for i in 0..32 {
+            let t = i as f64 * 0.37 - 4.1;
+
+            assert_eq!(warp.get([t, t * 0.5]), source.get([t + 1.0, t * 0.5 - 2.0]));
+            assert_eq!(
+                warp.get([t, t * 0.5, -t]),
+                source.get([t + 1.0, t * 0.5 - 2.0, -t + 0.5])
+            );
+        }
+
+        let unwarped = warp.set_strength(0.0);
+        assert_eq!(unwarped.get([0.3, 0.7]), source.get([0.3, 0.7]));
+    }
+}
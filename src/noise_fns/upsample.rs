@@ -0,0 +1,236 @@
+use crate::{math::interpolate, noise_fns::NoiseFn};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+use super::cache::Slot;
+
+/// Noise function that samples the source function on a coarse lattice and
+/// interpolates between the samples.
+///
+/// The input point is floored to a lattice with a spacing of `step` along
+/// every axis, the source function is sampled at the corners of the lattice
+/// cell holding the point, and the output is interpolated between the corners
+/// (bilinearly in 2D, trilinearly in 3D and so on). This trades the detail
+/// finer than `step` for speed, which suits expensive sources that only need
+/// to contribute large features.
+///
+/// The corner values of the last cell are cached, so sampling many points in
+/// the same cell, as a map builder does, only evaluates the source once per
+/// corner. Like [`Cache`](crate::Cache), the cache is only ever *tried* from
+/// other threads, so `Upsample` is `Sync` whenever its source is.
+pub struct Upsample<Source> {
+    /// Outputs the values sampled at the lattice points.
+    pub source: Source,
+
+    // Private, since the cached corners are only valid for the step they were
+    // sampled with, and `set_step` clears them.
+    step: f64,
+
+    cell: Slot<Cell>,
+}
+
+#[derive(Default)]
+struct Cell {
+    lattice: Vec<i64>,
+    corners: Vec<f64>,
+}
+
+impl<Source> Upsample<Source> {
+    pub const DEFAULT_STEP: f64 = 1.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            step: Self::DEFAULT_STEP,
+            cell: Slot::new(Cell::default()),
+        }
+    }
+
+    /// Sets the spacing of the lattice points.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not positive.
+    pub fn set_step(self, step: f64) -> Self {
+        assert!(step > 0.0, "Upsample step must be positive, got {}", step);
+
+        // The cached corners were sampled on the old lattice.
+        Self {
+            step,
+            cell: Slot::new(Cell::default()),
+            ..self
+        }
+    }
+
+    /// Returns the spacing of the lattice points along every axis. The
+    /// default is 1.0.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+}
+
+impl<Source> Clone for Upsample<Source>
+where
+    Source: Clone,
+{
+    /// Clones the source function and step. The clone starts with an empty
+    /// cache.
+    fn clone(&self) -> Self {
+        Self::new(self.source.clone()).set_step(self.step)
+    }
+}
+
+impl<Source> fmt::Debug for Upsample<Source>
+where
+    Source: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Upsample")
+            .field("source", &self.source)
+            .field("step", &self.step)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Source, const DIM: usize> NoiseFn<f64, DIM> for Upsample<Source>
+where
+    Source: NoiseFn<f64, DIM>,
+{
+    fn get(&self, point: [f64; DIM]) -> f64 {
+        let mut lattice = [0; DIM];
+        let mut blend = [0.0; DIM];
+        for ((lattice, blend), x) in lattice.iter_mut().zip(&mut blend).zip(point) {
+            let x = x / self.step;
+            let floor = x.floor();
+
+            *lattice = floor as i64;
+            *blend = x - floor;
+        }
+
+        // Corner `i` is offset by one step along each axis whose bit is set in
+        // `i`.
+        let sample_corner = |corner: usize| {
+            let mut corner_point = [0.0; DIM];
+            for (axis, (x, &lattice)) in corner_point.iter_mut().zip(&lattice).enumerate() {
+                *x = (lattice + (corner >> axis & 1) as i64) as f64 * self.step;
+            }
+
+            self.source.get(corner_point)
+        };
+
+        if let Some(mut cell) = self.cell.try_lock() {
+            if cell.lattice != lattice {
+                // Refill the cached corners in place, reusing their allocation.
+                cell.lattice.clear();
+                cell.lattice.extend_from_slice(&lattice);
+                cell.corners.clear();
+                cell.corners.extend((0..1usize << DIM).map(sample_corner));
+            }
+
+            return multilinear(&|corner| cell.corners[corner], 0, &blend);
+        }
+
+        // Another thread holds the cache, so sample the corners as they are
+        // needed instead.
+        multilinear(&sample_corner, 0, &blend)
+    }
+}
+
+/// Interpolates between the corners of a cell, indexed as in `get` and given
+/// by `corner`, with one blend factor per axis. `offset` is the index of the
+/// first corner of the sub-cell spanned by the axes in `blend`.
+fn multilinear<F>(corner: &F, offset: usize, blend: &[f64]) -> f64
+where
+    F: Fn(usize) -> f64,
+{
+    match blend.split_last() {
+        Some((&last, blend)) => {
+            let upper = offset + (1 << blend.len());
+
+            interpolate::linear(
+                multilinear(corner, offset, blend),
+                multilinear(corner, upper, blend),
+                last,
+            )
+        }
+        None => corner(offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Outputs a smooth function of the point, counting the calls.
+    #[derive(Default)]
+    struct CountCalls(AtomicUsize);
+
+    impl<const DIM: usize> NoiseFn<f64, DIM> for CountCalls {
+        fn get(&self, point: [f64; DIM]) -> f64 {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            point.iter().map(|x| x.sin()).product()
+        }
+    }
+
+    fn calls(upsample: &Upsample<CountCalls>) -> usize {
+        upsample.source.0.load(Ordering::Relaxed)
+    }
+
+    #[test]
+    fn lattice_points_match_the_source() {
+        let upsample = Upsample::new(CountCalls::default()).set_step(0.5);
+
+        for i in -8..8 {
+            let point = [i as f64 * 0.5, (i * 3) as f64 * 0.5];
+            assert!((upsample.get(point) - upsample.source.get(point)).abs() < 1e-12);
+
+            let point = [point[0], point[1], 1.5];
+            assert!((upsample.get(point) - upsample.source.get(point)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cells_interpolate_their_corners() {
+        let upsample = Upsample::new(CountCalls::default()).set_step(0.25);
+        let corners = [[0.5, 1.0], [0.75, 1.0], [0.5, 1.25], [0.75, 1.25]]
+            .map(|corner| NoiseFn::<f64, 2>::get(&upsample.source, corner));
+
+        let center = upsample.get([0.625, 1.125]);
+        assert!((center - corners.iter().sum::<f64>() * 0.25).abs() < 1e-12);
+
+        let edge = upsample.get([0.6875, 1.0]);
+        assert!((edge - (corners[0] * 0.25 + corners[1] * 0.75)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn points_in_the_same_cell_reuse_the_corners() {
+        let upsample = Upsample::new(CountCalls::default()).set_step(2.0);
+
+        upsample.get([0.1, 0.1]);
+        assert_eq!(calls(&upsample), 4);
+
+        for i in 0..16 {
+            upsample.get([i as f64 * 0.12, 1.9 - i as f64 * 0.1]);
+        }
+        assert_eq!(calls(&upsample), 4);
+
+        upsample.get([2.1, 0.1]);
+        assert_eq!(calls(&upsample), 8);
+        upsample.get([2.1, 0.1, 0.0]);
+        assert_eq!(calls(&upsample), 16);
+    }
+
+    #[test]
+    fn set_step_discards_the_cached_corners() {
+        let upsample = Upsample::new(CountCalls::default()).set_step(2.0);
+        upsample.get([0.5, 0.5]);
+
+        let upsample = upsample.set_step(0.25);
+        assert_eq!(upsample.step(), 0.25);
+        let point = [0.5, 0.5];
+        assert!((upsample.get(point) - upsample.source.get(point)).abs() < 1e-12);
+    }
+}
@@ -1,4 +1,4 @@
-use core::fmt;
+use core::{convert::TryFrom, fmt};
 use rand::{
     distributions::{Distribution, Standard},
     seq::SliceRandom,
@@ -8,7 +8,15 @@ use rand_xorshift::XorShiftRng;
 
 const TABLE_SIZE: usize = 256;
 
+/// Hashes lattice points to pseudo-random values for the noise functions.
+///
+/// [`PermutationTable`] is the hasher used by default. Generators such as
+/// [`Perlin::with_hasher`](crate::Perlin::with_hasher) accept other
+/// implementations, for example one with a longer period than the 256 cells
+/// of the permutation table. The noise functions only use the low bits of the
+/// hash, so those should be well distributed.
 pub trait NoiseHasher: Send + Sync {
+    /// Returns the hash of the lattice point with the given coordinates.
     fn hash(&self, to_hash: &[isize]) -> usize;
 }
 
@@ -56,6 +64,59 @@ impl PermutationTable {
         let mut rng: XorShiftRng = SeedableRng::from_seed(real);
         rng.gen()
     }
+
+    /// Deterministically generates a new permutation table based on a `u64`
+    /// seed value.
+    ///
+    /// Seeds that fit in a `u32` give the same table as [`new`](Self::new).
+    /// Larger seeds are mixed with SplitMix64 into the whole state of the
+    /// generator, so that seeds differing only in their upper bits still give
+    /// unrelated tables. The table still repeats every 256 cells.
+    pub fn new_u64(seed: u64) -> Self {
+        if let Ok(seed) = u32::try_from(seed) {
+            return Self::new(seed);
+        }
+
+        let mut state = seed;
+        let mut real = [0; 16];
+        for chunk in real.chunks_exact_mut(8) {
+            chunk.copy_from_slice(&splitmix64(&mut state).to_le_bytes());
+        }
+        // An all-zero state would make the generator output only zeros.
+        real[0] |= 1;
+
+        let mut rng: XorShiftRng = SeedableRng::from_seed(real);
+        rng.gen()
+    }
+
+    /// Creates a permutation table that uses `table` directly, for example to
+    /// reproduce the output of another implementation that ships a fixed
+    /// permutation such as Ken Perlin's reference one.
+    ///
+    /// `table` must contain every value from 0 to 255 exactly once. This is
+    /// checked in debug builds only.
+    pub fn from_array(table: [u8; TABLE_SIZE]) -> Self {
+        debug_assert!(
+            {
+                let mut seen = [false; TABLE_SIZE];
+                table.iter().for_each(|&value| seen[value as usize] = true);
+                seen.iter().all(|&seen| seen)
+            },
+            "PermutationTable::from_array needs a permutation of 0..=255"
+        );
+
+        Self { values: table }
+    }
+}
+
+/// Advances `state` and returns the next output of the SplitMix64 generator.
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
 }
 
 impl NoiseHasher for PermutationTable {
@@ -69,6 +130,59 @@ impl NoiseHasher for PermutationTable {
     }
 }
 
+/// Hasher that translates the hashed lattice cells by a fixed offset, so that
+/// noise can be evaluated relative to a lattice cell far from the origin.
+#[cfg(feature = "twofloat")]
+pub(crate) struct OffsetHasher<'a, NH: ?Sized, const DIM: usize> {
+    pub(crate) hasher: &'a NH,
+    pub(crate) offset: [isize; DIM],
+}
+
+#[cfg(feature = "twofloat")]
+impl<NH, const DIM: usize> NoiseHasher for OffsetHasher<'_, NH, DIM>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        let mut cell = self.offset;
+        for (x, offset) in cell.iter_mut().zip(to_hash) {
+            *x = x.wrapping_add(*offset);
+        }
+
+        self.hasher.hash(&cell)
+    }
+}
+
+/// Hasher that wraps the hashed lattice cells around a period on each axis,
+/// so that the noise tiles. Axes with a period of 0 are not wrapped.
+pub(crate) struct WrapHasher<'a, NH: ?Sized> {
+    pub(crate) hasher: &'a NH,
+    pub(crate) period: Option<[i64; 4]>,
+}
+
+impl<NH> NoiseHasher for WrapHasher<'_, NH>
+where
+    NH: NoiseHasher + ?Sized,
+{
+    fn hash(&self, to_hash: &[isize]) -> usize {
+        let period = match self.period {
+            Some(period) => period,
+            None => return self.hasher.hash(to_hash),
+        };
+
+        let mut cell = [0; 4];
+        for ((x, &to_hash), &period) in cell.iter_mut().zip(to_hash).zip(&period) {
+            *x = if period > 0 {
+                to_hash.rem_euclid(period as isize)
+            } else {
+                to_hash
+            };
+        }
+
+        self.hasher.hash(&cell[..to_hash.len()])
+    }
+}
+
 impl fmt::Debug for PermutationTable {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "PermutationTable {{ .. }}")
@@ -77,7 +191,11 @@ impl fmt::Debug for PermutationTable {
 
 #[cfg(test)]
 mod tests {
-    use crate::{NoiseFn, Perlin, Seedable};
+    use super::{NoiseHasher, PermutationTable};
+    use crate::{
+        NoiseFn, OpenSimplex, Perlin, PerlinSurflet, Seedable, Seedable64, Simplex, SuperSimplex,
+        Value, Worley,
+    };
     use rand::random;
 
     #[test]
@@ -86,9 +204,126 @@ mod tests {
         let _ = perlin.get([1.0, 2.0, 3.0]);
     }
 
+    #[test]
+    fn u64_seeds_extend_u32_seeds() {
+        assert_eq!(
+            PermutationTable::new_u64(12_345).values,
+            PermutationTable::new(12_345).values
+        );
+
+        // Seeds that only differ in their upper bits give different tables.
+        let low = PermutationTable::new_u64(7 | 1 << 40);
+        let high = PermutationTable::new_u64(7 | 1 << 41);
+        assert_ne!(low.values, high.values);
+        assert_ne!(low.values, PermutationTable::new(7).values);
+
+        let perlin = Perlin::new(3).set_seed_u64(1 << 40 | 3);
+        assert_eq!(perlin.seed(), 3);
+        assert_eq!(perlin.seed_u64(), 1 << 40 | 3);
+        assert_ne!(perlin.get([0.4, 0.7]), Perlin::new(3).get([0.4, 0.7]));
+    }
+
+    #[test]
+    fn huge_coordinates_do_not_overflow() {
+        fn check<N>(name: &str, noise: N)
+        where
+            N: NoiseFn<f64, 2> + NoiseFn<f64, 3> + NoiseFn<f64, 4>,
+        {
+            for &huge in &[1e15, -1e15, i64::MAX as f64, i64::MIN as f64] {
+                for value in [
+                    noise.get([huge, 0.5]),
+                    noise.get([0.25, huge, -huge]),
+                    noise.get([huge, huge, 0.5, -huge]),
+                ] {
+                    assert!(value.is_finite(), "{} gave {} at {}", name, value, huge);
+                }
+            }
+        }
+
+        check("Perlin", Perlin::new(1));
+        check("PerlinSurflet", PerlinSurflet::new(1));
+        check("Simplex", Simplex::new(1));
+        check("OpenSimplex", OpenSimplex::new(1));
+        check("Value", Value::new(1));
+        check("Worley", Worley::new(1));
+
+        for &huge in &[i64::MAX as f64, i64::MIN as f64] {
+            assert!(Perlin::new(1).get([huge]).is_finite());
+            assert!(Value::new(1).get([huge]).is_finite());
+            assert!(SuperSimplex::new(1).get([huge, huge, 0.5]).is_finite());
+        }
+    }
+
     #[test]
     fn test_negative_params() {
         let perlin = Perlin::default();
         let _ = perlin.get([-1.0, 2.0, 3.0]);
     }
+
+    /// Permutation table hashing with the lattice point moved by a large
+    /// multiple of 256 cells for every 256 cells along x, so that the pattern
+    /// doesn't repeat along x.
+    struct LongPeriod(PermutationTable);
+
+    impl NoiseHasher for LongPeriod {
+        fn hash(&self, to_hash: &[isize]) -> usize {
+            let mut point = [0; 4];
+            point[..to_hash.len()].copy_from_slice(to_hash);
+            point[1] = point[1].wrapping_add(to_hash[0].div_euclid(256) * 97);
+
+            self.0.hash(&point[..to_hash.len()])
+        }
+    }
+
+    #[test]
+    fn generators_use_custom_hasher() {
+        let table = PermutationTable::new(6);
+
+        let default = Perlin::new(6);
+        let same = Perlin::with_hasher(table);
+        let custom = Perlin::with_hasher(LongPeriod(table));
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.37 + 0.1, i as f64 * 0.19 - 5.0];
+            let shifted = [point[0] + 256.0, point[1]];
+
+            assert_eq!(same.get(point), default.get(point));
+            assert!((default.get(shifted) - default.get(point)).abs() < 1e-9);
+            assert_eq!(custom.get(point), default.get(point));
+        }
+
+        let differs = (0..100).any(|i| {
+            let point = [i as f64 * 0.37 + 0.1, i as f64 * 0.19 - 5.0, 0.5];
+            let shifted = [point[0] + 256.0, point[1], point[2]];
+
+            Simplex::with_hasher(LongPeriod(table)).get(shifted)
+                != Simplex::with_hasher(LongPeriod(table)).get(point)
+                && Worley::with_hasher(LongPeriod(table)).get(shifted)
+                    != Worley::with_hasher(LongPeriod(table)).get(point)
+        });
+        assert!(differs);
+    }
+
+    #[test]
+    fn from_array_uses_the_given_permutation() {
+        let mut table = [0; 256];
+        for (i, value) in table.iter_mut().enumerate() {
+            *value = (i as u8).wrapping_mul(7).wrapping_add(3);
+        }
+        let perm_table = PermutationTable::from_array(table);
+
+        assert_eq!(perm_table.hash(&[5]), table[5] as usize);
+        assert_eq!(perm_table.hash(&[-1]), table[255] as usize);
+        assert_eq!(
+            perm_table.hash(&[2, 9]),
+            table[table[2] as usize ^ 9] as usize
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "permutation")]
+    fn from_array_rejects_repeated_values() {
+        PermutationTable::from_array([0; 256]);
+    }
 }
@@ -0,0 +1,38 @@
+//! Ready-made noise functions for common world generation tasks.
+
+use crate::noise_fns::IslandMask;
+
+/// Creates a mask that is 1.0 at the center of a `width` by `height` rectangle
+/// centered on the origin, and falls off smoothly to 0.0 at its corners.
+///
+/// Multiply fractal terrain by this mask to turn the edges of the map into
+/// ocean. See [`IslandMask`] for a description of `strength`.
+pub fn island_mask(width: f64, height: f64, strength: f64) -> IslandMask {
+    IslandMask::new()
+        .set_size(width, height)
+        .set_strength(strength)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoiseFn;
+
+    #[test]
+    fn island_mask_center_and_corners() {
+        let mask = island_mask(200.0, 100.0, 2.0);
+
+        assert!((mask.get([0.0, 0.0]) - 1.0).abs() < 1e-9);
+
+        for &corner in &[
+            [100.0, 50.0],
+            [-100.0, 50.0],
+            [100.0, -50.0],
+            [-100.0, -50.0],
+        ] {
+            assert!(mask.get(corner).abs() < 1e-9);
+        }
+
+        assert_eq!(0.0, mask.get([500.0, 500.0]));
+    }
+}
@@ -1,10 +1,17 @@
 #[cfg(feature = "image")]
 pub use self::image_renderer::*;
-pub use self::{color_gradient::*, noise_image::*, noise_map::*, noise_map_builder::*};
+pub use self::{
+    color_gradient::*, noise_image::*, noise_map::*, noise_map_builder::*, noise_volume::*,
+    poisson::*,
+};
 
 mod color_gradient;
+#[cfg(test)]
+mod golden;
 #[cfg(feature = "image")]
 mod image_renderer;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod noise_volume;
+mod poisson;
@@ -1,7 +1,86 @@
+use crate::math;
 use alloc::vec::Vec;
+use core::fmt;
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+#[cfg(feature = "std")]
+use std::io::{self, BufRead, BufReader, Read};
 
 pub type Color = [u8; 4];
 
+/// Parses colors from hexadecimal strings, as copied from design tools.
+///
+/// ```
+/// use noise::utils::{Color, FromHex};
+///
+/// assert_eq!(Color::from_hex("#3366ff"), Ok([0x33, 0x66, 0xff, 0xff]));
+/// assert_eq!(Color::from_hex("3366ff80"), Ok([0x33, 0x66, 0xff, 0x80]));
+/// assert!(Color::from_hex("#3366f").is_err());
+/// ```
+pub trait FromHex: Sized {
+    /// Parses `RRGGBB` or `RRGGBBAA`, with an optional leading `#`. Six-digit
+    /// colors are fully opaque.
+    fn from_hex(hex: &str) -> Result<Self, ParseHexColorError>;
+}
+
+impl FromHex for Color {
+    fn from_hex(hex: &str) -> Result<Self, ParseHexColorError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() != 6 && digits.len() != 8 {
+            return Err(ParseHexColorError::InvalidLength);
+        }
+        // Checked up front, since `from_str_radix` also accepts a sign.
+        if !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+            return Err(ParseHexColorError::InvalidDigit);
+        }
+
+        let mut color = [255; 4];
+        for (channel, pair) in color.iter_mut().zip(digits.as_bytes().chunks_exact(2)) {
+            let pair = core::str::from_utf8(pair).map_err(|_| ParseHexColorError::InvalidDigit)?;
+            *channel =
+                u8::from_str_radix(pair, 16).map_err(|_| ParseHexColorError::InvalidDigit)?;
+        }
+
+        Ok(color)
+    }
+}
+
+/// Error returned when a hexadecimal color can't be parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseHexColorError {
+    /// The color doesn't have 6 or 8 digits.
+    InvalidLength,
+
+    /// The color contains a character that isn't a hexadecimal digit.
+    InvalidDigit,
+}
+
+impl fmt::Display for ParseHexColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLength => write!(f, "hex color must have 6 or 8 digits"),
+            Self::InvalidDigit => write!(f, "invalid digit in hex color"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHexColorError {}
+
+/// Color space that [`ColorGradient`] blends between its points in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GradientBlend {
+    /// Blends each of the red, green and blue channels linearly. Blending
+    /// between complementary hues passes through gray.
+    #[default]
+    Rgb,
+
+    /// Blends hue, saturation and value linearly, taking the shorter way
+    /// around the hue circle, so that the colors stay saturated between hues.
+    /// Gray, black and white points take on the hue of the other point.
+    Hsv,
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 struct GradientPoint {
     pos: f64,
@@ -32,6 +111,8 @@ impl GradientDomain {
 pub struct ColorGradient {
     gradient_points: Vec<GradientPoint>,
     domain: GradientDomain,
+    input_range: Option<GradientDomain>,
+    blend: GradientBlend,
 }
 
 impl ColorGradient {
@@ -39,11 +120,123 @@ impl ColorGradient {
         let gradient = Self {
             gradient_points: Vec::new(),
             domain: GradientDomain::new(0.0, 1.0),
+            input_range: None,
+            blend: GradientBlend::Rgb,
         };
 
         gradient.build_grayscale_gradient()
     }
 
+    /// Creates a gradient from black at -1.0 to white at 1.0. This is the
+    /// same gradient that [`new`](Self::new) starts with.
+    pub fn grayscale() -> Self {
+        Self::new().build_grayscale_gradient()
+    }
+
+    /// Creates a terrain gradient, from deep water at -1.0 through the
+    /// shoreline at 0.0 to grass, rock and snow at 1.0.
+    pub fn terrain() -> Self {
+        Self::new().build_terrain_gradient()
+    }
+
+    /// Creates a heat map gradient, from black at -1.0 through red, orange and
+    /// yellow to white at 1.0.
+    pub fn heat() -> Self {
+        Self::new().build_heat_gradient()
+    }
+
+    /// Reads a gradient saved by GIMP in its `.ggr` format, mapping the
+    /// gradient's [0, 1] range onto [-1, 1].
+    ///
+    /// Each segment of the file becomes a stop at each of its ends and one at
+    /// its midpoint, which reproduces the linear blending exactly. The curved,
+    /// sine and spherical blend types, and the HSV coloring modes, are
+    /// approximated with the same linear stops. Where a segment starts with a
+    /// different color than the previous one ends, the hard edge is kept by
+    /// placing the new color just after the previous one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidData`] if the file is
+    /// not a valid GIMP gradient, or any error encountered while reading.
+    #[cfg(feature = "std")]
+    pub fn from_ggr(reader: impl Read) -> io::Result<Self> {
+        // Distance between the two stops that make up a hard edge.
+        const HARD_EDGE_WIDTH: f64 = 1e-9;
+
+        fn invalid(message: &str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, message)
+        }
+
+        fn to_color(channels: &[f64]) -> Color {
+            let mut color = Color::default();
+            for (channel, value) in color.iter_mut().zip(channels) {
+                *channel = (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+            }
+            color
+        }
+
+        let mut lines = BufReader::new(reader).lines();
+        let mut next_line = || {
+            lines
+                .next()
+                .unwrap_or_else(|| Err(invalid("unexpected end of gradient file")))
+        };
+
+        if next_line()?.trim() != "GIMP Gradient" {
+            return Err(invalid("missing GIMP Gradient header"));
+        }
+
+        let mut line = next_line()?;
+        if line.starts_with("Name:") {
+            line = next_line()?;
+        }
+        let segment_count: usize = line
+            .trim()
+            .parse()
+            .map_err(|_| invalid("invalid segment count"))?;
+
+        let mut gradient = Self::new().clear_gradient();
+        let mut previous: Option<(f64, Color)> = None;
+
+        for _ in 0..segment_count {
+            let line = next_line()?;
+            let values = line
+                .split_whitespace()
+                .take(11)
+                .map(|field| field.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid("invalid segment"))?;
+            if values.len() < 11 {
+                return Err(invalid("segment has too few fields"));
+            }
+
+            let [left, middle, right] = [values[0], values[1], values[2]].map(|x| x * 2.0 - 1.0);
+            let left_color = to_color(&values[3..7]);
+            let right_color = to_color(&values[7..11]);
+            let middle_values: Vec<f64> = values[3..7]
+                .iter()
+                .zip(&values[7..11])
+                .map(|(a, b)| (a + b) / 2.0)
+                .collect();
+
+            let left = match previous {
+                Some((end, color)) if (end - left).abs() < f64::EPSILON && color != left_color => {
+                    left + HARD_EDGE_WIDTH
+                }
+                _ => left,
+            };
+
+            gradient = gradient
+                .add_gradient_point(left, left_color)
+                .add_gradient_point(middle, to_color(&middle_values))
+                .add_gradient_point(right, right_color);
+            previous = Some((right, right_color));
+        }
+
+        Ok(gradient)
+    }
+
     pub fn add_gradient_point(mut self, pos: f64, color: Color) -> Self {
         let new_point = GradientPoint { pos, color };
 
@@ -76,6 +269,12 @@ impl ColorGradient {
         self
     }
 
+    /// Adds a gradient point with a color given in hexadecimal, as parsed by
+    /// [`FromHex`].
+    pub fn add_hex_point(self, pos: f64, hex: &str) -> Result<Self, ParseHexColorError> {
+        Ok(self.add_gradient_point(pos, Color::from_hex(hex)?))
+    }
+
     fn find_insertion_point(&self, pos: f64) -> usize {
         self.gradient_points
             .iter()
@@ -124,14 +323,53 @@ impl ColorGradient {
             .add_gradient_point( 1.0, [255,   0,   0, 255])
     }
 
+    #[rustfmt::skip]
+    pub fn build_heat_gradient(self) -> Self {
+        self.clear_gradient()
+            .add_gradient_point(-1.0,  [  0,   0,   0, 255])
+            .add_gradient_point(-0.5,  [128,   0,   0, 255])
+            .add_gradient_point( 0.0,  [255,  32,   0, 255])
+            .add_gradient_point( 0.4,  [255, 140,   0, 255])
+            .add_gradient_point( 0.75, [255, 230,  64, 255])
+            .add_gradient_point( 1.0,  [255, 255, 255, 255])
+    }
+
+    /// Sets the range of the values passed to [`get_color`](Self::get_color).
+    ///
+    /// Input values are linearly mapped from `[min, max]` onto the range
+    /// spanned by the gradient points before the lookup, so a gradient built
+    /// for `[-1, 1]` can color noise with any other output range, such as the
+    /// distances output by `Worley`. Without an input range, the values are
+    /// looked up directly.
+    pub fn set_input_range(self, min: f64, max: f64) -> Self {
+        Self {
+            input_range: Some(GradientDomain::new(min, max)),
+            ..self
+        }
+    }
+
+    /// Sets the color space that the colors are blended in between the
+    /// gradient points. The alpha channel is always blended linearly.
+    pub fn set_interpolation(self, blend: GradientBlend) -> Self {
+        Self { blend, ..self }
+    }
+
     pub fn get_color(&self, pos: f64) -> Color {
         let mut color = Color::default();
 
+        let pos = match self.input_range {
+            Some(range) => {
+                let alpha = (pos - range.min) / (range.max - range.min);
+                (self.domain.max - self.domain.min).mul_add(alpha, self.domain.min)
+            }
+            None => pos,
+        };
+
         // If there are no colors in the gradient, return black
         if !self.gradient_points.is_empty() {
             match () {
                 _ if pos < self.domain.min => color = self.gradient_points.first().unwrap().color,
-                _ if pos > self.domain.max => color = self.gradient_points.last().unwrap().color,
+                _ if pos >= self.domain.max => color = self.gradient_points.last().unwrap().color,
                 _ => {
                     for points in self.gradient_points.windows(2) {
                         if (points[0].pos <= pos) && (points[1].pos > pos) {
@@ -139,7 +377,14 @@ impl ColorGradient {
                             let alpha = (pos - points[0].pos) / (points[1].pos - points[0].pos);
 
                             // Now perform the interpolation and return.
-                            color = interpolate_color(points[0].color, points[1].color, alpha)
+                            color = match self.blend {
+                                GradientBlend::Rgb => {
+                                    interpolate_color(points[0].color, points[1].color, alpha)
+                                }
+                                GradientBlend::Hsv => {
+                                    interpolate_color_hsv(points[0].color, points[1].color, alpha)
+                                }
+                            }
                         }
                     }
                 }
@@ -167,6 +412,76 @@ fn interpolate_color(color0: Color, color1: Color, alpha: f64) -> Color {
     color
 }
 
+fn interpolate_color_hsv(color0: Color, color1: Color, alpha: f64) -> Color {
+    let [h0, s0, v0] = rgb_to_hsv(color0);
+    let [h1, s1, v1] = rgb_to_hsv(color1);
+
+    // The hue of a gray is meaningless, so take the other one.
+    let (h0, h1) = match (s0 == 0.0, s1 == 0.0) {
+        (true, false) => (h1, h1),
+        (false, true) => (h0, h0),
+        _ => (h0, h1),
+    };
+
+    // Go around the shorter side of the hue circle.
+    let mut delta = h1 - h0;
+    if delta > 180.0 {
+        delta -= 360.0;
+    } else if delta < -180.0 {
+        delta += 360.0;
+    }
+
+    let hue = math::rem_euclid(h0 + delta * alpha, 360.0);
+    let saturation = (s1 - s0).mul_add(alpha, s0);
+    let value = (v1 - v0).mul_add(alpha, v0);
+
+    let mut color = hsv_to_rgb([hue, saturation, value]);
+    color[3] = interpolate_color(color0, color1, alpha)[3];
+    color
+}
+
+/// Converts the RGB channels of `color` to hue in degrees, and saturation and
+/// value in [0, 1].
+fn rgb_to_hsv(color: Color) -> [f64; 3] {
+    let [r, g, b] = [color[0], color[1], color[2]].map(|c| f64::from(c) / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let chroma = max - min;
+
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * math::rem_euclid((g - b) / chroma, 6.0)
+    } else if max == g {
+        60.0 * ((b - r) / chroma + 2.0)
+    } else {
+        60.0 * ((r - g) / chroma + 4.0)
+    };
+    let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+    [hue, saturation, max]
+}
+
+/// Converts hue, saturation and value to an opaque color.
+fn hsv_to_rgb([hue, saturation, value]: [f64; 3]) -> Color {
+    let chroma = value * saturation;
+    let sector = hue / 60.0;
+    let x = chroma * (1.0 - (math::rem_euclid(sector, 2.0) - 1.0).abs());
+
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let min = value - chroma;
+
+    let [r, g, b] = [r, g, b].map(|c| ((c + min) * 255.0).round() as u8);
+    [r, g, b, 255]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,4 +505,116 @@ mod tests {
 
         assert_eq!([127, 127, 127, 127], gradient.get_color(0.5));
     }
+
+    #[test]
+    fn presets_span_the_noise_range() {
+        for gradient in &[
+            ColorGradient::grayscale(),
+            ColorGradient::terrain(),
+            ColorGradient::heat(),
+        ] {
+            assert_eq!(gradient.get_color(-1.0), gradient.get_color(-2.0));
+            assert_eq!(gradient.get_color(1.0), gradient.get_color(2.0));
+            assert_ne!(gradient.get_color(-1.0), gradient.get_color(1.0));
+        }
+
+        assert_eq!(ColorGradient::heat().get_color(-1.0), [0, 0, 0, 255]);
+        assert_eq!(ColorGradient::heat().get_color(1.0), [255, 255, 255, 255]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ggr_segments_become_stops() {
+        let ggr = "GIMP Gradient\n\
+                   Name: Test\n\
+                   2\n\
+                   0.0 0.25 0.5 0 0 0 1 1 0 0 1 0 0\n\
+                   0.5 0.75 1.0 0 0 1 1 1 1 1 1 0 0\n";
+        let gradient = ColorGradient::from_ggr(ggr.as_bytes()).unwrap();
+
+        assert_eq!(gradient.get_color(-1.0), [0, 0, 0, 255]);
+        assert_eq!(gradient.get_color(-0.5), [128, 0, 0, 255]);
+        assert_eq!(gradient.get_color(-1e-6), [254, 0, 0, 255]);
+        assert_eq!(gradient.get_color(1e-6), [0, 0, 255, 255]);
+        assert_eq!(gradient.get_color(1.0), [255, 255, 255, 255]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn ggr_rejects_other_files() {
+        let error = ColorGradient::from_ggr(&b"GIMP Palette\n"[..]).unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(ColorGradient::from_ggr(&b"GIMP Gradient\n2\n0 0.5 1\n"[..]).is_err());
+    }
+
+    #[test]
+    fn hsv_blend_keeps_colors_saturated() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(0.0, [255, 0, 0, 0])
+            .add_gradient_point(1.0, [0, 0, 255, 255]);
+
+        assert_eq!([127, 0, 127, 127], gradient.get_color(0.5));
+
+        let gradient = gradient.set_interpolation(GradientBlend::Hsv);
+        assert_eq!([255, 0, 255, 127], gradient.get_color(0.5));
+        assert_eq!([255, 0, 0, 0], gradient.get_color(0.0));
+    }
+
+    #[test]
+    fn hsv_round_trips_colors() {
+        for &color in &[
+            [12, 200, 99, 255],
+            [255, 255, 255, 255],
+            [0, 0, 0, 255],
+            [40, 40, 41, 255],
+        ] {
+            assert_eq!(hsv_to_rgb(rgb_to_hsv(color)), color);
+        }
+    }
+
+    #[test]
+    fn input_range_maps_onto_gradient_points() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(-1.0, [0, 0, 0, 255])
+            .add_gradient_point(0.0, [0, 0, 255, 255])
+            .add_gradient_point(1.0, [255, 255, 255, 255])
+            .set_input_range(0.0, 5.0);
+
+        assert_eq!([0, 0, 0, 255], gradient.get_color(0.0));
+        assert_eq!([0, 0, 127, 255], gradient.get_color(1.25));
+        assert_eq!([0, 0, 255, 255], gradient.get_color(2.5));
+        assert_eq!([127, 127, 255, 255], gradient.get_color(3.75));
+        assert_eq!([0, 0, 0, 255], gradient.get_color(-3.0));
+        assert_eq!([255, 255, 255, 255], gradient.get_color(5.0));
+        assert_eq!([255, 255, 255, 255], gradient.get_color(7.0));
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(Color::from_hex("#3366FFff"), Ok([0x33, 0x66, 0xff, 0xff]));
+        assert_eq!(Color::from_hex("000000"), Ok([0, 0, 0, 255]));
+        assert_eq!(
+            Color::from_hex("#12345"),
+            Err(ParseHexColorError::InvalidLength)
+        );
+        assert_eq!(
+            Color::from_hex("#+12345"),
+            Err(ParseHexColorError::InvalidDigit)
+        );
+        assert_eq!(
+            Color::from_hex("#12345g"),
+            Err(ParseHexColorError::InvalidDigit)
+        );
+
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_hex_point(-1.0, "#000000")
+            .and_then(|gradient| gradient.add_hex_point(1.0, "#ff8000"))
+            .unwrap();
+        assert_eq!(gradient.get_color(1.0), [255, 128, 0, 255]);
+        assert!(ColorGradient::new().add_hex_point(0.0, "red").is_err());
+    }
 }
@@ -0,0 +1,110 @@
+//! Regression renders of the generators and combinators.
+//!
+//! Each noise function is rendered over a fixed tile with a fixed seed and
+//! colored with the default grayscale gradient, and a hash of the pixels is
+//! compared against the value recorded when the output was last known to be
+//! good. The hash is over 8-bit colors, so it only changes when the output
+//! moves at least one color step in some pixel. Drift smaller than that goes
+//! unnoticed, but wrong scale factors, seeds or lattice lookups do not.
+//!
+//! When a change to the output is intended, update the recorded hash with the
+//! one printed by the failing test.
+
+use crate::{
+    utils::{ColorGradient, NoiseMap, NoiseMapBuilder, PlaneMapBuilder},
+    Add, Fbm, MultiFractal, NoiseFn, OpenSimplex, Perlin, RidgedMulti, Select, Simplex, Worley,
+};
+
+const TILE_SIZE: usize = 64;
+
+fn render<N>(noise: N) -> NoiseMap
+where
+    N: NoiseFn<f64, 3>,
+{
+    PlaneMapBuilder::<_, 3>::new(noise)
+        .set_size(TILE_SIZE, TILE_SIZE)
+        .set_x_bounds(-2.3, 3.1)
+        .set_y_bounds(-1.7, 3.7)
+        .build()
+}
+
+/// FNV-1a hash of the pixels of `map`, colored with the grayscale gradient.
+fn pixel_hash(map: &NoiseMap) -> u64 {
+    let gradient = ColorGradient::new();
+
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for &value in map {
+        for channel in gradient.get_color(value) {
+            hash ^= u64::from(channel);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    hash
+}
+
+fn assert_golden<N>(name: &str, noise: N, expected: u64)
+where
+    N: NoiseFn<f64, 3>,
+{
+    let hash = pixel_hash(&render(noise));
+
+    assert_eq!(
+        hash, expected,
+        "the {} render drifted from its golden hash {:#018x}, now {:#018x}",
+        name, expected, hash
+    );
+}
+
+#[test]
+fn perlin() {
+    assert_golden("Perlin", Perlin::new(11), 0x49a9_4b9c_dce1_f0ce);
+}
+
+#[test]
+fn simplex() {
+    assert_golden("Simplex", Simplex::new(11), 0xfea1_ab06_178c_4602);
+}
+
+#[test]
+fn open_simplex() {
+    assert_golden("OpenSimplex", OpenSimplex::new(11), 0x0958_0b82_13bf_e64d);
+}
+
+#[test]
+fn worley() {
+    assert_golden(
+        "Worley",
+        Worley::new(11).set_frequency(2.0),
+        0xe2d8_4621_21e6_2780,
+    );
+}
+
+#[test]
+fn fbm() {
+    assert_golden(
+        "Fbm",
+        Fbm::<Perlin>::new(11).set_octaves(5),
+        0xea2b_767d_13a3_5c21,
+    );
+}
+
+#[test]
+fn ridged_multi() {
+    assert_golden(
+        "RidgedMulti",
+        RidgedMulti::<Perlin>::new(11),
+        0xbdf1_e760_0da5_2564,
+    );
+}
+
+#[test]
+fn combinators() {
+    let sum = Add::new(Perlin::new(11), Worley::new(12));
+    assert_golden("Add", sum, 0x7a21_78d7_1137_aaa5);
+
+    let select = Select::new(Perlin::new(11), Simplex::new(12), Fbm::<Perlin>::new(13))
+        .set_bounds(-0.2, 0.4)
+        .set_falloff(0.1);
+    assert_golden("Select", select, 0x31c7_3401_5872_5a5b);
+}
@@ -3,6 +3,22 @@ use core::{self, f64::consts::SQRT_2};
 
 use super::{color_gradient::*, noise_image::*, noise_map::*};
 
+/// Default bump height for normal maps.
+pub const DEFAULT_BUMP_HEIGHT: f64 = 1.0;
+
+/// How pixels on the edges of a map find their missing neighbours when the map
+/// is shaded or turned into a normal map.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EdgeMode {
+    /// Use the edge pixel itself.
+    #[default]
+    Clamp,
+    /// Use the pixel on the opposite edge, for maps that tile.
+    Wrap,
+    /// Reflect the map at the edge, using the pixel next to the edge pixel.
+    Mirror,
+}
+
 pub struct ImageRenderer {
     // The color gradient used to specify the image colors.
     gradient: ColorGradient,
@@ -12,8 +28,11 @@ pub struct ImageRenderer {
 
     light_enabled: bool,
 
-    // Flag specifying whether wrapping is enabled.
-    wrap_enabled: bool,
+    // How the neighbours of edge pixels are found.
+    edge_mode: EdgeMode,
+
+    // Height scale applied to the slopes when rendering normal maps.
+    bump_height: f64,
 }
 
 impl ImageRenderer {
@@ -22,7 +41,8 @@ impl ImageRenderer {
             gradient: ColorGradient::new(),
             light_source: LightSource::new(),
             light_enabled: false,
-            wrap_enabled: false,
+            edge_mode: EdgeMode::Clamp,
+            bump_height: DEFAULT_BUMP_HEIGHT,
         }
     }
 
@@ -106,15 +126,36 @@ impl ImageRenderer {
         self.light_source.intensity
     }
 
+    /// Shorthand for [`set_edge_mode(EdgeMode::Wrap)`](Self::set_edge_mode).
     pub fn enable_wrap(self) -> Self {
+        self.set_edge_mode(EdgeMode::Wrap)
+    }
+
+    pub fn wrap_enabled(&self) -> bool {
+        self.edge_mode == EdgeMode::Wrap
+    }
+
+    /// Sets how pixels on the edges of the map find their missing neighbours
+    /// for the lighting and normal maps. The default is [`EdgeMode::Clamp`].
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self { edge_mode, ..self }
+    }
+
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
+    /// Sets the scale applied to the slopes of the noise map when rendering
+    /// normal maps. Larger values give steeper looking surfaces.
+    pub fn set_bump_height(self, bump_height: f64) -> Self {
         Self {
-            wrap_enabled: true,
+            bump_height,
             ..self
         }
     }
 
-    pub fn wrap_enabled(&self) -> bool {
-        self.wrap_enabled
+    pub fn bump_height(&self) -> f64 {
+        self.bump_height
     }
 
     pub fn render(&mut self, noise_map: &NoiseMap) -> NoiseImage {
@@ -132,50 +173,8 @@ impl ImageRenderer {
                 let mut light_intensity;
 
                 if self.light_enabled {
-                    let mut x_left_offset: isize = -1;
-                    let mut x_right_offset: isize = 1;
-                    let mut y_down_offset: isize = -1;
-                    let mut y_up_offset: isize = 1;
-
-                    if self.wrap_enabled {
-                        if x == 0 {
-                            x_left_offset = width as isize - 1;
-                            x_right_offset = 1;
-                        } else if x == (width as isize - 1) as usize {
-                            x_left_offset = -1;
-                            x_right_offset = width as isize - 1;
-                        }
-
-                        if y == 0 {
-                            y_down_offset = height as isize - 1;
-                            y_up_offset = 1;
-                        } else if y == (height as isize - 1) as usize {
-                            y_down_offset = -1;
-                            y_up_offset = height as isize - 1;
-                        }
-                    } else {
-                        if x == 0 {
-                            x_left_offset = 0;
-                            x_right_offset = 1;
-                        } else if x == (width as isize - 1) as usize {
-                            x_left_offset = -1;
-                            x_right_offset = 0;
-                        }
-
-                        if y == 0 {
-                            y_down_offset = 0;
-                            y_up_offset = 1;
-                        } else if y == (height as isize - 1) as usize {
-                            y_down_offset = -1;
-                            y_up_offset = 0;
-                        }
-                    }
-
                     let pc = point;
-                    let pl = noise_map[((x as isize + x_left_offset) as usize, y)];
-                    let pr = noise_map[((x as isize + x_right_offset) as usize, y)];
-                    let pd = noise_map[(x, (y as isize + y_down_offset) as usize)];
-                    let pu = noise_map[(x, (y as isize + y_up_offset) as usize)];
+                    let [pl, pr, pd, pu] = self.neighbor_values(noise_map, x, y);
 
                     light_intensity = self.light_source.calc_light_intensity(pc, pl, pr, pd, pu);
                     light_intensity *= self.light_source.brightness;
@@ -192,6 +191,57 @@ impl ImageRenderer {
         destination_image
     }
 
+    /// Renders the noise map as a tangent-space normal map.
+    ///
+    /// The normal of each pixel is built from the slopes of the noise map
+    /// along x and y, scaled by the bump height, and stored with its x, y and
+    /// z components mapped from [-1, 1] onto the red, green and blue channels.
+    /// Pixels on the edges of the map use the same neighbours as the lighting
+    /// in [`render`](Self::render), as chosen by the [`EdgeMode`].
+    pub fn render_normal_map(&self, noise_map: &NoiseMap) -> NoiseImage {
+        let (width, height) = noise_map.size();
+
+        let mut destination_image = NoiseImage::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let [left, right, down, up] = self.neighbor_values(noise_map, x, y);
+
+                let slope_x = (right - left) * 0.5 * self.bump_height;
+                let slope_y = (up - down) * 0.5 * self.bump_height;
+
+                let length = (slope_x * slope_x + slope_y * slope_y + 1.0).sqrt();
+                let normal = [-slope_x / length, -slope_y / length, 1.0 / length];
+
+                destination_image[(x, y)] = [
+                    ((normal[0] * 0.5 + 0.5) * 255.0).round() as u8,
+                    ((normal[1] * 0.5 + 0.5) * 255.0).round() as u8,
+                    ((normal[2] * 0.5 + 0.5) * 255.0).round() as u8,
+                    255,
+                ];
+            }
+        }
+
+        destination_image
+    }
+
+    /// Returns the values of the left, right, lower and upper neighbours of a
+    /// pixel. At the edges of the map the missing neighbours are found as
+    /// chosen by the [`EdgeMode`].
+    fn neighbor_values(&self, noise_map: &NoiseMap, x: usize, y: usize) -> [f64; 4] {
+        let (width, height) = noise_map.size();
+
+        let (left, right) = neighbor_indices(x, width, self.edge_mode);
+        let (down, up) = neighbor_indices(y, height, self.edge_mode);
+
+        [
+            noise_map[(left, y)],
+            noise_map[(right, y)],
+            noise_map[(x, down)],
+            noise_map[(x, up)],
+        ]
+    }
+
     fn calc_destination_color(&self, source_color: Color, light_value: f64) -> Color {
         let source = u8_array_to_f64_array(source_color);
 
@@ -243,50 +293,8 @@ impl ImageRenderer {
                 let mut light_intensity;
 
                 if self.light_enabled {
-                    let mut x_left_offset: isize = -1;
-                    let mut x_right_offset: isize = 1;
-                    let mut y_down_offset: isize = -1;
-                    let mut y_up_offset: isize = 1;
-
-                    if self.wrap_enabled {
-                        if x == 0 {
-                            x_left_offset = width as isize - 1;
-                            x_right_offset = 1;
-                        } else if x == (width as isize - 1) as usize {
-                            x_left_offset = -1;
-                            x_right_offset = width as isize - 1;
-                        }
-
-                        if y == 0 {
-                            y_down_offset = height as isize - 1;
-                            y_up_offset = 1;
-                        } else if y == (height as isize - 1) as usize {
-                            y_down_offset = -1;
-                            y_up_offset = height as isize - 1;
-                        }
-                    } else {
-                        if x == 0 {
-                            x_left_offset = 0;
-                            x_right_offset = 1;
-                        } else if x == (width as isize - 1) as usize {
-                            x_left_offset = -1;
-                            x_right_offset = 0;
-                        }
-
-                        if y == 0 {
-                            y_down_offset = 0;
-                            y_up_offset = 1;
-                        } else if y == (height as isize - 1) as usize {
-                            y_down_offset = -1;
-                            y_up_offset = 0;
-                        }
-                    }
-
                     let pc = point;
-                    let pl = noise_map[((x as isize + x_left_offset) as usize, y)];
-                    let pr = noise_map[((x as isize + x_right_offset) as usize, y)];
-                    let pd = noise_map[(x, (y as isize + y_down_offset) as usize)];
-                    let pu = noise_map[(x, (y as isize + y_up_offset) as usize)];
+                    let [pl, pr, pd, pu] = self.neighbor_values(noise_map, x, y);
 
                     light_intensity = self.light_source.calc_light_intensity(pc, pl, pr, pd, pu);
                     light_intensity *= self.light_source.brightness;
@@ -482,6 +490,25 @@ impl LightSource {
     }
 }
 
+/// Returns the indices before and after `index` along an axis of length `len`.
+#[inline]
+fn neighbor_indices(index: usize, len: usize, edge_mode: EdgeMode) -> (usize, usize) {
+    let before = match (index, edge_mode) {
+        (0, EdgeMode::Clamp) => 0,
+        (0, EdgeMode::Wrap) => len - 1,
+        (0, EdgeMode::Mirror) => 1.min(len - 1),
+        _ => index - 1,
+    };
+    let after = match (index + 1, edge_mode) {
+        (next, _) if next < len => next,
+        (_, EdgeMode::Clamp) => index,
+        (_, EdgeMode::Wrap) => 0,
+        (_, EdgeMode::Mirror) => index.saturating_sub(1),
+    };
+
+    (before, after)
+}
+
 #[inline]
 fn u8_array_to_f64_array(input: [u8; 4]) -> [f64; 4] {
     let mut result = [0.0; 4];
@@ -502,4 +529,48 @@ mod tests {
         assert_eq!([0.0; 4], u8_array_to_f64_array([0; 4]));
         assert_eq!([1.0; 4], u8_array_to_f64_array([255; 4]));
     }
+
+    #[test]
+    fn normal_map_of_ramp_tilts_against_slope() {
+        let mut noise_map = NoiseMap::new(4, 3);
+        for y in 0..3 {
+            for x in 0..4 {
+                noise_map[(x, y)] = x as f64 * 0.5;
+            }
+        }
+
+        let image = ImageRenderer::new()
+            .set_bump_height(2.0)
+            .render_normal_map(&noise_map);
+
+        // A slope of 1 along x gives the normal (-1, 0, 1) / sqrt(2).
+        for y in 0..3 {
+            for x in 0..4 {
+                let expected_x = if x == 0 || x == 3 { 0.5 } else { 1.0 };
+                let length = (expected_x * expected_x + 1.0_f64).sqrt();
+                let red = ((-expected_x / length * 0.5 + 0.5) * 255.0).round() as u8;
+                let blue = ((1.0 / length * 0.5 + 0.5) * 255.0).round() as u8;
+
+                assert_eq!(image[(x, y)], [red, 128, blue, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn neighbors_clamp_wrap_or_mirror_at_edges() {
+        assert_eq!(neighbor_indices(0, 5, EdgeMode::Clamp), (0, 1));
+        assert_eq!(neighbor_indices(4, 5, EdgeMode::Clamp), (3, 4));
+        assert_eq!(neighbor_indices(0, 5, EdgeMode::Wrap), (4, 1));
+        assert_eq!(neighbor_indices(4, 5, EdgeMode::Wrap), (3, 0));
+        assert_eq!(neighbor_indices(2, 5, EdgeMode::Wrap), (1, 3));
+        assert_eq!(neighbor_indices(0, 5, EdgeMode::Mirror), (1, 1));
+        assert_eq!(neighbor_indices(4, 5, EdgeMode::Mirror), (3, 3));
+        assert_eq!(neighbor_indices(2, 5, EdgeMode::Mirror), (1, 3));
+        assert_eq!(neighbor_indices(0, 1, EdgeMode::Mirror), (0, 0));
+
+        let renderer = ImageRenderer::new().enable_wrap();
+        assert!(renderer.wrap_enabled());
+        assert_eq!(renderer.edge_mode(), EdgeMode::Wrap);
+        assert!(!renderer.set_edge_mode(EdgeMode::Mirror).wrap_enabled());
+    }
 }
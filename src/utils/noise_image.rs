@@ -94,6 +94,36 @@ impl NoiseImage {
         }
     }
 
+    /// Creates an image from tightly packed RGBA pixels, four bytes per pixel
+    /// in row-major order, as produced by [`as_bytes`](Self::as_bytes).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` does not hold exactly `width * height` pixels.
+    pub fn from_bytes(width: usize, height: usize, bytes: &[u8]) -> Self {
+        assert_eq!(
+            bytes.len(),
+            width * height * 4,
+            "expected {} bytes for a {}x{} RGBA image",
+            width * height * 4,
+            width,
+            height
+        );
+
+        let mut image = Self::new(width, height);
+        image.map.as_flattened_mut().copy_from_slice(bytes);
+        image
+    }
+
+    /// Returns the pixels as tightly packed RGBA bytes, four bytes per pixel
+    /// in row-major order, ready to be uploaded as a texture. This does not
+    /// copy the pixels.
+    pub fn as_bytes(&self) -> &[u8] {
+        let (width, height) = self.size;
+
+        self.map[..width * height].as_flattened()
+    }
+
     fn initialize() -> Self {
         Self {
             size: (0, 0),
@@ -104,19 +134,9 @@ impl NoiseImage {
 
     #[cfg(feature = "images")]
     pub fn write_to_file(&self, filename: &Path) {
-        // collect the values from the map vector into an array
-        let (width, height) = self.size;
-        let mut result = Vec::with_capacity(width * height);
-
-        for i in &self.map {
-            for j in i.iter() {
-                result.push(*j);
-            }
-        }
-
         let _ = image::save_buffer(
             filename,
-            &result,
+            self.as_bytes(),
             self.size.0 as u32,
             self.size.1 as u32,
             image::ColorType::Rgba8,
@@ -188,3 +208,31 @@ impl<'a> IntoIterator for &'a mut NoiseImage {
         self.iter_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes: Vec<u8> = (0..2 * 3 * 4).map(|x| x as u8).collect();
+        let image = NoiseImage::from_bytes(2, 3, &bytes);
+
+        assert_eq!(image.get_value(1, 0), [4, 5, 6, 7]);
+        assert_eq!(image.get_value(0, 2), [16, 17, 18, 19]);
+        assert_eq!(image.as_bytes(), &bytes[..]);
+    }
+
+    #[test]
+    fn shrunk_image_only_exposes_its_pixels() {
+        let image = NoiseImage::new(4, 4).set_size(2, 2);
+
+        assert_eq!(image.as_bytes().len(), 2 * 2 * 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_bytes_checks_the_length() {
+        NoiseImage::from_bytes(2, 2, &[0; 15]);
+    }
+}
@@ -1,10 +1,11 @@
+use crate::math::interpolate;
 use alloc::{
-    slice::{Iter, IterMut},
+    slice::{ChunksExact, ChunksExactMut, Iter, IterMut},
     vec::{IntoIter, Vec},
 };
-use core::ops::{Index, IndexMut};
-#[cfg(feature = "images")]
-use std::path::Path;
+use core::ops::{self, Index, IndexMut};
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path};
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
@@ -20,12 +21,69 @@ impl NoiseMap {
         Self::initialize().set_size(width, height)
     }
 
+    /// Creates a map of the given size with the value at `(x, y)` set to
+    /// `f(x, y)`. The function is called in row-major order.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> f64,
+    {
+        let mut map = Self::new(width, height);
+        for (y, row) in map.rows_mut().enumerate() {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = f(x, y);
+            }
+        }
+
+        map
+    }
+
+    /// Returns an iterator over the values of the map in row-major order.
     pub fn iter(&self) -> Iter<'_, f64> {
-        self.map.iter()
+        let (width, height) = self.size;
+
+        self.map[..width * height].iter()
     }
 
+    /// Returns an iterator over the mutable values of the map in row-major
+    /// order.
     pub fn iter_mut(&mut self) -> IterMut<'_, f64> {
-        self.map.iter_mut()
+        let (width, height) = self.size;
+
+        self.map[..width * height].iter_mut()
+    }
+
+    /// Returns an iterator over the values of the map along with their
+    /// `(x, y)` coordinates, in row-major order.
+    pub fn indexed_iter(&self) -> impl Iterator<Item = ((usize, usize), f64)> + '_ {
+        self.rows().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, &value)| ((x, y), value))
+        })
+    }
+
+    /// Returns an iterator over the rows of the map, from `y = 0` upwards.
+    pub fn rows(&self) -> ChunksExact<'_, f64> {
+        let (width, height) = self.size;
+
+        // A zero width leaves nothing to iterate, but chunks can't be empty.
+        self.map[..width * height].chunks_exact(width.max(1))
+    }
+
+    /// Returns an iterator over the mutable rows of the map, from `y = 0`
+    /// upwards.
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, f64> {
+        let (width, height) = self.size;
+
+        self.map[..width * height].chunks_exact_mut(width.max(1))
+    }
+
+    /// Returns the values of the map as a mutable slice in row-major order.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [f64] {
+        let (width, height) = self.size;
+
+        &mut self.map[..width * height]
     }
 
     pub fn set_size(self, width: usize, height: usize) -> Self {
@@ -93,6 +151,166 @@ impl NoiseMap {
         }
     }
 
+    /// Returns the smallest and the largest value in the map.
+    ///
+    /// An empty map returns `(f64::INFINITY, f64::NEG_INFINITY)`.
+    pub fn minmax(&self) -> (f64, f64) {
+        let (width, height) = self.size;
+
+        self.map[..width * height]
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            })
+    }
+
+    /// Rescales the values of the map in place so that they span [0, 1].
+    ///
+    /// A constant map is left unchanged.
+    pub fn normalize(&mut self) {
+        self.normalize_to(0.0, 1.0);
+    }
+
+    /// Rescales the values of the map in place so that the smallest becomes
+    /// `min` and the largest becomes `max`.
+    ///
+    /// A constant map is left unchanged.
+    pub fn normalize_to(&mut self, min: f64, max: f64) {
+        let (width, height) = self.size;
+        let (current_min, current_max) = self.minmax();
+
+        if current_max <= current_min {
+            return;
+        }
+
+        let scale = (max - min) / (current_max - current_min);
+        for value in &mut self.map[..width * height] {
+            *value = min + (*value - current_min) * scale;
+        }
+    }
+
+    /// Returns a copy of the map resampled to a new size using bilinear
+    /// interpolation.
+    ///
+    /// The corner values of the map are kept at the corners of the resized map,
+    /// and every other value is interpolated from the four nearest stored
+    /// values. This changes the resolution without changing the pattern, which
+    /// generating the noise again at another size would.
+    pub fn resize(&self, width: usize, height: usize) -> NoiseMap {
+        let (old_width, old_height) = self.size;
+        let mut result = NoiseMap::new(width, height).set_border_value(self.border_value);
+
+        if old_width == 0 || old_height == 0 {
+            return result;
+        }
+
+        // Maps an index in the resized map onto the stored samples, returning
+        // the two samples to interpolate between and the blend factor.
+        fn source_position(index: usize, new_len: usize, old_len: usize) -> (usize, usize, f64) {
+            if new_len <= 1 || old_len <= 1 {
+                return (0, 0, 0.0);
+            }
+
+            let position = (index * (old_len - 1)) as f64 / (new_len - 1) as f64;
+            let lower = (position as usize).min(old_len - 1);
+            let upper = (lower + 1).min(old_len - 1);
+
+            (lower, upper, position - lower as f64)
+        }
+
+        for y in 0..height {
+            let (y0, y1, y_blend) = source_position(y, height, old_height);
+            for x in 0..width {
+                let (x0, x1, x_blend) = source_position(x, width, old_width);
+
+                let top = interpolate::linear(self[(x0, y0)], self[(x1, y0)], x_blend);
+                let bottom = interpolate::linear(self[(x0, y1)], self[(x1, y1)], x_blend);
+                result[(x, y)] = interpolate::linear(top, bottom, y_blend);
+            }
+        }
+
+        result
+    }
+
+    /// Converts the map into an 8-bit grayscale image, mapping the smallest
+    /// value in the map to black and the largest to white.
+    #[cfg(feature = "images")]
+    pub fn to_luma8(&self) -> image::GrayImage {
+        let (min, max) = self.minmax();
+
+        self.to_luma8_in_range(min, max)
+    }
+
+    /// Converts the map into an 8-bit grayscale image, mapping `min` to black
+    /// and `max` to white. Values outside of the range are clamped.
+    ///
+    /// Using the same range for several maps keeps their brightness consistent,
+    /// so that tiles of a larger map line up.
+    #[cfg(feature = "images")]
+    pub fn to_luma8_in_range(&self, min: f64, max: f64) -> image::GrayImage {
+        let pixels = self
+            .normalized_values(min, max)
+            .map(|value| (value * 255.0).round() as u8)
+            .collect();
+
+        self.to_image_buffer(pixels)
+    }
+
+    /// Converts the map into a 16-bit grayscale image, mapping the smallest
+    /// value in the map to black and the largest to white.
+    #[cfg(feature = "images")]
+    pub fn to_luma16(&self) -> image::ImageBuffer<image::Luma<u16>, Vec<u16>> {
+        let (min, max) = self.minmax();
+
+        self.to_luma16_in_range(min, max)
+    }
+
+    /// Converts the map into a 16-bit grayscale image, mapping `min` to black
+    /// and `max` to white. Values outside of the range are clamped.
+    ///
+    /// Using the same range for several maps keeps their brightness consistent,
+    /// so that tiles of a larger map line up.
+    #[cfg(feature = "images")]
+    pub fn to_luma16_in_range(
+        &self,
+        min: f64,
+        max: f64,
+    ) -> image::ImageBuffer<image::Luma<u16>, Vec<u16>> {
+        let pixels = self
+            .normalized_values(min, max)
+            .map(|value| (value * 65535.0).round() as u16)
+            .collect();
+
+        self.to_image_buffer(pixels)
+    }
+
+    /// Returns the values of the map in row-major order, mapped from `min` and
+    /// `max` onto 0.0 and 1.0 and clamped to that range. If the range is empty,
+    /// every value maps to 0.0.
+    #[cfg(feature = "images")]
+    fn normalized_values(&self, min: f64, max: f64) -> impl Iterator<Item = f64> + '_ {
+        let (width, height) = self.size;
+        let scale = if max > min { 1.0 / (max - min) } else { 0.0 };
+
+        self.map[..width * height]
+            .iter()
+            .map(move |value| ((value - min) * scale).clamp(0.0, 1.0))
+    }
+
+    #[cfg(feature = "images")]
+    fn to_image_buffer<P>(
+        &self,
+        pixels: Vec<P::Subpixel>,
+    ) -> image::ImageBuffer<P, Vec<P::Subpixel>>
+    where
+        P: image::Pixel,
+    {
+        let (width, height) = self.size;
+
+        image::ImageBuffer::from_raw(width as u32, height as u32, pixels)
+            .expect("pixel buffer matches the map size")
+    }
+
     #[cfg(feature = "images")]
     pub fn write_to_file(&self, filename: &Path) {
         // collect the values from f64 into u8 in a separate vec
@@ -114,6 +332,56 @@ impl NoiseMap {
         println!("\nFinished generating {}", filename.to_string_lossy());
     }
 
+    /// Writes the map to a 16-bit grayscale image file, mapping values from
+    /// -1.0 to 1.0 onto the full range of 0 to 65535. Values outside of the
+    /// range are clamped. The image format is chosen from the file extension,
+    /// and should be one that supports 16-bit grayscale, such as PNG.
+    #[cfg(feature = "images")]
+    pub fn write_to_file_u16(&self, filename: &Path) -> image::ImageResult<()> {
+        self.to_luma16_in_range(-1.0, 1.0).save(filename)
+    }
+
+    /// Writes the values of the map to a headerless file of 32-bit floats.
+    ///
+    /// The file holds `width * height` values in row-major order, starting at
+    /// the top-left and moving along the rows first, so the value at `(x, y)`
+    /// starts at byte `(x + y * width) * 4`. Each value is a little-endian IEEE
+    /// 754 `f32`. This is the RAW heightmap layout imported by terrain tools,
+    /// which need to be given the width and height separately, and it can be
+    /// read back with `f32::from_le_bytes` on each 4-byte chunk.
+    #[cfg(feature = "std")]
+    pub fn write_to_file_raw_f32(&self, filename: &Path) -> io::Result<()> {
+        let (width, height) = self.size;
+
+        let bytes: Vec<u8> = self.map[..width * height]
+            .iter()
+            .flat_map(|&value| (value as f32).to_le_bytes())
+            .collect();
+
+        fs::write(filename, bytes)
+    }
+
+    /// Replaces each value of the map, and the border value, with `f` of it
+    /// and the matching value of `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maps don't have the same size.
+    fn combine<F>(&mut self, other: &NoiseMap, f: F)
+    where
+        F: Fn(f64, f64) -> f64,
+    {
+        assert_eq!(
+            self.size, other.size,
+            "can't combine NoiseMaps of different sizes"
+        );
+
+        for (value, &other) in self.iter_mut().zip(other.iter()) {
+            *value = f(*value, other);
+        }
+        self.border_value = f(self.border_value, other.border_value);
+    }
+
     fn initialize() -> Self {
         Self {
             size: (0, 0),
@@ -156,12 +424,92 @@ impl IndexMut<(usize, usize)> for NoiseMap {
     }
 }
 
+/// Element-wise arithmetic between maps of the same size, for compositing
+/// built maps, e.g. `heightmap + detail * 0.1`. The border values are combined
+/// the same way. Combining maps of different sizes panics.
+macro_rules! impl_map_ops {
+    ($($trait:ident::$method:ident($op:tt);)*) => {
+        $(
+            impl ops::$trait<&NoiseMap> for NoiseMap {
+                type Output = NoiseMap;
+
+                fn $method(mut self, rhs: &NoiseMap) -> NoiseMap {
+                    self.combine(rhs, |a, b| a $op b);
+                    self
+                }
+            }
+
+            impl ops::$trait<NoiseMap> for NoiseMap {
+                type Output = NoiseMap;
+
+                fn $method(self, rhs: NoiseMap) -> NoiseMap {
+                    self $op &rhs
+                }
+            }
+
+            impl ops::$trait<NoiseMap> for &NoiseMap {
+                type Output = NoiseMap;
+
+                fn $method(self, mut rhs: NoiseMap) -> NoiseMap {
+                    rhs.combine(self, |b, a| a $op b);
+                    rhs
+                }
+            }
+
+            impl ops::$trait<&NoiseMap> for &NoiseMap {
+                type Output = NoiseMap;
+
+                fn $method(self, rhs: &NoiseMap) -> NoiseMap {
+                    let (width, height) = self.size;
+                    let copy = NoiseMap::from_fn(width, height, |x, y| self[(x, y)])
+                        .set_border_value(self.border_value);
+
+                    copy $op rhs
+                }
+            }
+        )*
+    };
+}
+
+impl_map_ops! {
+    Add::add(+);
+    Sub::sub(-);
+    Mul::mul(*);
+}
+
+/// Scales every value of the map, and the border value.
+impl ops::Mul<f64> for NoiseMap {
+    type Output = NoiseMap;
+
+    fn mul(mut self, rhs: f64) -> NoiseMap {
+        self.iter_mut().for_each(|value| *value *= rhs);
+        self.border_value *= rhs;
+        self
+    }
+}
+
+/// Scales every value of the map, and the border value.
+impl ops::Mul<f64> for &NoiseMap {
+    type Output = NoiseMap;
+
+    fn mul(self, rhs: f64) -> NoiseMap {
+        let (width, height) = self.size;
+
+        NoiseMap::from_fn(width, height, |x, y| self[(x, y)] * rhs)
+            .set_border_value(self.border_value * rhs)
+    }
+}
+
 impl IntoIterator for NoiseMap {
     type Item = f64;
 
     type IntoIter = IntoIter<f64>;
 
-    fn into_iter(self) -> Self::IntoIter {
+    fn into_iter(mut self) -> Self::IntoIter {
+        let (width, height) = self.size;
+
+        // A map that was shrunk with `set_size` keeps its old allocation.
+        self.map.truncate(width * height);
         self.map.into_iter()
     }
 }
@@ -185,3 +533,165 @@ impl<'a> IntoIterator for &'a mut NoiseMap {
         self.iter_mut()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp_map(width: usize, height: usize) -> NoiseMap {
+        NoiseMap::from_fn(width, height, |x, y| {
+            (x * x) as f64 * 0.1 - (y * 3) as f64 * 0.2
+        })
+    }
+
+    #[test]
+    fn normalize_rescales_to_range() {
+        let mut map = ramp_map(5, 3);
+        let (min, max) = map.minmax();
+        assert!(min < max);
+
+        map.normalize_to(-2.0, 6.0);
+        assert_eq!(map.minmax(), (-2.0, 6.0));
+
+        map.normalize();
+        assert_eq!(map.minmax(), (0.0, 1.0));
+
+        let mut constant = NoiseMap::new(4, 4);
+        constant.iter_mut().for_each(|value| *value = 0.25);
+        constant.normalize();
+        assert!(constant.iter().all(|&value| value == 0.25));
+    }
+
+    #[test]
+    fn resize_to_same_size_is_identity() {
+        let map = ramp_map(7, 5);
+        let resized = map.resize(7, 5);
+
+        assert_eq!(resized.size(), (7, 5));
+        for y in 0..5 {
+            for x in 0..7 {
+                assert_eq!(resized[(x, y)], map[(x, y)]);
+            }
+        }
+    }
+
+    #[test]
+    fn upscale_interpolates_midpoints() {
+        let map = ramp_map(4, 3);
+        let resized = map.resize(7, 5);
+
+        for y in 0..5usize {
+            for x in 0..7usize {
+                let (x0, x1) = (x / 2, x.div_ceil(2));
+                let (y0, y1) = (y / 2, y.div_ceil(2));
+                let expected =
+                    (map[(x0, y0)] + map[(x1, y0)] + map[(x0, y1)] + map[(x1, y1)]) * 0.25;
+
+                assert!((resized[(x, y)] - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[cfg(feature = "images")]
+    #[test]
+    fn luma_images_span_value_range() {
+        let map = ramp_map(4, 3);
+
+        let luma16 = map.to_luma16();
+        assert_eq!(luma16.dimensions(), (4, 3));
+        assert_eq!(luma16.get_pixel(0, 2).0, [0]);
+        assert_eq!(luma16.get_pixel(3, 0).0, [65535]);
+
+        let luma8 = map.to_luma8_in_range(-1.0, 1.0);
+        assert_eq!(luma8.get_pixel(0, 0).0, [128]);
+        assert_eq!(luma8.get_pixel(3, 0).0, [242]);
+        assert_eq!(luma8.get_pixel(1, 1).0, [64]);
+        assert_eq!(map.to_luma8_in_range(-0.2, 0.2).get_pixel(0, 2).0, [0]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn raw_f32_file_is_row_major_little_endian() {
+        let map = ramp_map(4, 3);
+        let path = std::env::temp_dir().join("noise_map_raw_f32_test.raw");
+
+        map.write_to_file_raw_f32(&path).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(bytes.len(), 4 * 3 * 4);
+        for (i, chunk) in bytes.chunks_exact(4).enumerate() {
+            let value = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+
+            assert_eq!(value, map[(i % 4, i / 4)] as f32);
+        }
+    }
+
+    #[test]
+    fn iterators_cover_only_the_current_size() {
+        let mut map = ramp_map(4, 3);
+        map.iter_mut().for_each(|value| *value += 1.0);
+        let map = map.set_size(3, 2);
+
+        let rows: Vec<&[f64]> = map.rows().collect();
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|row| row.len() == 3));
+
+        let indexed: Vec<_> = map.indexed_iter().collect();
+        assert_eq!(indexed.len(), 6);
+        assert_eq!(indexed[4].0, (1, 1));
+        for ((x, y), value) in indexed {
+            assert_eq!(value, map[(x, y)]);
+            assert_eq!(value, rows[y][x]);
+        }
+
+        assert_eq!((&map).into_iter().count(), 6);
+        assert_eq!(map.into_iter().count(), 6);
+        assert_eq!(NoiseMap::new(0, 0).rows().count(), 0);
+    }
+
+    #[test]
+    fn from_fn_fills_in_row_major_order() {
+        let mut calls = 0;
+        let map = NoiseMap::from_fn(3, 2, |x, y| {
+            assert_eq!((x, y), (calls % 3, calls / 3));
+            calls += 1;
+            (x + 10 * y) as f64
+        });
+
+        assert_eq!(calls, 6);
+        assert_eq!(map.size(), (3, 2));
+        assert_eq!(
+            map.iter().copied().collect::<Vec<_>>(),
+            [0.0, 1.0, 2.0, 10.0, 11.0, 12.0]
+        );
+        assert_eq!(
+            NoiseMap::from_fn(0, 4, |_, _| unreachable!()).size(),
+            (0, 0)
+        );
+    }
+
+    #[test]
+    fn arithmetic_is_element_wise() {
+        let a = ramp_map(4, 3).set_border_value(1.0);
+        let b = NoiseMap::from_fn(4, 3, |x, y| (x + y) as f64).set_border_value(2.0);
+
+        let sum = &a + &b;
+        let difference = &a - b.set_border_value(2.0) * 0.5;
+        let product = ramp_map(4, 3) * &a;
+
+        for ((x, y), value) in a.indexed_iter() {
+            assert_eq!(sum[(x, y)], value + (x + y) as f64);
+            assert_eq!(difference[(x, y)], value - (x + y) as f64 * 0.5);
+            assert_eq!(product[(x, y)], value * value);
+        }
+        assert_eq!(sum.border_value(), 3.0);
+        assert_eq!(difference.border_value(), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "different sizes")]
+    fn arithmetic_requires_equal_sizes() {
+        let _ = NoiseMap::new(4, 3) + NoiseMap::new(3, 4);
+    }
+}
@@ -1,4 +1,12 @@
-use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use crate::{
+    math::interpolate,
+    noise_fns::{NoiseFn, NoiseFnWithDerivative},
+    utils::{noise_map::NoiseMap, noise_volume::NoiseVolume},
+};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 pub struct NoiseFnWrapper<SourceFn, const DIM: usize>
 where
@@ -26,6 +34,12 @@ pub trait NoiseMapBuilder<SourceModule> {
     fn build(&self) -> NoiseMap;
 }
 
+/// Builds a noise map by sampling a 3-dimensional noise function on the
+/// surface of a unit-radius cylinder standing along the y axis.
+///
+/// Columns run around the cylinder along the angle bounds, in degrees, and
+/// rows run up its axis along the height bounds. With angle bounds spanning
+/// 360 degrees the left and right edges of the map tile seamlessly.
 pub struct CylinderMapBuilder<SourceModule>
 where
     SourceModule: NoiseFn<f64, 3>,
@@ -82,6 +96,35 @@ where
     pub fn height_bounds(&self) -> (f64, f64) {
         self.height_bounds
     }
+
+    /// Builds the noise map, splitting the rows across the rayon thread pool.
+    ///
+    /// The output is identical to [`NoiseMapBuilder::build`].
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseMap
+    where
+        SourceModule: Sync,
+    {
+        par_fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+
+    fn value_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let angle_extent = self.angle_bounds.1 - self.angle_bounds.0;
+        let height_extent = self.height_bounds.1 - self.height_bounds.0;
+
+        let x_step = angle_extent / width as f64;
+        let y_step = height_extent / height as f64;
+
+        let current_height = self.height_bounds.0 + y_step * y as f64;
+        let current_angle = self.angle_bounds.0 + x_step * x as f64;
+
+        let point_x = current_angle.to_radians().cos();
+        let point_z = current_angle.to_radians().sin();
+
+        self.source_module.get([point_x, current_height, point_z])
+    }
 }
 
 impl<SourceModule> NoiseMapBuilder<SourceModule> for CylinderMapBuilder<SourceModule>
@@ -107,32 +150,7 @@ where
     }
 
     fn build(&self) -> NoiseMap {
-        let mut result_map = NoiseMap::new(self.size.0, self.size.1);
-
-        let (width, height) = self.size;
-
-        let angle_extent = self.angle_bounds.1 - self.angle_bounds.0;
-        let height_extent = self.height_bounds.1 - self.height_bounds.0;
-
-        let x_step = angle_extent / width as f64;
-        let y_step = height_extent / height as f64;
-
-        for y in 0..height {
-            let current_height = self.height_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_angle = self.angle_bounds.0 + x_step * x as f64;
-
-                let point_x = current_angle.to_radians().cos();
-                let point_z = current_angle.to_radians().sin();
-
-                let value = self.source_module.get([point_x, current_height, point_z]);
-
-                result_map[(x, y)] = value;
-            }
-        }
-
-        result_map
+        fill_map(self.size, |x, y| self.value_at(x, y))
     }
 }
 
@@ -168,6 +186,12 @@ where
         }
     }
 
+    /// Sets the range of x coordinates covered by the map.
+    ///
+    /// The first column samples `lower_x_bound` and the last column samples
+    /// `upper_x_bound`, so adjacent chunks sharing a boundary agree along it.
+    /// Seamless maps instead stop one column short of the upper bound, since
+    /// it wraps around to the lower one.
     pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
         PlaneMapBuilder {
             x_bounds: (lower_x_bound, upper_x_bound),
@@ -175,6 +199,10 @@ where
         }
     }
 
+    /// Sets the range of y coordinates covered by the map.
+    ///
+    /// Rows map onto the bounds in the same way as the columns do for
+    /// [`set_x_bounds`](Self::set_x_bounds).
     pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
         PlaneMapBuilder {
             y_bounds: (lower_y_bound, upper_y_bound),
@@ -191,6 +219,58 @@ where
     }
 }
 
+impl<SourceModule> PlaneMapBuilder<SourceModule, 3>
+where
+    SourceModule: NoiseFn<f64, 3>,
+{
+    /// Builds the noise map, splitting the rows across the rayon thread pool.
+    ///
+    /// The output is identical to [`NoiseMapBuilder::build`].
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseMap
+    where
+        SourceModule: Sync,
+    {
+        par_fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+
+    fn value_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let x_extent = self.x_bounds.1 - self.x_bounds.0;
+        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+
+        let x_step = plane_step(x_extent, width, self.is_seamless);
+        let y_step = plane_step(y_extent, height, self.is_seamless);
+
+        let current_y = self.y_bounds.0 + y_step * y as f64;
+        let current_x = self.x_bounds.0 + x_step * x as f64;
+
+        if self.is_seamless {
+            let sw_value = self.source_module.get([current_x, current_y, 0.0]);
+            let se_value = self
+                .source_module
+                .get([current_x + x_extent, current_y, 0.0]);
+            let nw_value = self
+                .source_module
+                .get([current_x, current_y + y_extent, 0.0]);
+            let ne_value =
+                self.source_module
+                    .get([current_x + x_extent, current_y + y_extent, 0.0]);
+
+            let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
+            let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
+
+            let y0 = interpolate::linear(sw_value, se_value, x_blend);
+            let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+            interpolate::linear(y0, y1, y_blend)
+        } else {
+            self.source_module.get([current_x, current_y, 0.0])
+        }
+    }
+}
+
 impl<SourceModule> NoiseMapBuilder<SourceModule> for PlaneMapBuilder<SourceModule, 3>
 where
     SourceModule: NoiseFn<f64, 3>,
@@ -214,50 +294,7 @@ where
     }
 
     fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
-
-        let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
-
-        result_map
+        fill_map(self.size, |x, y| self.value_at(x, y))
     }
 }
 
@@ -288,98 +325,61 @@ where
     SourceFn: Fn([f64; 2]) -> f64,
 {
     pub fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
+        fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+
+    /// Builds the noise map, splitting the rows across the rayon thread pool.
+    ///
+    /// The output is identical to [`build`](Self::build).
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseMap
+    where
+        SourceFn: Sync,
+    {
+        par_fill_map(self.size, |x, y| self.value_at(x, y))
+    }
 
-        let mut result_map = NoiseMap::new(width, height);
+    fn value_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
 
         let x_extent = self.x_bounds.1 - self.x_bounds.0;
         let y_extent = self.y_bounds.1 - self.y_bounds.0;
 
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
+        let x_step = plane_step(x_extent, width, self.is_seamless);
+        let y_step = plane_step(y_extent, height, self.is_seamless);
 
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y]);
-                    let se_value = self.source_module.get([current_x + x_extent, current_y]);
-                    let nw_value = self.source_module.get([current_x, current_y + y_extent]);
-                    let ne_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y + y_extent]);
+        let current_y = self.y_bounds.0 + y_step * y as f64;
+        let current_x = self.x_bounds.0 + x_step * x as f64;
 
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
+        if self.is_seamless {
+            let sw_value = self.source_module.get([current_x, current_y]);
+            let se_value = self.source_module.get([current_x + x_extent, current_y]);
+            let nw_value = self.source_module.get([current_x, current_y + y_extent]);
+            let ne_value = self
+                .source_module
+                .get([current_x + x_extent, current_y + y_extent]);
 
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+            let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
+            let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
 
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y])
-                };
+            let y0 = interpolate::linear(sw_value, se_value, x_blend);
+            let y1 = interpolate::linear(nw_value, ne_value, x_blend);
 
-                result_map[(x, y)] = final_value;
-            }
+            interpolate::linear(y0, y1, y_blend)
+        } else {
+            self.source_module.get([current_x, current_y])
         }
-
-        result_map
     }
 }
 
+// The wrapper is a 3D noise function, so `par_build` and `value_at` come from
+// the impl for any 3D source above, and defining them here would clash.
 impl<SourceFn> PlaneMapBuilder<NoiseFnWrapper<SourceFn, 3>, 3>
 where
     SourceFn: Fn([f64; 3]) -> f64,
 {
     pub fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
-
-        let mut result_map = NoiseMap::new(width, height);
-
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
-
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
-
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
-
-        result_map
+        fill_map(self.size, |x, y| self.value_at(x, y))
     }
 }
 
@@ -388,56 +388,65 @@ where
     SourceFn: Fn([f64; 4]) -> f64,
 {
     pub fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
+        fill_map(self.size, |x, y| self.value_at(x, y))
+    }
 
-        let mut result_map = NoiseMap::new(width, height);
+    /// Builds the noise map, splitting the rows across the rayon thread pool.
+    ///
+    /// The output is identical to [`build`](Self::build).
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseMap
+    where
+        SourceFn: Sync,
+    {
+        par_fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+
+    fn value_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
 
         let x_extent = self.x_bounds.1 - self.x_bounds.0;
         let y_extent = self.y_bounds.1 - self.y_bounds.0;
 
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
+        let x_step = plane_step(x_extent, width, self.is_seamless);
+        let y_step = plane_step(y_extent, height, self.is_seamless);
 
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
-
-            for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0, 0.5]);
-                    let se_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y, 0.0, 0.5]);
-                    let nw_value =
-                        self.source_module
-                            .get([current_x, current_y + y_extent, 0.0, 0.5]);
-                    let ne_value = self.source_module.get([
-                        current_x + x_extent,
-                        current_y + y_extent,
-                        0.0,
-                        0.5,
-                    ]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0, 0.5])
-                };
-
-                result_map[(x, y)] = final_value;
-            }
-        }
+        let current_y = self.y_bounds.0 + y_step * y as f64;
+        let current_x = self.x_bounds.0 + x_step * x as f64;
+
+        if self.is_seamless {
+            let sw_value = self.source_module.get([current_x, current_y, 0.0, 0.5]);
+            let se_value = self
+                .source_module
+                .get([current_x + x_extent, current_y, 0.0, 0.5]);
+            let nw_value = self
+                .source_module
+                .get([current_x, current_y + y_extent, 0.0, 0.5]);
+            let ne_value =
+                self.source_module
+                    .get([current_x + x_extent, current_y + y_extent, 0.0, 0.5]);
+
+            let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
+            let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
 
-        result_map
+            let y0 = interpolate::linear(sw_value, se_value, x_blend);
+            let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+            interpolate::linear(y0, y1, y_blend)
+        } else {
+            self.source_module.get([current_x, current_y, 0.0, 0.5])
+        }
     }
 }
 
+/// Builds an equirectangular noise map by sampling a 3-dimensional noise
+/// function on the surface of the unit sphere.
+///
+/// Columns run from west to east along the longitude bounds and rows from
+/// south to north along the latitude bounds, all in degrees. Sampling the
+/// sphere directly avoids the pole distortion of wrapping a flat plane, and
+/// with longitude bounds spanning 360 degrees the left and right edges of the
+/// map join up seamlessly.
 pub struct SphereMapBuilder<SourceModule>
 where
     SourceModule: NoiseFn<f64, 3>,
@@ -475,6 +484,8 @@ where
         }
     }
 
+    /// Sets the southern, northern, western and eastern bounds of the map, in
+    /// degrees.
     pub fn set_bounds(
         self,
         min_lat_bound: f64,
@@ -496,6 +507,33 @@ where
     pub fn longitude_bounds(&self) -> (f64, f64) {
         self.longitude_bounds
     }
+
+    /// Builds the noise map, splitting the rows across the rayon thread pool.
+    ///
+    /// The output is identical to [`NoiseMapBuilder::build`].
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseMap
+    where
+        SourceModule: Sync,
+    {
+        par_fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+
+    fn value_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let lon_extent = self.longitude_bounds.1 - self.longitude_bounds.0;
+        let lat_extent = self.latitude_bounds.1 - self.latitude_bounds.0;
+
+        let x_step = lon_extent / width as f64;
+        let y_step = lat_extent / height as f64;
+
+        let current_lat = self.latitude_bounds.0 + y_step * y as f64;
+        let current_lon = self.longitude_bounds.0 + x_step * x as f64;
+
+        self.source_module
+            .get(lat_lon_to_xyz(current_lat, current_lon))
+    }
 }
 
 impl<SourceModule> NoiseMapBuilder<SourceModule> for SphereMapBuilder<SourceModule>
@@ -521,37 +559,414 @@ where
     }
 
     fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
+        fill_map(self.size, |x, y| self.value_at(x, y))
+    }
+}
 
-        let mut result_map = NoiseMap::new(width, height);
+fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
+    let r = lat.to_radians().cos();
+    let x = r * lon.to_radians().cos();
+    let y = lat.to_radians().sin();
+    let z = r * lon.to_radians().sin();
 
-        let lon_extent = self.longitude_bounds.1 - self.longitude_bounds.0;
-        let lat_extent = self.latitude_bounds.1 - self.latitude_bounds.0;
+    [x, y, z]
+}
 
-        let x_step = lon_extent / width as f64;
-        let y_step = lat_extent / height as f64;
+/// Returns the distance between adjacent pixels of a plane map spanning
+/// `extent` with `pixels` pixels.
+///
+/// Seamless maps leave out the upper bound, as it is the same as the lower
+/// one once the map is tiled; otherwise both bounds are sampled.
+/// Builds a noise volume by sampling a 3-dimensional noise function on a
+/// regular grid.
+///
+/// The grid spans the bounds along each axis, with the first and last samples
+/// on the lower and upper bounds, in the same way as the columns and rows of a
+/// non-seamless [`PlaneMapBuilder`].
+pub struct VolumeBuilder<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 3>,
+{
+    lower_bounds: [f64; 3],
+    upper_bounds: [f64; 3],
+    size: (usize, usize, usize),
+    source_module: SourceModule,
+}
 
-        for y in 0..height {
-            let current_lat = self.latitude_bounds.0 + y_step * y as f64;
+impl<SourceModule> VolumeBuilder<SourceModule>
+where
+    SourceModule: NoiseFn<f64, 3>,
+{
+    pub fn new(source_module: SourceModule) -> Self {
+        VolumeBuilder {
+            lower_bounds: [-1.0; 3],
+            upper_bounds: [1.0; 3],
+            size: (32, 32, 32),
+            source_module,
+        }
+    }
 
-            for x in 0..width {
-                let current_lon = self.longitude_bounds.0 + x_step * x as f64;
+    pub fn set_size(self, width: usize, height: usize, depth: usize) -> Self {
+        VolumeBuilder {
+            size: (width, height, depth),
+            ..self
+        }
+    }
 
-                let point = lat_lon_to_xyz(current_lat, current_lon);
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
 
-                result_map[(x, y)] = self.source_module.get(point);
-            }
+    /// Sets the corners of the box covered by the volume, as the lower and the
+    /// upper _x_, _y_ and _z_ coordinates.
+    pub fn set_bounds(self, lower_bounds: [f64; 3], upper_bounds: [f64; 3]) -> Self {
+        VolumeBuilder {
+            lower_bounds,
+            upper_bounds,
+            ..self
+        }
+    }
+
+    pub fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        (self.lower_bounds, self.upper_bounds)
+    }
+
+    pub fn set_source_module(self, source_module: SourceModule) -> Self {
+        VolumeBuilder {
+            source_module,
+            ..self
+        }
+    }
+
+    pub fn build(&self) -> NoiseVolume {
+        let (width, height, depth) = self.size;
+        let mut volume = NoiseVolume::new(width, height, depth);
+
+        if width == 0 || height == 0 {
+            return volume;
+        }
+
+        for (z, slice) in volume.as_mut_slice().chunks_mut(width * height).enumerate() {
+            self.fill_slice(z, slice);
+        }
+
+        volume
+    }
+
+    /// Builds the noise volume, splitting the _z_ slices across the rayon
+    /// thread pool.
+    ///
+    /// The output is identical to [`build`](Self::build).
+    #[cfg(feature = "rayon")]
+    pub fn par_build(&self) -> NoiseVolume
+    where
+        SourceModule: Sync,
+    {
+        let (width, height, depth) = self.size;
+        let mut volume = NoiseVolume::new(width, height, depth);
+
+        if width == 0 || height == 0 {
+            return volume;
         }
 
-        result_map
+        volume
+            .as_mut_slice()
+            .par_chunks_mut(width * height)
+            .enumerate()
+            .for_each(|(z, slice)| self.fill_slice(z, slice));
+
+        volume
+    }
+
+    fn fill_slice(&self, z: usize, slice: &mut [f64]) {
+        let (width, height, depth) = self.size;
+        let counts = [width, height, depth];
+
+        let mut step = [0.0; 3];
+        for axis in 0..3 {
+            let extent = self.upper_bounds[axis] - self.lower_bounds[axis];
+            step[axis] = plane_step(extent, counts[axis], false);
+        }
+
+        let current_z = self.lower_bounds[2] + step[2] * z as f64;
+        for (index, value) in slice.iter_mut().enumerate() {
+            let current_x = self.lower_bounds[0] + step[0] * (index % width) as f64;
+            let current_y = self.lower_bounds[1] + step[1] * (index / width) as f64;
+
+            *value = self.source_module.get([current_x, current_y, current_z]);
+        }
     }
 }
 
-fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
-    let r = lat.to_radians().cos();
-    let x = r * lon.to_radians().cos();
-    let y = lat.to_radians().sin();
-    let z = r * lon.to_radians().sin();
+fn plane_step(extent: f64, pixels: usize, is_seamless: bool) -> f64 {
+    if is_seamless {
+        extent / pixels as f64
+    } else if pixels > 1 {
+        extent / (pixels - 1) as f64
+    } else {
+        0.0
+    }
+}
 
-    [x, y, z]
+/// Builds a noise map of the steepness of a 2-dimensional noise function.
+///
+/// Each value is the magnitude of the gradient of `noise` at the pixel's
+/// position, with the pixels mapped onto `x_bounds` and `y_bounds` in the same
+/// way as [`PlaneMapBuilder`]. Noise functions without an analytic derivative
+/// can be wrapped in [`FiniteDifference`](crate::FiniteDifference).
+pub fn build_slope_map<N>(
+    noise: &N,
+    width: usize,
+    height: usize,
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+) -> NoiseMap
+where
+    N: NoiseFnWithDerivative<f64, 2> + ?Sized,
+{
+    let x_step = plane_step(x_bounds.1 - x_bounds.0, width, false);
+    let y_step = plane_step(y_bounds.1 - y_bounds.0, height, false);
+
+    fill_map((width, height), |x, y| {
+        let current_x = x_bounds.0 + x_step * x as f64;
+        let current_y = y_bounds.0 + y_step * y as f64;
+
+        let [dx, dy] = noise.get_derivative([current_x, current_y]);
+
+        dx.hypot(dy)
+    })
+}
+
+/// Fills a new noise map of the given size with the value of `f` at each pixel.
+fn fill_map<F>((width, height): (usize, usize), f: F) -> NoiseMap
+where
+    F: Fn(usize, usize) -> f64,
+{
+    let mut result_map = NoiseMap::new(width, height);
+
+    for y in 0..height {
+        for x in 0..width {
+            result_map[(x, y)] = f(x, y);
+        }
+    }
+
+    result_map
+}
+
+/// Fills a new noise map of the given size with the value of `f` at each pixel,
+/// computing the rows in parallel.
+///
+/// Each value only depends on its pixel coordinates, so the result does not
+/// depend on the number of threads or the order the rows are computed in.
+#[cfg(feature = "rayon")]
+fn par_fill_map<F>((width, height): (usize, usize), f: F) -> NoiseMap
+where
+    F: Fn(usize, usize) -> f64 + Sync,
+{
+    let mut result_map = NoiseMap::new(width, height);
+
+    if width == 0 || height == 0 {
+        return result_map;
+    }
+
+    result_map
+        .as_mut_slice()
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            for (x, value) in row.iter_mut().enumerate() {
+                *value = f(x, y);
+            }
+        });
+
+    result_map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FiniteDifference;
+    #[cfg(feature = "rayon")]
+    use crate::{Fbm, Perlin};
+
+    /// Linear ramp rising by 0.3 along x and falling by 0.4 along y.
+    struct Ramp;
+
+    impl NoiseFn<f64, 2> for Ramp {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            0.3 * point[0] - 0.4 * point[1]
+        }
+    }
+
+    impl NoiseFnWithDerivative<f64, 2> for Ramp {
+        fn get_derivative(&self, _point: [f64; 2]) -> [f64; 2] {
+            [0.3, -0.4]
+        }
+    }
+
+    #[test]
+    fn slope_map_of_ramp_is_constant() {
+        let analytic = build_slope_map(&Ramp, 16, 12, (-3.0, 5.0), (1.0, 2.0));
+        let approximated = build_slope_map(
+            &FiniteDifference::new(Ramp),
+            16,
+            12,
+            (-3.0, 5.0),
+            (1.0, 2.0),
+        );
+
+        assert_eq!(analytic.size(), (16, 12));
+        for (analytic, approximated) in analytic.iter().zip(approximated.iter()) {
+            assert!((analytic - 0.5).abs() < 1e-12);
+            assert!((approximated - 0.5).abs() < 1e-9);
+        }
+    }
+
+    /// Records the distance from the origin of each sampled point.
+    struct Radius;
+
+    impl NoiseFn<f64, 3> for Radius {
+        fn get(&self, point: [f64; 3]) -> f64 {
+            (point[0] * point[0] + point[1] * point[1] + point[2] * point[2]).sqrt()
+        }
+    }
+
+    /// Returns the x coordinate of each sampled point.
+    struct AxisX;
+
+    impl NoiseFn<f64, 3> for AxisX {
+        fn get(&self, point: [f64; 3]) -> f64 {
+            point[0]
+        }
+    }
+
+    #[test]
+    fn sphere_map_samples_unit_sphere() {
+        let map = SphereMapBuilder::new(Radius)
+            .set_size(36, 18)
+            .set_bounds(-90.0, 90.0, -180.0, 180.0)
+            .build();
+
+        for value in map.iter() {
+            assert!((value - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn sphere_map_wraps_around_longitude() {
+        let (width, height) = (36, 18);
+        let map = SphereMapBuilder::new(AxisX)
+            .set_size(width, height)
+            .set_bounds(-90.0, 90.0, -180.0, 180.0)
+            .build();
+
+        // One more step east of the last column lands back on the first.
+        let wrapped = SphereMapBuilder::new(AxisX)
+            .set_size(width, height)
+            .set_bounds(-90.0, 90.0, -170.0, 190.0)
+            .build();
+
+        for y in 0..height {
+            assert!((wrapped[(width - 1, y)] - map[(0, y)]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn cylinder_map_is_seamless_around_axis() {
+        let (width, height) = (90, 12);
+        let map = CylinderMapBuilder::new(crate::Perlin::new(5))
+            .set_size(width, height)
+            .set_angle_bounds(0.0, 360.0)
+            .set_height_bounds(0.0, 3.0)
+            .build();
+
+        for y in 0..height {
+            let max_step = (1..width)
+                .map(|x| (map[(x, y)] - map[(x - 1, y)]).abs())
+                .fold(0.0, f64::max);
+            let seam = (map[(0, y)] - map[(width - 1, y)]).abs();
+
+            assert!(seam <= max_step);
+        }
+    }
+
+    #[test]
+    fn plane_map_samples_both_bounds() {
+        let map = PlaneMapBuilder::new_fn(|point: [f64; 2]| Ramp.get(point))
+            .set_size(9, 5)
+            .set_x_bounds(-3.0, 5.0)
+            .set_y_bounds(1.0, 2.0)
+            .build();
+
+        assert!((map[(0, 0)] - Ramp.get([-3.0, 1.0])).abs() < 1e-12);
+        assert!((map[(8, 4)] - Ramp.get([5.0, 2.0])).abs() < 1e-12);
+    }
+
+    #[test]
+    fn adjacent_plane_chunks_share_their_seam() {
+        let chunk = |x_lower: f64| {
+            PlaneMapBuilder::new(crate::Perlin::new(9))
+                .set_size(33, 17)
+                .set_x_bounds(x_lower, x_lower + 2.0)
+                .set_y_bounds(-0.5, 1.5)
+                .build()
+        };
+        let west = chunk(-1.3);
+        let east = chunk(0.7);
+
+        for y in 0..17 {
+            assert_eq!(west[(32, y)], east[(0, y)]);
+        }
+    }
+
+    #[test]
+    fn volume_samples_both_bounds() {
+        let sample = |point: [f64; 3]| point[0] + 10.0 * point[1] + 100.0 * point[2];
+        let volume = VolumeBuilder::new(crate::FnNoise(sample))
+            .set_size(5, 3, 4)
+            .set_bounds([-1.0, 0.0, 2.0], [1.0, 1.0, 5.0])
+            .build();
+
+        assert_eq!(volume.size(), (5, 3, 4));
+        assert!((volume[(0, 0, 0)] - sample([-1.0, 0.0, 2.0])).abs() < 1e-12);
+        assert!((volume[(4, 2, 3)] - sample([1.0, 1.0, 5.0])).abs() < 1e-12);
+        assert!((volume[(2, 1, 1)] - sample([0.0, 0.5, 3.0])).abs() < 1e-12);
+        assert_eq!(volume.z_slice(1)[(2, 1)], volume[(2, 1, 1)]);
+    }
+
+    #[cfg(feature = "rayon")]
+    fn assert_maps_identical(a: &NoiseMap, b: &NoiseMap) {
+        assert_eq!(a.size(), b.size());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn par_build_matches_build() {
+        let plane = PlaneMapBuilder::new(Fbm::<Perlin>::new(3))
+            .set_size(67, 41)
+            .set_is_seamless(true);
+        assert_maps_identical(&plane.build(), &plane.par_build());
+
+        let sphere = SphereMapBuilder::new(Perlin::new(3))
+            .set_size(67, 41)
+            .set_bounds(-90.0, 90.0, -180.0, 180.0);
+        assert_maps_identical(&sphere.build(), &sphere.par_build());
+
+        let cylinder = CylinderMapBuilder::new(Perlin::new(3)).set_size(67, 41);
+        assert_maps_identical(&cylinder.build(), &cylinder.par_build());
+
+        let perlin = Perlin::new(3);
+        let plane = PlaneMapBuilder::new_fn(|point: [f64; 2]| perlin.get(point)).set_size(67, 41);
+        assert_maps_identical(&plane.build(), &plane.par_build());
+        let plane = PlaneMapBuilder::new_fn(|point: [f64; 3]| perlin.get(point)).set_size(67, 41);
+        assert_maps_identical(&plane.build(), &plane.par_build());
+        let plane = PlaneMapBuilder::new_fn(|point: [f64; 4]| perlin.get(point)).set_size(67, 41);
+        assert_maps_identical(&plane.build(), &plane.par_build());
+
+        let volume = VolumeBuilder::new(Perlin::new(3)).set_size(13, 7, 5);
+        assert_eq!(volume.build().as_slice(), volume.par_build().as_slice());
+    }
 }
@@ -0,0 +1,161 @@
+use crate::utils::noise_map::NoiseMap;
+use alloc::{
+    slice::{Iter, IterMut},
+    vec::Vec,
+};
+use core::ops::{Index, IndexMut};
+
+/// A 3-dimensional grid of noise values, as built by
+/// [`VolumeBuilder`](crate::utils::VolumeBuilder).
+///
+/// The values are stored in a single `Vec` with _x_ varying fastest, then _y_,
+/// then _z_, so the value at `(x, y, z)` is at index
+/// `x + y * width + z * width * height`, and each _z_ slice is a contiguous
+/// [`NoiseMap`]-shaped block.
+#[derive(Clone, Debug, Default)]
+pub struct NoiseVolume {
+    size: (usize, usize, usize),
+    border_value: f64,
+    map: Vec<f64>,
+}
+
+impl NoiseVolume {
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            size: (width, height, depth),
+            border_value: 0.0,
+            map: vec![0.0; width * height * depth],
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    pub fn set_border_value(self, border_value: f64) -> Self {
+        Self {
+            border_value,
+            ..self
+        }
+    }
+
+    pub fn border_value(&self) -> f64 {
+        self.border_value
+    }
+
+    pub fn iter(&self) -> Iter<'_, f64> {
+        self.map.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, f64> {
+        self.map.iter_mut()
+    }
+
+    /// Returns all of the values in _x_-fastest order.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.map
+    }
+
+    /// Returns all of the values in _x_-fastest order, as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [f64] {
+        &mut self.map
+    }
+
+    pub fn set_value(&mut self, x: usize, y: usize, z: usize, value: f64) {
+        if let Some(index) = self.index_of(x, y, z) {
+            self.map[index] = value;
+        }
+    }
+
+    pub fn get_value(&self, x: usize, y: usize, z: usize) -> f64 {
+        match self.index_of(x, y, z) {
+            Some(index) => self.map[index],
+            None => self.border_value,
+        }
+    }
+
+    /// Copies the values with the given _z_ coordinate into a noise map, for
+    /// example to render a cross-section of the volume.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z` is not less than the depth of the volume.
+    pub fn z_slice(&self, z: usize) -> NoiseMap {
+        let (width, height, depth) = self.size;
+        assert!(
+            z < depth,
+            "slice {} out of bounds for NoiseVolume of depth {}",
+            z,
+            depth
+        );
+
+        let mut slice = NoiseMap::new(width, height).set_border_value(self.border_value);
+        let start = z * width * height;
+        for (value, &source) in slice
+            .iter_mut()
+            .zip(&self.map[start..start + width * height])
+        {
+            *value = source;
+        }
+
+        slice
+    }
+
+    fn index_of(&self, x: usize, y: usize, z: usize) -> Option<usize> {
+        let (width, height, depth) = self.size;
+
+        if x < width && y < height && z < depth {
+            Some(x + (y + z * height) * width)
+        } else {
+            None
+        }
+    }
+}
+
+impl Index<(usize, usize, usize)> for NoiseVolume {
+    type Output = f64;
+
+    fn index(&self, (x, y, z): (usize, usize, usize)) -> &Self::Output {
+        match self.index_of(x, y, z) {
+            Some(index) => &self.map[index],
+            None => &self.border_value,
+        }
+    }
+}
+
+impl IndexMut<(usize, usize, usize)> for NoiseVolume {
+    fn index_mut(&mut self, (x, y, z): (usize, usize, usize)) -> &mut Self::Output {
+        match self.index_of(x, y, z) {
+            Some(index) => &mut self.map[index],
+            None => panic!(
+                "index ({}, {}, {}) out of bounds for NoiseVolume of size {:?}",
+                x, y, z, self.size
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_are_stored_x_fastest() {
+        let mut volume = NoiseVolume::new(2, 3, 4).set_border_value(-1.0);
+        for (i, value) in volume.iter_mut().enumerate() {
+            *value = i as f64;
+        }
+
+        assert_eq!(volume.get_value(1, 0, 0), 1.0);
+        assert_eq!(volume.get_value(0, 1, 0), 2.0);
+        assert_eq!(volume.get_value(0, 0, 1), 6.0);
+        assert_eq!(volume[(1, 2, 3)], 23.0);
+        assert_eq!(volume.get_value(2, 0, 0), -1.0);
+
+        let slice = volume.z_slice(2);
+        assert_eq!(slice.size(), (2, 3));
+        assert_eq!(slice[(0, 0)], 12.0);
+        assert_eq!(slice[(1, 2)], 17.0);
+        assert_eq!(slice.border_value(), -1.0);
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{
+    core::worley::worley_2d_seed_point,
+    permutationtable::{NoiseHasher, PermutationTable},
+};
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(not(any(feature = "std", test)))]
+use num_traits::Float;
+
+/// Returns randomly placed but evenly spaced points within a rectangle, no two
+/// of which are closer than `min_dist`, for scattering objects such as trees
+/// or rocks.
+///
+/// `region` holds the lower and upper corners of the rectangle, which
+/// includes its lower edges but not its upper ones. The points are the seed
+/// points of the cells of [`Worley`](crate::Worley) noise with the same seed
+/// and a frequency of `1 / min_dist`, less those that are too close to a kept
+/// seed point of a neighboring cell which takes precedence. Whether a point is
+/// kept only depends on the seed and the cells around it, so every region
+/// containing a point agrees on it, and a large area can be filled one region
+/// at a time.
+///
+/// The points are returned in row-major order of their cells, and are the
+/// same for the same arguments on every platform.
+///
+/// # Panics
+///
+/// Panics if `min_dist` is not positive.
+pub fn poisson_points(region: ([f64; 2], [f64; 2]), min_dist: f64, seed: u32) -> Vec<[f64; 2]> {
+    assert!(
+        min_dist > 0.0,
+        "poisson_points min_dist must be positive, got {}",
+        min_dist
+    );
+
+    let mut sampler = Sampler {
+        hasher: PermutationTable::new(seed),
+        min_dist,
+        kept: BTreeMap::new(),
+    };
+    let (lower, upper) = region;
+
+    // Seed points are within half a cell of their lattice point.
+    let first = lower.map(|x| (x / min_dist - 0.5).floor() as isize);
+    let last = upper.map(|x| (x / min_dist + 0.5).ceil() as isize);
+
+    let mut points = Vec::new();
+    for y in first[1]..=last[1] {
+        for x in first[0]..=last[0] {
+            let point = sampler.seed_point([x, y]);
+            let inside = (0..2).all(|axis| lower[axis] <= point[axis] && point[axis] < upper[axis]);
+
+            if inside && sampler.is_kept([x, y]) {
+                points.push(point);
+            }
+        }
+    }
+
+    points
+}
+
+struct Sampler {
+    hasher: PermutationTable,
+    min_dist: f64,
+    // Whether the seed point of each cell visited so far is kept.
+    kept: BTreeMap<[isize; 2], bool>,
+}
+
+impl Sampler {
+    fn seed_point(&self, cell: [isize; 2]) -> [f64; 2] {
+        let point = worley_2d_seed_point(&self.hasher, cell, 1.0, 0.0);
+
+        [point.x * self.min_dist, point.y * self.min_dist]
+    }
+
+    /// The order in which conflicting seed points are kept: a hash of the cell,
+    /// with ties broken by its position.
+    fn precedence(&self, [x, y]: [isize; 2]) -> (usize, isize, isize) {
+        (self.hasher.hash(&[x, y, 1]), y, x)
+    }
+
+    /// Returns whether the seed point of `cell` is kept, which it is unless it
+    /// is closer than `min_dist` to the kept seed point of a cell that takes
+    /// precedence over it. Seed points are within half a cell of their lattice
+    /// point, so only the adjacent cells can be that close.
+    fn is_kept(&mut self, cell: [isize; 2]) -> bool {
+        if let Some(&kept) = self.kept.get(&cell) {
+            return kept;
+        }
+
+        let point = self.seed_point(cell);
+        let precedence = self.precedence(cell);
+
+        // The precedence falls with every step, so this always ends.
+        let mut kept = true;
+        for neighbor in (-1..=1).flat_map(|dy| (-1..=1).map(move |dx| [cell[0] + dx, cell[1] + dy]))
+        {
+            if neighbor == cell || self.precedence(neighbor) > precedence {
+                continue;
+            }
+
+            let other = self.seed_point(neighbor);
+            let (dx, dy) = (other[0] - point[0], other[1] - point[1]);
+            if (dx * dx + dy * dy).sqrt() < self.min_dist && self.is_kept(neighbor) {
+                kept = false;
+                break;
+            }
+        }
+
+        self.kept.insert(cell, kept);
+        kept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGION: ([f64; 2], [f64; 2]) = ([-3.0, 1.5], [7.0, 9.5]);
+
+    #[test]
+    fn points_are_spaced_and_cover_the_region() {
+        let min_dist = 0.25;
+        let points = poisson_points(REGION, min_dist, 3);
+
+        for (i, a) in points.iter().enumerate() {
+            assert!((-3.0..7.0).contains(&a[0]) && (1.5..9.5).contains(&a[1]));
+            for b in &points[i + 1..] {
+                assert!(((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)).sqrt() >= min_dist);
+            }
+        }
+
+        // About half of the cells keep their seed point, close to the density
+        // of dart throwing, where it becomes hard to fit in further points.
+        let cells = 10.0 * 8.0 / (min_dist * min_dist);
+        assert!(
+            points.len() as f64 > cells * 0.45,
+            "{} points",
+            points.len()
+        );
+    }
+
+    #[test]
+    fn points_are_deterministic_and_agree_across_regions() {
+        let points = poisson_points(REGION, 0.5, 7);
+        assert_eq!(points, poisson_points(REGION, 0.5, 7));
+        assert_ne!(points, poisson_points(REGION, 0.5, 8));
+
+        let left = poisson_points(([-3.0, 1.5], [2.0, 9.5]), 0.5, 7);
+        let right = poisson_points(([2.0, 1.5], [7.0, 9.5]), 0.5, 7);
+        assert_eq!(
+            left,
+            points
+                .iter()
+                .copied()
+                .filter(|point| point[0] < 2.0)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(left.len() + right.len(), points.len());
+    }
+}